@@ -0,0 +1,470 @@
+//! Compiles shaders from a build script, so that the shipped binaries don't compile them at startup.
+//!
+//! The shaders are compiled by the serialization device of the engine, with the same glslang and DXC
+//! paths as at runtime, to SPIR-V for Vulkan and to GLSL for OpenGL. The compiled shaders are written
+//! to `OUT_DIR`, with a Rust module that embeds them as `ShaderSource` constants :
+//!
+//! ```ignore
+//! // build.rs
+//! use diligent::{ShaderLanguage, ShaderType};
+//! use diligent_build::{BuildShader, ShaderBuild};
+//!
+//! fn main() {
+//!     ShaderBuild::new()
+//!         .search_path("shaders")
+//!         .shader(
+//!             BuildShader::builder()
+//!                 .name("CUBE_VS")
+//!                 .path("cube.vsh")
+//!                 .shader_type(ShaderType::Vertex)
+//!                 .source_language(ShaderLanguage::HLSL)
+//!                 .build()
+//!                 .unwrap_or_else(|error| panic!("{error}")),
+//!         )
+//!         .compile("shaders.rs")
+//!         .unwrap_or_else(|error| panic!("{error}"));
+//! }
+//! ```
+//!
+//! ```ignore
+//! mod shaders {
+//!     include!(concat!(env!("OUT_DIR"), "/shaders.rs"));
+//! }
+//!
+//! let shader_ci = ShaderCreateInfo::builder()
+//!     .source(shaders::vulkan::CUBE_VS)
+//!     .source_language(shaders::vulkan::SOURCE_LANGUAGE)
+//!     .shader_type(ShaderType::Vertex)
+//!     .build();
+//! ```
+//!
+//! The generated module has a submodule per target : `vulkan` with the SPIR-V as `ShaderSource::ByteCode`,
+//! and `gl` with the GLSL as `ShaderSource::SourceCode`, since the OpenGL backend does not accept bytecode.
+//! The SPIR-V keeps the entry point of the source, the entry point of the GLSL is `main`.
+//! The SPIR-V is embedded with the 4 bytes alignment of its words.
+
+use std::{
+    collections::HashSet,
+    fmt::{Display, Write},
+    path::{Path, PathBuf},
+    sync::Arc,
+};
+
+use diligent::{
+    ArchiveDeviceDataFlags, RenderDeviceType, SerializationDeviceCreateInfo, ShaderCompileError,
+    ShaderCompiler, ShaderCreateInfo, ShaderLanguage, ShaderSource, ShaderSourceInputStreamFactory,
+    ShaderType, TrackingShaderSource, get_archiver_factory,
+};
+
+/// The version of the engine crate the shaders are described with, for the build scripts that don't depend on it.
+pub use diligent;
+
+#[cfg(not(any(feature = "vulkan", feature = "opengl")))]
+compile_error!("diligent_build needs at least one of the vulkan and opengl features");
+
+/// A backend the shaders are compiled for.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Target {
+    #[cfg(feature = "vulkan")]
+    Vulkan,
+    #[cfg(feature = "opengl")]
+    GL,
+}
+
+impl Target {
+    /// The targets enabled by the features of the crate.
+    pub const ALL: &'static [Target] = &[
+        #[cfg(feature = "vulkan")]
+        Target::Vulkan,
+        #[cfg(feature = "opengl")]
+        Target::GL,
+    ];
+
+    fn device_type(self) -> RenderDeviceType {
+        match self {
+            #[cfg(feature = "vulkan")]
+            Target::Vulkan => RenderDeviceType::VULKAN,
+            #[cfg(feature = "opengl")]
+            Target::GL => RenderDeviceType::GL,
+        }
+    }
+
+    fn device_flags(self) -> ArchiveDeviceDataFlags {
+        match self {
+            #[cfg(feature = "vulkan")]
+            Target::Vulkan => ArchiveDeviceDataFlags::Vulkan,
+            #[cfg(feature = "opengl")]
+            Target::GL => ArchiveDeviceDataFlags::GL,
+        }
+    }
+
+    fn module_name(self) -> &'static str {
+        match self {
+            #[cfg(feature = "vulkan")]
+            Target::Vulkan => "vulkan",
+            #[cfg(feature = "opengl")]
+            Target::GL => "gl",
+        }
+    }
+
+    fn extension(self) -> &'static str {
+        match self {
+            #[cfg(feature = "vulkan")]
+            Target::Vulkan => "spv",
+            #[cfg(feature = "opengl")]
+            Target::GL => "glsl",
+        }
+    }
+
+    /// The `ShaderLanguage` of the generated constants.
+    fn source_language(self) -> &'static str {
+        match self {
+            #[cfg(feature = "vulkan")]
+            Target::Vulkan => "ByteCode",
+            #[cfg(feature = "opengl")]
+            Target::GL => "GLSLVerbatim",
+        }
+    }
+
+    /// The expression of the `ShaderSource` that embeds the compiled file at `path`.
+    fn source_expression(self, path: &Path) -> String {
+        match self {
+            // SPIR-V is made of 32-bit words : the bytes are embedded with the alignment of a word
+            #[cfg(feature = "vulkan")]
+            Target::Vulkan => format!(
+                "::diligent::ShaderSource::ByteCode({{\n            const ALIGNED: &super::Aligned<[u8]> = &super::Aligned(*include_bytes!({:?}));\n            &ALIGNED.0\n        }})",
+                path.display().to_string()
+            ),
+            #[cfg(feature = "opengl")]
+            Target::GL => format!(
+                "::diligent::ShaderSource::SourceCode(include_str!({:?}))",
+                path.display().to_string()
+            ),
+        }
+    }
+}
+
+/// A shader compiled by `ShaderBuild`.
+pub struct BuildShader {
+    name: String,
+    path: PathBuf,
+    shader_type: ShaderType,
+    entry_point: String,
+    source_language: ShaderLanguage,
+    compiler: ShaderCompiler,
+    macros: Vec<(String, String)>,
+}
+
+#[bon::bon]
+impl BuildShader {
+    /// Fails if `name` can not be used as the name of a Rust constant.
+    #[builder]
+    pub fn new(
+        /// The name of the generated constants, such as `CUBE_VS`. It is also the name of the compiled files.
+        #[builder(into)]
+        name: String,
+
+        /// The path of the source file, relative to the search paths.
+        #[builder(into)]
+        path: PathBuf,
+
+        shader_type: ShaderType,
+
+        #[builder(into, default = "main".to_owned())] entry_point: String,
+
+        #[builder(default)] source_language: ShaderLanguage,
+
+        #[builder(default)] compiler: ShaderCompiler,
+
+        #[builder(with =|macros: Vec<(impl Into<String>, impl Into<String>)>|
+            macros
+                .into_iter()
+                .map(|(name, def)| (name.into(), def.into()))
+                .collect())]
+        #[builder(default = Vec::new())]
+        macros: Vec<(String, String)>,
+    ) -> Result<Self, BuildError> {
+        if !is_constant_name(&name) {
+            return Err(BuildError::InvalidName { name });
+        }
+
+        Ok(BuildShader {
+            name,
+            path,
+            shader_type,
+            entry_point,
+            source_language,
+            compiler,
+            macros,
+        })
+    }
+}
+
+/// Whether `name` is a Rust identifier that is not a keyword, nor a constant of the generated modules.
+fn is_constant_name(name: &str) -> bool {
+    const KEYWORDS: &[&str] = &[
+        "_", "Self", "abstract", "as", "async", "await", "become", "box", "break", "const",
+        "continue", "crate", "do", "dyn", "else", "enum", "extern", "false", "final", "fn", "for",
+        "gen", "if", "impl", "in", "let", "loop", "macro", "match", "mod", "move", "mut",
+        "override", "priv", "pub", "ref", "return", "self", "static", "struct", "super", "trait",
+        "true", "try", "type", "typeof", "unsafe", "unsized", "use", "virtual", "where", "while",
+        "yield",
+    ];
+
+    let mut chars = name.chars();
+    chars
+        .next()
+        .is_some_and(|first| first.is_ascii_alphabetic() || first == '_')
+        && chars.all(|char| char.is_ascii_alphanumeric() || char == '_')
+        && !KEYWORDS.contains(&name)
+        && name != "SOURCE_LANGUAGE"
+}
+
+#[derive(Debug)]
+pub enum BuildError {
+    /// `compile` was not called from a build script.
+    MissingOutDir,
+    /// The name of a shader is not a Rust identifier, or is used by another shader of the build.
+    InvalidName {
+        name: String,
+    },
+    CreateDevice(diligent::Error),
+    Compile(ShaderCompileError),
+    /// The engine compiled the shader, but not for one of the targets.
+    MissingBytecode {
+        name: String,
+        target: Target,
+    },
+    Write {
+        path: PathBuf,
+        error: std::io::Error,
+    },
+}
+
+impl std::error::Error for BuildError {}
+
+impl Display for BuildError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            BuildError::MissingOutDir => f.write_str(
+                "OUT_DIR is not set, the shaders can only be compiled by a build script",
+            ),
+            BuildError::InvalidName { name } => write!(
+                f,
+                "'{name}' can not be the name of a shader constant, or is used by another shader"
+            ),
+            BuildError::CreateDevice(error) => {
+                write!(f, "failed to create the serialization device : {error}")
+            }
+            BuildError::Compile(error) => write!(f, "{error}"),
+            BuildError::MissingBytecode { name, target } => {
+                write!(f, "the engine did not compile '{name}' for {target:?}")
+            }
+            BuildError::Write { path, error } => {
+                write!(f, "failed to write {} : {error}", path.display())
+            }
+        }
+    }
+}
+
+/// Compiles a set of shaders for each target, and generates the module that embeds them.
+pub struct ShaderBuild {
+    search_paths: Vec<PathBuf>,
+    targets: Vec<Target>,
+    shaders: Vec<BuildShader>,
+    dx_compiler_path: Option<PathBuf>,
+}
+
+impl Default for ShaderBuild {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl ShaderBuild {
+    /// A build for all the targets enabled by the features of the crate.
+    pub fn new() -> Self {
+        ShaderBuild {
+            search_paths: Vec::new(),
+            targets: Target::ALL.to_vec(),
+            shaders: Vec::new(),
+            dx_compiler_path: None,
+        }
+    }
+
+    /// Adds a directory in which the shaders and their `#include` files are searched,
+    /// relative to the directory of the manifest.
+    pub fn search_path(mut self, path: impl AsRef<Path>) -> Self {
+        self.search_paths.push(path.as_ref().to_path_buf());
+        self
+    }
+
+    pub fn targets(mut self, targets: &[Target]) -> Self {
+        self.targets = targets.to_vec();
+        self
+    }
+
+    pub fn shader(mut self, shader: BuildShader) -> Self {
+        self.shaders.push(shader);
+        self
+    }
+
+    /// The DXC library used to compile HLSL to SPIR-V. glslang is used when it is not set.
+    pub fn dx_compiler_path(mut self, path: impl AsRef<Path>) -> Self {
+        self.dx_compiler_path = Some(path.as_ref().to_path_buf());
+        self
+    }
+
+    /// Compiles the shaders to `OUT_DIR/shaders`, and writes the module that embeds them to `OUT_DIR/module_name`.
+    ///
+    /// Cargo is told to run the build script again when one of the shader files or their `#include` files change.
+    /// The warnings of the compiler are reported as cargo warnings.
+    pub fn compile(&self, module_name: impl AsRef<Path>) -> Result<PathBuf, BuildError> {
+        let out_dir = PathBuf::from(std::env::var_os("OUT_DIR").ok_or(BuildError::MissingOutDir)?);
+
+        // The constants and the compiled files of two shaders with the same name would collide
+        let mut names = HashSet::new();
+        if let Some(shader) = self
+            .shaders
+            .iter()
+            .find(|shader| !names.insert(&shader.name))
+        {
+            return Err(BuildError::InvalidName {
+                name: shader.name.clone(),
+            });
+        }
+
+        let shaders_dir = out_dir.join("shaders");
+        std::fs::create_dir_all(&shaders_dir).map_err(|error| BuildError::Write {
+            path: shaders_dir.clone(),
+            error,
+        })?;
+
+        let create_info = match &self.dx_compiler_path {
+            Some(path) => SerializationDeviceCreateInfo::builder()
+                .vulkan_dx_compiler_path(path)
+                .build(),
+            None => SerializationDeviceCreateInfo::builder().build(),
+        };
+
+        let device = get_archiver_factory()
+            .create_serialization_device(&create_info)
+            .map_err(BuildError::CreateDevice)?;
+
+        let device_flags = self
+            .targets
+            .iter()
+            .fold(ArchiveDeviceDataFlags::None, |flags, target| {
+                flags | target.device_flags()
+            });
+
+        let search_paths: Arc<[PathBuf]> = self.search_paths.clone().into();
+
+        // The paths of the compiled files, per shader and per target
+        let mut compiled_paths = Vec::with_capacity(self.shaders.len());
+
+        for shader in &self.shaders {
+            let source = TrackingShaderSource::new(search_paths.clone());
+            let factory = ShaderSourceInputStreamFactory::from_source(source.clone());
+
+            let shader_ci = ShaderCreateInfo::builder()
+                .name(&shader.name)
+                .source(ShaderSource::FilePath(&shader.path))
+                .shader_type(shader.shader_type)
+                .entry_point(&shader.entry_point)
+                .macros(shader.macros.clone())
+                .source_language(shader.source_language)
+                .compiler(shader.compiler)
+                .shader_source_input_stream_factory(&factory)
+                .build();
+
+            let result = device.create_shader(&shader_ci, device_flags);
+
+            // The files are watched even when the compilation failed, so that fixing them runs the build again
+            for dependency in source.dependencies() {
+                println!("cargo::rerun-if-changed={}", dependency.display());
+            }
+
+            let (serialized_shader, output) = result.map_err(BuildError::Compile)?;
+
+            for warning in output.warnings() {
+                println!("cargo::warning={}: {warning}", shader.name);
+            }
+
+            let paths = self
+                .targets
+                .iter()
+                .map(|&target| {
+                    let bytecode = serialized_shader
+                        .get_device_shader(target.device_type())
+                        .and_then(|device_shader| device_shader.get_bytecode())
+                        .ok_or_else(|| BuildError::MissingBytecode {
+                            name: shader.name.clone(),
+                            target,
+                        })?;
+
+                    let path = shaders_dir.join(format!("{}.{}", shader.name, target.extension()));
+                    write_file(&path, bytecode)?;
+                    Ok(path)
+                })
+                .collect::<Result<Vec<_>, BuildError>>()?;
+
+            compiled_paths.push(paths);
+        }
+
+        let module_path = out_dir.join(module_name);
+        write_file(
+            &module_path,
+            self.generate_module(&compiled_paths).as_bytes(),
+        )?;
+
+        Ok(module_path)
+    }
+
+    fn generate_module(&self, compiled_paths: &[Vec<PathBuf>]) -> String {
+        let mut module = String::from("// Generated by diligent_build, do not edit.\n");
+
+        #[cfg(feature = "vulkan")]
+        if self.targets.contains(&Target::Vulkan) {
+            module.push_str(
+                "\n#[allow(dead_code)]\n#[repr(C, align(4))]\nstruct Aligned<B: ?Sized>(B);\n",
+            );
+        }
+
+        for (target_index, target) in self.targets.iter().enumerate() {
+            // Writing to a String can not fail
+            let _ = write!(
+                module,
+                "\npub mod {} {{\n    #![allow(dead_code)]\n\n    pub const SOURCE_LANGUAGE: ::diligent::ShaderLanguage = ::diligent::ShaderLanguage::{};\n",
+                target.module_name(),
+                target.source_language()
+            );
+
+            for (shader, paths) in self.shaders.iter().zip(compiled_paths) {
+                let _ = write!(
+                    module,
+                    "\n    pub const {}: ::diligent::ShaderSource<'static> =\n        {};\n",
+                    shader.name,
+                    target.source_expression(&paths[target_index])
+                );
+            }
+
+            module.push_str("}\n");
+        }
+
+        module
+    }
+}
+
+/// Writes `content` to `path`, unless the file already has this content,
+/// so that the crates that include it are not rebuilt needlessly.
+fn write_file(path: &Path, content: &[u8]) -> Result<(), BuildError> {
+    if std::fs::read(path).is_ok_and(|existing| existing == content) {
+        return Ok(());
+    }
+
+    std::fs::write(path, content).map_err(|error| BuildError::Write {
+        path: path.to_path_buf(),
+        error,
+    })
+}
@@ -0,0 +1,23 @@
+//! Derive macros for the `diligent` crate, re-exported by its `derive` feature.
+
+use proc_macro::TokenStream;
+use syn::{DeriveInput, parse_macro_input};
+
+mod shader_resources;
+mod vertex_layout;
+
+/// Implements `diligent::VertexLayout` for a `#[repr(C)]` struct whose fields implement `diligent::VertexAttribute`.
+#[proc_macro_derive(VertexLayout, attributes(vertex))]
+pub fn derive_vertex_layout(input: TokenStream) -> TokenStream {
+    vertex_layout::derive(parse_macro_input!(input as DeriveInput))
+        .unwrap_or_else(syn::Error::into_compile_error)
+        .into()
+}
+
+/// Implements `diligent::ShaderResources` for a struct whose fields are annotated with `#[resource(...)]`.
+#[proc_macro_derive(ShaderResources, attributes(resource))]
+pub fn derive_shader_resources(input: TokenStream) -> TokenStream {
+    shader_resources::derive(parse_macro_input!(input as DeriveInput))
+        .unwrap_or_else(syn::Error::into_compile_error)
+        .into()
+}
@@ -0,0 +1,172 @@
+use std::ffi::CString;
+
+use proc_macro2::TokenStream;
+use quote::quote;
+use syn::{
+    Data, DeriveInput, Fields, Ident, LitCStr, LitStr, Token, punctuated::Punctuated,
+    spanned::Spanned,
+};
+
+struct FieldOptions {
+    name: Option<LitStr>,
+    stages: Vec<Ident>,
+    variable_type: Option<Ident>,
+}
+
+fn field_options(field: &syn::Field) -> syn::Result<Option<FieldOptions>> {
+    let mut attrs = field
+        .attrs
+        .iter()
+        .filter(|attr| attr.path().is_ident("resource"))
+        .peekable();
+
+    if attrs.peek().is_none() {
+        return Ok(None);
+    }
+
+    let mut options = FieldOptions {
+        name: None,
+        stages: Vec::new(),
+        variable_type: None,
+    };
+
+    for attr in attrs {
+        attr.parse_nested_meta(|meta| {
+            if meta.path.is_ident("name") {
+                options.name = Some(meta.value()?.parse()?);
+                Ok(())
+            } else if meta.path.is_ident("stages") {
+                let stages =
+                    Punctuated::<Ident, Token![|]>::parse_separated_nonempty(meta.value()?)?;
+                options.stages.extend(stages);
+                Ok(())
+            } else if meta.path.is_ident("variable_type") {
+                options.variable_type = Some(meta.value()?.parse()?);
+                Ok(())
+            } else {
+                Err(meta
+                    .error("expected `name = \"...\"`, `stages = A | B` or `variable_type = T`"))
+            }
+        })?;
+    }
+
+    if options.stages.is_empty() {
+        return Err(syn::Error::new(
+            field.span(),
+            "the shader stages of the variable are required : `stages = Pixel`",
+        ));
+    }
+
+    Ok(Some(options))
+}
+
+pub(crate) fn derive(input: DeriveInput) -> syn::Result<TokenStream> {
+    let Data::Struct(data) = &input.data else {
+        return Err(syn::Error::new(
+            input.ident.span(),
+            "ShaderResources can only be derived for structs",
+        ));
+    };
+
+    let fields: Vec<_> = match &data.fields {
+        Fields::Named(fields) => fields.named.iter().collect(),
+        Fields::Unnamed(fields) => fields.unnamed.iter().collect(),
+        Fields::Unit => Vec::new(),
+    };
+
+    let mut variable_descs = Vec::new();
+    let mut bindings = Vec::new();
+    let mut static_bindings = Vec::new();
+
+    for (index, field) in fields.into_iter().enumerate() {
+        let Some(options) = field_options(field)? else {
+            return Err(syn::Error::new(
+                field.span(),
+                "every field needs a `#[resource(...)]` attribute",
+            ));
+        };
+
+        let (member, name) = match (&field.ident, options.name) {
+            (_, Some(name)) => (field_member(field, index), name.value()),
+            (Some(ident), None) => (quote!(#ident), ident.to_string()),
+            (None, None) => {
+                return Err(syn::Error::new(
+                    field.span(),
+                    "the fields of a tuple struct need a variable name : `name = \"...\"`",
+                ));
+            }
+        };
+
+        let c_name = CString::new(name.as_str())
+            .map_err(|_| syn::Error::new(field.span(), "the variable name contains a nul byte"))?;
+        let c_name = LitCStr::new(&c_name, field.span());
+
+        let stages = options.stages.iter();
+        let stages = quote!(#(::diligent::ShaderTypes::#stages)|*);
+
+        let variable_type = options
+            .variable_type
+            .unwrap_or_else(|| Ident::new("Mutable", field.span()));
+
+        variable_descs.push(quote! {
+            ::diligent::ShaderResourceVariableDesc::builder()
+                .name(#c_name)
+                .variable_type(::diligent::ShaderResourceVariableType::#variable_type)
+                .shader_stages(#stages)
+                .build()
+        });
+
+        let resources = quote!(&::diligent::ShaderResourceArray::resources(&self.#member));
+
+        if variable_type == "Static" {
+            static_bindings.push(quote! {
+                ::diligent::bind_static_variable(pipeline_state, #name, #stages, #resources, &mut errors);
+            });
+        } else {
+            bindings.push(quote! {
+                ::diligent::bind_srb_variable(srb, #name, #stages, #resources, &mut errors);
+            });
+        }
+    }
+
+    let struct_name = &input.ident;
+    let (impl_generics, ty_generics, where_clause) = input.generics.split_for_impl();
+
+    Ok(quote! {
+        impl #impl_generics ::diligent::ShaderResources for #struct_name #ty_generics #where_clause {
+            fn variable_descs() -> ::std::vec::Vec<::diligent::ShaderResourceVariableDesc<'static>> {
+                ::std::vec![#(#variable_descs),*]
+            }
+
+            #[allow(unused_mut, unused_variables)]
+            fn bind(
+                &self,
+                srb: &mut ::diligent::ShaderResourceBinding,
+            ) -> ::core::result::Result<(), ::std::vec::Vec<::diligent::ShaderResourceBindError>> {
+                let mut errors = ::std::vec::Vec::new();
+                #(#bindings)*
+                if errors.is_empty() { Ok(()) } else { Err(errors) }
+            }
+
+            #[allow(unused_mut, unused_variables)]
+            fn bind_static(
+                &self,
+                pipeline_state: &::diligent::PipelineState,
+            ) -> ::core::result::Result<(), ::std::vec::Vec<::diligent::ShaderResourceBindError>> {
+                let mut errors = ::std::vec::Vec::new();
+                #(#static_bindings)*
+                if errors.is_empty() { Ok(()) } else { Err(errors) }
+            }
+        }
+    })
+}
+
+fn field_member(field: &syn::Field, index: usize) -> TokenStream {
+    match &field.ident {
+        Some(ident) => quote!(#ident),
+        None => {
+            let index = syn::Index::from(index);
+            quote!(#index)
+        }
+    }
+}
@@ -0,0 +1,200 @@
+use proc_macro2::TokenStream;
+use quote::quote;
+use syn::{Data, DeriveInput, Fields, LitBool, LitInt};
+
+struct FieldOptions {
+    normalized: Option<bool>,
+    skip: bool,
+}
+
+struct StructOptions {
+    per_instance: bool,
+    step_rate: Option<LitInt>,
+}
+
+fn is_repr_c(input: &DeriveInput) -> syn::Result<bool> {
+    let mut repr_c = false;
+    for attr in input
+        .attrs
+        .iter()
+        .filter(|attr| attr.path().is_ident("repr"))
+    {
+        attr.parse_nested_meta(|meta| {
+            if meta.path.is_ident("C") {
+                repr_c = true;
+            }
+            // Skip the arguments of the other representations, such as `align(16)`
+            if meta.input.peek(syn::token::Paren) {
+                let _content;
+                syn::parenthesized!(_content in meta.input);
+            }
+            Ok(())
+        })?;
+    }
+    Ok(repr_c)
+}
+
+fn struct_options(input: &DeriveInput) -> syn::Result<StructOptions> {
+    let mut options = StructOptions {
+        per_instance: false,
+        step_rate: None,
+    };
+
+    for attr in input
+        .attrs
+        .iter()
+        .filter(|attr| attr.path().is_ident("vertex"))
+    {
+        attr.parse_nested_meta(|meta| {
+            if meta.path.is_ident("per_instance") {
+                options.per_instance = true;
+                Ok(())
+            } else if meta.path.is_ident("step_rate") {
+                options.step_rate = Some(meta.value()?.parse()?);
+                Ok(())
+            } else {
+                Err(meta.error("expected `per_instance` or `step_rate = N`"))
+            }
+        })?;
+    }
+
+    if let Some(step_rate) = &options.step_rate
+        && !options.per_instance
+    {
+        return Err(syn::Error::new(
+            step_rate.span(),
+            "`step_rate` requires `per_instance`",
+        ));
+    }
+
+    Ok(options)
+}
+
+fn field_options(field: &syn::Field) -> syn::Result<FieldOptions> {
+    let mut options = FieldOptions {
+        normalized: None,
+        skip: false,
+    };
+
+    for attr in field
+        .attrs
+        .iter()
+        .filter(|attr| attr.path().is_ident("vertex"))
+    {
+        attr.parse_nested_meta(|meta| {
+            if meta.path.is_ident("normalized") {
+                options.normalized = Some(if meta.input.peek(syn::Token![=]) {
+                    meta.value()?.parse::<LitBool>()?.value
+                } else {
+                    true
+                });
+                Ok(())
+            } else if meta.path.is_ident("skip") {
+                options.skip = true;
+                Ok(())
+            } else {
+                Err(meta.error("expected `normalized`, `normalized = bool` or `skip`"))
+            }
+        })?;
+    }
+
+    Ok(options)
+}
+
+pub(crate) fn derive(input: DeriveInput) -> syn::Result<TokenStream> {
+    let Data::Struct(data) = &input.data else {
+        return Err(syn::Error::new(
+            input.ident.span(),
+            "VertexLayout can only be derived for structs",
+        ));
+    };
+
+    if !is_repr_c(&input)? {
+        return Err(syn::Error::new(
+            input.ident.span(),
+            "VertexLayout requires #[repr(C)], so that the layout matches the vertex buffer",
+        ));
+    }
+
+    let options = struct_options(&input)?;
+
+    let (frequency, step_rate) = if options.per_instance {
+        let step_rate = options
+            .step_rate
+            .map_or_else(|| quote!(1), |step_rate| quote!(#step_rate));
+        (quote!(PerInstance), step_rate)
+    } else {
+        (quote!(PerVertex), quote!(1))
+    };
+
+    let fields = match &data.fields {
+        Fields::Named(fields) => fields.named.iter().collect(),
+        Fields::Unnamed(fields) => fields.unnamed.iter().collect(),
+        Fields::Unit => Vec::new(),
+    };
+
+    let mut attribute_counts = Vec::new();
+    let mut elements = Vec::new();
+
+    for (index, field) in fields.into_iter().enumerate() {
+        let field_options = field_options(field)?;
+        if field_options.skip {
+            continue;
+        }
+
+        let ty = &field.ty;
+        let member = match &field.ident {
+            Some(ident) => quote!(#ident),
+            None => {
+                let index = syn::Index::from(index);
+                quote!(#index)
+            }
+        };
+
+        let attribute = quote!(<#ty as ::diligent::VertexAttribute>);
+        let is_normalized = field_options.normalized.map_or_else(
+            || quote!(#attribute::IS_NORMALIZED),
+            |normalized| quote!(#normalized),
+        );
+
+        attribute_counts.push(quote!(#attribute::ATTRIBUTE_COUNT));
+
+        elements.push(quote! {
+            let offset = ::core::mem::offset_of!(Self, #member) as u32;
+            let attribute_size = ::core::mem::size_of::<#ty>() as u32 / #attribute::ATTRIBUTE_COUNT;
+            for attribute in 0..#attribute::ATTRIBUTE_COUNT {
+                elements.push(
+                    ::diligent::LayoutElement::builder()
+                        .input_index(input_index)
+                        .slot(slot)
+                        .value_type(#attribute::VALUE_TYPE)
+                        .num_components(#attribute::NUM_COMPONENTS)
+                        .is_normalized(#is_normalized)
+                        .relative_offset(offset + attribute * attribute_size)
+                        .stride(::core::mem::size_of::<Self>() as u32)
+                        .frequency(::diligent::InputElementFrequency::#frequency)
+                        .instance_data_step_rate(#step_rate)
+                        .build(),
+                );
+                input_index += 1;
+            }
+        });
+    }
+
+    let name = &input.ident;
+    let (impl_generics, ty_generics, where_clause) = input.generics.split_for_impl();
+
+    Ok(quote! {
+        impl #impl_generics ::diligent::VertexLayout for #name #ty_generics #where_clause {
+            const ATTRIBUTE_COUNT: u32 = 0 #(+ #attribute_counts)*;
+
+            #[allow(unused_mut, unused_variables)]
+            fn layout_elements(slot: u32, first_input_index: u32) -> ::std::vec::Vec<::diligent::LayoutElement> {
+                let mut elements = ::std::vec::Vec::with_capacity(Self::ATTRIBUTE_COUNT as usize);
+                let mut input_index = first_input_index;
+                #({ #elements })*
+                elements
+            }
+        }
+    })
+}
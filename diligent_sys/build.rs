@@ -17,6 +17,10 @@ fn configure_diligent_libs(diligent_install_dir: &Path) {
         diligent_install_dir.display()
     );
 
+    // The archiver uses the engine, so it must be linked first
+    #[cfg(feature = "archiver")]
+    println!("cargo::rustc-link-lib=static=Diligent-Archiver-static");
+
     println!("cargo::rustc-link-lib=static=DiligentCore");
 
     #[allow(unused)]
@@ -150,6 +154,9 @@ fn generate_diligent_c_bindings(
         builder
     }
 
+    #[cfg(feature = "archiver")]
+    let builder = builder.clang_arg("-DARCHIVER_SUPPORTED=1");
+
     #[cfg(feature = "vulkan")]
     let builder = configure_vulkan(builder);
 
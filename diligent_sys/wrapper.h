@@ -1,6 +1,7 @@
 #define DILIGENT_C_INTERFACE 1
 
 #include <DiligentCore/Common/interface/GeometryPrimitives.h>
+#include <DiligentCore/Common/interface/ThreadPool.h>
 
 extern unsigned int Diligent_GetGeometryPrimitiveVertexSize(GEOMETRY_PRIMITIVE_VERTEX_FLAGS VertexFlags);
 
@@ -42,6 +43,13 @@ extern void Diligent_CreateGeometryPrimitive(const GeometryPrimitiveAttributes*
 #include <DiligentCore/Graphics/GraphicsEngine/interface/TextureView.h>
 #include <DiligentCore/Graphics/GraphicsEngine/interface/TopLevelAS.h>
 
+#ifdef ARCHIVER_SUPPORTED
+#include <DiligentCore/Graphics/Archiver/interface/ArchiverFactory.h>
+#include <DiligentCore/Graphics/Archiver/interface/ArchiverFactoryLoader.h>
+#include <DiligentCore/Graphics/Archiver/interface/SerializationDevice.h>
+#include <DiligentCore/Graphics/Archiver/interface/SerializedShader.h>
+#endif
+
 #ifdef VULKAN_SUPPORTED
 
 #include <DiligentCore/Graphics/GraphicsEngineVulkan/interface/EngineFactoryVk.h>
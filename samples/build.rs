@@ -0,0 +1,51 @@
+#[cfg(feature = "vulkan")]
+#[path = "src/window/imgui/color_conversion.rs"]
+mod color_conversion;
+
+fn main() {
+    // The SPIR-V of the imgui shaders is compiled here, the other backends compile them at runtime
+    #[cfg(feature = "vulkan")]
+    compile_imgui_shaders();
+}
+
+#[cfg(feature = "vulkan")]
+fn compile_imgui_shaders() {
+    use color_conversion::{GAMMA_TO_LINEAR, SRGBA_TO_LINEAR};
+    use diligent_build::{
+        BuildShader, ShaderBuild, Target,
+        diligent::{ShaderLanguage, ShaderType},
+    };
+
+    let shader = |name: &str, path: &str, shader_type, macros: Vec<(&str, &str)>| {
+        BuildShader::builder()
+            .name(name)
+            .path(path)
+            .shader_type(shader_type)
+            .source_language(ShaderLanguage::GLSL)
+            .macros(macros)
+            .build()
+            .unwrap_or_else(|error| panic!("{error}"))
+    };
+
+    ShaderBuild::new()
+        .search_path("src/window/imgui/shaders")
+        .targets(&[Target::Vulkan])
+        .shader(shader("IMGUI_VS", "imgui.vsh", ShaderType::Vertex, vec![]))
+        .shader(shader(
+            "IMGUI_PS",
+            "imgui.psh",
+            ShaderType::Pixel,
+            vec![("SRGBA_TO_LINEAR(col)", "")],
+        ))
+        .shader(shader(
+            "IMGUI_PS_GAMMA",
+            "imgui.psh",
+            ShaderType::Pixel,
+            vec![
+                ("GAMMA_TO_LINEAR(Gamma)", GAMMA_TO_LINEAR),
+                ("SRGBA_TO_LINEAR(col)", SRGBA_TO_LINEAR),
+            ],
+        ))
+        .compile("imgui_shaders.rs")
+        .unwrap_or_else(|error| panic!("{error}"));
+}
@@ -0,0 +1,10 @@
+// The macros that convert the colors of the imgui shaders from sRGB to linear.
+// They are also used by the build script, which compiles the SPIR-V of the shaders.
+
+pub const GAMMA_TO_LINEAR: &str =
+    "((Gamma) < 0.04045 ? (Gamma) / 12.92 : pow(max((Gamma) + 0.055, 0.0) / 1.055, 2.4))";
+pub const SRGBA_TO_LINEAR: &str = r#"\
+col.r = GAMMA_TO_LINEAR(col.r);\
+col.g = GAMMA_TO_LINEAR(col.g);\
+col.b = GAMMA_TO_LINEAR(col.b);\
+col.a = 1.0 - GAMMA_TO_LINEAR(1.0 - col.a);"#;
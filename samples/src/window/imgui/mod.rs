@@ -1,3 +1,5 @@
 pub mod renderer;
 
+mod color_conversion;
+
 pub mod events;
@@ -0,0 +1,175 @@
+use std::{fmt::Display, sync::Once};
+
+use crate::engine_factory::EngineFactory;
+
+/// A struct whose size differs between the engine library and the bindings of this crate.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct StructSizeMismatch {
+    pub struct_name: &'static str,
+    /// The size reported by `EngineFactory::get_api_info`.
+    pub engine_size: usize,
+    /// The size of the `diligent_sys` type.
+    pub crate_size: usize,
+}
+
+impl Display for StructSizeMismatch {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "{} is {} bytes in the engine but {} bytes in the bindings",
+            self.struct_name, self.engine_size, self.crate_size
+        )
+    }
+}
+
+/// The engine library that was linked was not built from the headers used to generate the bindings.
+///
+/// The API versions are only reported to help finding the mismatching library :
+/// different versions with the same struct layouts are compatible.
+#[derive(Clone, Debug)]
+pub struct AbiMismatch {
+    /// The API version of the engine library.
+    pub engine_api_version: u32,
+    /// The API version of the headers used to generate the bindings.
+    pub crate_api_version: u32,
+    pub struct_sizes: Vec<StructSizeMismatch>,
+}
+
+impl std::error::Error for AbiMismatch {}
+
+impl Display for AbiMismatch {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "the engine library (API version {}) does not match the bindings (API version {})",
+            self.engine_api_version, self.crate_api_version
+        )?;
+
+        self.struct_sizes
+            .iter()
+            .try_for_each(|mismatch| write!(f, "\n    {mismatch}"))
+    }
+}
+
+macro_rules! check_struct_sizes {
+    ($mismatches:ident, $api_info:ident, $($getter:ident => $sys_type:ident),+ $(,)?) => {
+        $(
+            if $api_info.$getter() != std::mem::size_of::<diligent_sys::$sys_type>() {
+                $mismatches.push(StructSizeMismatch {
+                    struct_name: stringify!($sys_type),
+                    engine_size: $api_info.$getter(),
+                    crate_size: std::mem::size_of::<diligent_sys::$sys_type>(),
+                });
+            }
+        )+
+    };
+}
+
+/// Compares the struct sizes reported by the engine library with the ones of the bindings.
+///
+/// A mismatch means that the library was built from a different version of the engine than
+/// the bindings, and that passing any of the mismatching structs to the engine is undefined behavior.
+/// In debug builds, this check runs automatically when the first engine factory is created.
+pub fn verify_abi(engine_factory: &EngineFactory) -> Result<(), AbiMismatch> {
+    let api_info = engine_factory.get_api_info();
+
+    let mut struct_sizes = Vec::new();
+
+    check_struct_sizes!(
+        struct_sizes,
+        api_info,
+        struct_size => APIInfo,
+        render_target_blend_desc_size => RenderTargetBlendDesc,
+        blend_state_desc_size => BlendStateDesc,
+        buffer_desc_size => BufferDesc,
+        buffer_data_size => BufferData,
+        buffer_format_size => BufferFormat,
+        buffer_view_desc_size => BufferViewDesc,
+        stencil_op_desc_size => StencilOpDesc,
+        depth_stencil_state_desc_size => DepthStencilStateDesc,
+        sampler_properties_size => SamplerProperties,
+        texture_properties_size => TextureProperties,
+        render_device_info_size => RenderDeviceInfo,
+        draw_attribs_size => DrawAttribs,
+        dispatch_compute_attribs_size => DispatchComputeAttribs,
+        viewport_size => Viewport,
+        rect_size => Rect,
+        copy_texture_attribs_size => CopyTextureAttribs,
+        device_object_attribs_size => DeviceObjectAttribs,
+        graphics_adapter_info_size => GraphicsAdapterInfo,
+        display_mode_attribs_size => DisplayModeAttribs,
+        swap_chain_desc_size => SwapChainDesc,
+        full_screen_mode_desc_size => FullScreenModeDesc,
+        open_xr_attribs_size => OpenXRAttribs,
+        engine_create_info_size => EngineCreateInfo,
+        box_size => Box,
+        texture_format_attribs_size => TextureFormatAttribs,
+        texture_format_info_size => TextureFormatInfo,
+        texture_format_info_ext_size => TextureFormatInfoExt,
+        state_transition_desc_size => StateTransitionDesc,
+        layout_element_size => LayoutElement,
+        input_layout_desc_size => InputLayoutDesc,
+        sample_desc_size => SampleDesc,
+        shader_resource_variable_desc_size => ShaderResourceVariableDesc,
+        immutable_sampler_desc_size => ImmutableSamplerDesc,
+        pipeline_resource_layout_desc_size => PipelineResourceLayoutDesc,
+        pipeline_state_desc_size => PipelineStateDesc,
+        graphics_pipeline_desc_size => GraphicsPipelineDesc,
+        graphics_pipeline_state_create_info_size => GraphicsPipelineStateCreateInfo,
+        compute_pipeline_state_create_info_size => ComputePipelineStateCreateInfo,
+        ray_tracing_pipeline_desc_size => RayTracingPipelineDesc,
+        ray_tracing_pipeline_state_create_info_size => RayTracingPipelineStateCreateInfo,
+        rasterizer_state_desc_size => RasterizerStateDesc,
+        resource_mapping_entry_size => ResourceMappingEntry,
+        resource_mapping_create_info_size => ResourceMappingCreateInfo,
+        sampler_desc_size => SamplerDesc,
+        shader_desc_size => ShaderDesc,
+        shader_macro_size => ShaderMacro,
+        shader_macro_array_size => ShaderMacroArray,
+        shader_create_info_size => ShaderCreateInfo,
+        shader_resource_desc_size => ShaderResourceDesc,
+        depth_stencil_clear_value_size => DepthStencilClearValue,
+        optimized_clear_value_size => OptimizedClearValue,
+        texture_desc_size => TextureDesc,
+        texture_sub_res_data_size => TextureSubResData,
+        texture_data_size => TextureData,
+        mapped_texture_subresource_size => MappedTextureSubresource,
+        texture_view_desc_size => TextureViewDesc,
+    );
+
+    // The create infos of the backends are only part of the bindings when their feature is enabled
+    #[cfg(feature = "opengl")]
+    check_struct_sizes!(struct_sizes, api_info, engine_gl_create_info_size => EngineGLCreateInfo);
+
+    #[cfg(feature = "d3d11")]
+    check_struct_sizes!(struct_sizes, api_info, engine_d3d11_create_info_size => EngineD3D11CreateInfo);
+
+    #[cfg(feature = "d3d12")]
+    check_struct_sizes!(struct_sizes, api_info, engine_d3d12_create_info_size => EngineD3D12CreateInfo);
+
+    #[cfg(feature = "vulkan")]
+    check_struct_sizes!(struct_sizes, api_info, engine_vk_create_info_size => EngineVkCreateInfo);
+
+    if struct_sizes.is_empty() {
+        Ok(())
+    } else {
+        Err(AbiMismatch {
+            engine_api_version: api_info.api_version(),
+            crate_api_version: diligent_sys::DILIGENT_API_VERSION,
+            struct_sizes,
+        })
+    }
+}
+
+static VERIFY_ABI: Once = Once::new();
+
+/// Panics on the first factory created by a debug build if the engine library does not match the bindings.
+pub(crate) fn debug_verify_abi(engine_factory: &EngineFactory) {
+    if cfg!(debug_assertions) {
+        VERIFY_ABI.call_once(|| {
+            if let Err(mismatch) = verify_abi(engine_factory) {
+                panic!("{mismatch}");
+            }
+        });
+    }
+}
@@ -0,0 +1,178 @@
+use std::{ffi::CString, path::Path};
+
+use bitflags::bitflags;
+use bon::Builder;
+
+use crate::{
+    Boxed, Error,
+    data_blob::DataBlob,
+    debug_output::install_archiver_message_hook,
+    error::{MessageCapture, Operation},
+    graphics_types::{RenderDeviceType, Version},
+    object::Object,
+    render_device::RenderDevice,
+    shader::{Shader, ShaderCreateInfo, ShaderCreateInfoWrapper},
+    shader_compile_error::{ShaderCompileError, ShaderCompilerOutput},
+};
+
+bitflags! {
+    #[derive(Clone, Copy)]
+    pub struct ArchiveDeviceDataFlags: diligent_sys::ARCHIVE_DEVICE_DATA_FLAGS {
+        const None       = diligent_sys::ARCHIVE_DEVICE_DATA_FLAG_NONE as diligent_sys::ARCHIVE_DEVICE_DATA_FLAGS;
+        const D3D11      = diligent_sys::ARCHIVE_DEVICE_DATA_FLAG_D3D11 as diligent_sys::ARCHIVE_DEVICE_DATA_FLAGS;
+        const D3D12      = diligent_sys::ARCHIVE_DEVICE_DATA_FLAG_D3D12 as diligent_sys::ARCHIVE_DEVICE_DATA_FLAGS;
+        const GL         = diligent_sys::ARCHIVE_DEVICE_DATA_FLAG_GL as diligent_sys::ARCHIVE_DEVICE_DATA_FLAGS;
+        const GLES       = diligent_sys::ARCHIVE_DEVICE_DATA_FLAG_GLES as diligent_sys::ARCHIVE_DEVICE_DATA_FLAGS;
+        const Vulkan     = diligent_sys::ARCHIVE_DEVICE_DATA_FLAG_VULKAN as diligent_sys::ARCHIVE_DEVICE_DATA_FLAGS;
+        const MetalMacOS = diligent_sys::ARCHIVE_DEVICE_DATA_FLAG_METAL_MACOS as diligent_sys::ARCHIVE_DEVICE_DATA_FLAGS;
+        const MetalIOS   = diligent_sys::ARCHIVE_DEVICE_DATA_FLAG_METAL_IOS as diligent_sys::ARCHIVE_DEVICE_DATA_FLAGS;
+        const WebGPU     = diligent_sys::ARCHIVE_DEVICE_DATA_FLAG_WEBGPU as diligent_sys::ARCHIVE_DEVICE_DATA_FLAGS;
+    }
+}
+
+impl Default for ArchiveDeviceDataFlags {
+    fn default() -> Self {
+        ArchiveDeviceDataFlags::None
+    }
+}
+
+#[derive(Builder)]
+pub struct SerializationDeviceCreateInfo {
+    #[builder(default = Version { major: 1, minor: 0 })]
+    vulkan_api_version: Version,
+
+    #[builder(default = false)]
+    vulkan_supports_spirv14: bool,
+
+    /// The DXC library used to compile HLSL to SPIR-V. glslang is used when it is not set.
+    #[builder(with =|path : impl AsRef<Path>| CString::new(path.as_ref().to_string_lossy().as_bytes()).unwrap())]
+    vulkan_dx_compiler_path: Option<CString>,
+}
+
+impl From<&SerializationDeviceCreateInfo> for diligent_sys::SerializationDeviceCreateInfo {
+    fn from(value: &SerializationDeviceCreateInfo) -> Self {
+        // The members that are not set here have the same zero defaults as in the engine headers
+        let mut create_info: diligent_sys::SerializationDeviceCreateInfo =
+            unsafe { std::mem::zeroed() };
+
+        create_info.Vulkan.ApiVersion = diligent_sys::Version {
+            Major: value.vulkan_api_version.major,
+            Minor: value.vulkan_api_version.minor,
+        };
+        create_info.Vulkan.SupportsSpirv14 = value.vulkan_supports_spirv14;
+        create_info.Vulkan.DxCompilerPath = value
+            .vulkan_dx_compiler_path
+            .as_ref()
+            .map_or(std::ptr::null(), |path| path.as_ptr());
+
+        create_info
+    }
+}
+
+define_ported!(
+    ArchiverFactory,
+    diligent_sys::IArchiverFactory,
+    diligent_sys::IArchiverFactoryMethods : 9,
+    Object
+);
+
+pub fn get_archiver_factory() -> Boxed<ArchiverFactory> {
+    let archiver_factory = unsafe { diligent_sys::Diligent_GetArchiverFactory() };
+
+    let archiver_factory = Boxed::<ArchiverFactory>::new(archiver_factory).unwrap();
+    install_archiver_message_hook(&archiver_factory);
+    archiver_factory
+}
+
+impl ArchiverFactory {
+    /// Creates a device that compiles shaders and pipeline states for several backends,
+    /// without a GPU. It can be used in build scripts and tools.
+    pub fn create_serialization_device(
+        &self,
+        create_info: &SerializationDeviceCreateInfo,
+    ) -> Result<Boxed<SerializationDevice>, Error> {
+        let create_info = diligent_sys::SerializationDeviceCreateInfo::from(create_info);
+
+        let mut device_ptr = std::ptr::null_mut();
+        let capture = MessageCapture::begin();
+        unsafe_member_call!(
+            self,
+            ArchiverFactory,
+            CreateSerializationDevice,
+            &create_info,
+            &mut device_ptr
+        );
+        capture.boxed(device_ptr, Operation::CreateSerializationDevice, None)
+    }
+}
+
+define_ported!(
+    SerializationDevice,
+    diligent_sys::ISerializationDevice,
+    diligent_sys::ISerializationDeviceMethods : 9,
+    RenderDevice
+);
+
+impl SerializationDevice {
+    /// Compiles the shader for each backend of `device_flags`.
+    ///
+    /// The compiled shaders of the backends are read with `SerializedShader::get_device_shader`.
+    pub fn create_shader(
+        &self,
+        shader_ci: &ShaderCreateInfo,
+        device_flags: ArchiveDeviceDataFlags,
+    ) -> Result<(Boxed<SerializedShader>, ShaderCompilerOutput), ShaderCompileError> {
+        let mut shader_ptr = std::ptr::null_mut();
+        let mut data_blob_ptr = std::ptr::null_mut();
+
+        let shader_ci_wrapper = ShaderCreateInfoWrapper::from(shader_ci);
+
+        let archive_info = diligent_sys::ShaderArchiveInfo {
+            DeviceFlags: device_flags.bits(),
+        };
+
+        let capture = MessageCapture::begin();
+        unsafe_member_call!(
+            self,
+            SerializationDevice,
+            CreateShader,
+            std::ptr::from_ref(&shader_ci_wrapper),
+            &archive_info,
+            &mut shader_ptr,
+            &mut data_blob_ptr
+        );
+
+        let output = Boxed::<DataBlob>::new(data_blob_ptr)
+            .map(|data_blob| ShaderCompilerOutput::from_data_blob(&data_blob))
+            .unwrap_or_default();
+
+        // The shaders created by a serialization device always implement ISerializedShader
+        match Boxed::new(shader_ptr as *mut diligent_sys::ISerializedShader) {
+            Some(shader) => Ok((shader, output)),
+            None => Err(ShaderCompileError::new(
+                shader_ci,
+                output,
+                capture.messages(),
+            )),
+        }
+    }
+}
+
+define_ported!(
+    SerializedShader,
+    diligent_sys::ISerializedShader,
+    diligent_sys::ISerializedShaderMethods : 1,
+    Shader
+);
+
+impl SerializedShader {
+    /// The shader compiled for `device_type`, or `None` if it was not requested when the shader was created.
+    ///
+    /// Its bytecode is SPIR-V for Vulkan, and the GLSL source generated for the shader for OpenGL.
+    pub fn get_device_shader(&self, device_type: RenderDeviceType) -> Option<&Shader> {
+        let shader_ptr =
+            unsafe_member_call!(self, SerializedShader, GetDeviceShader, device_type.into());
+
+        unsafe { (shader_ptr as *const Shader).as_ref() }
+    }
+}
@@ -13,6 +13,7 @@ use crate::{
         ResourceStateVerify,
     },
     graphics_types::{ResourceState, ValueType},
+    object::Interface,
 };
 
 bitflags! {
@@ -201,6 +202,15 @@ define_ported!(
     DeviceObject
 );
 
+impl Interface for BottomLevelAS {
+    const IID: diligent_sys::INTERFACE_ID = diligent_sys::INTERFACE_ID {
+        Data1: 0xe56f5755,
+        Data2: 0xfe5e,
+        Data3: 0x496c,
+        Data4: [0xbf, 0xa7, 0xbc, 0xd5, 0x35, 0x36, 0x0f, 0xf7],
+    };
+}
+
 #[repr(transparent)]
 #[derive(Clone)]
 pub struct ScratchBufferSizes(pub(crate) diligent_sys::ScratchBufferSizes);
@@ -8,18 +8,21 @@ use bitflags::bitflags;
 use static_assertions::const_assert_eq;
 
 use crate::{
-    Boxed, BoxedFromNulError, MapType,
+    Boxed, Error, MapType,
     buffer_view::{BufferView, BufferViewDesc, BufferViewType},
     device_context::DeviceContext,
     device_object::{
         DeviceObject, DeviceObjectAttribs, ResourceStateNoTransition, ResourceStateTransition,
         ResourceStateVerify,
     },
+    error::{MessageCapture, Operation},
     graphics_types::{BindFlags, CpuAccessFlags, MemoryProperty, ResourceState, Usage},
+    object::Interface,
     resource_access_states,
 };
 
 #[derive(Clone, Copy)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum BufferMode {
     Formatted,
     Structured,
@@ -39,6 +42,7 @@ impl From<BufferMode> for diligent_sys::BUFFER_MODE {
 
 bitflags! {
     #[derive(Clone, Copy)]
+    #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
     pub struct MiscBufferFlags: diligent_sys::MISC_BUFFER_FLAGS {
         const None            = diligent_sys::MISC_BUFFER_FLAG_NONE as diligent_sys::MISC_BUFFER_FLAGS;
         const SparceAliasing  = diligent_sys::MISC_BUFFER_FLAG_SPARSE_ALIASING as diligent_sys::MISC_BUFFER_FLAGS;
@@ -154,21 +158,32 @@ define_ported!(
     DeviceObject
 );
 
+impl Interface for Buffer {
+    const IID: diligent_sys::INTERFACE_ID = diligent_sys::INTERFACE_ID {
+        Data1: 0xec47ead3,
+        Data2: 0xa2c4,
+        Data3: 0x44f2,
+        Data4: [0x81, 0xc5, 0x52, 0x48, 0xd1, 0x4f, 0x10, 0xe4],
+    };
+}
+
 impl Buffer {
     pub fn desc(&self) -> &BufferDesc<'_> {
         let desc_ptr = unsafe_member_call!(self, DeviceObject, GetDesc);
         unsafe { &*(desc_ptr as *const BufferDesc) }
     }
 
-    pub fn create_view(
-        &self,
-        view_desc: &BufferViewDesc,
-    ) -> Result<Boxed<BufferView>, BoxedFromNulError> {
+    pub fn create_view(&self, view_desc: &BufferViewDesc) -> Result<Boxed<BufferView>, Error> {
         let mut buffer_view_ptr = std::ptr::null_mut();
 
+        let capture = MessageCapture::begin();
         unsafe_member_call!(self, Buffer, CreateView, &view_desc.0, &mut buffer_view_ptr);
 
-        Boxed::new(buffer_view_ptr)
+        capture.boxed(
+            buffer_view_ptr,
+            Operation::CreateBufferView,
+            view_desc.name(),
+        )
     }
 
     pub fn get_default_view(&self, view_type: BufferViewType) -> Option<&BufferView> {
@@ -255,8 +270,9 @@ impl<'context, 'buffer, T: Sized, State: MapType> BufferMapToken<'context, 'buff
         device_context: &'context DeviceContext,
         buffer: &'buffer Buffer,
         map_flags: diligent_sys::MAP_FLAGS,
-    ) -> Result<BufferMapToken<'context, 'buffer, T, State>, BoxedFromNulError> {
+    ) -> Result<BufferMapToken<'context, 'buffer, T, State>, Error> {
         let mut ptr = std::ptr::null_mut();
+        let capture = MessageCapture::begin();
         unsafe_member_call!(
             device_context,
             DeviceContext,
@@ -268,7 +284,7 @@ impl<'context, 'buffer, T: Sized, State: MapType> BufferMapToken<'context, 'buff
         );
 
         if ptr.is_null() {
-            return Err(BoxedFromNulError);
+            return Err(capture.map_failed(Operation::MapBuffer, buffer.desc().name()));
         }
 
         Ok(BufferMapToken::<T, State> {
@@ -6,6 +6,7 @@ use crate::{
     buffer::Buffer,
     device_object::{DeviceObject, DeviceObjectAttribs},
     graphics_types::ValueType,
+    object::Interface,
 };
 
 #[derive(Clone, Copy)]
@@ -118,6 +119,15 @@ define_ported!(
     DeviceObject
 );
 
+impl Interface for BufferView {
+    const IID: diligent_sys::INTERFACE_ID = diligent_sys::INTERFACE_ID {
+        Data1: 0xe2e83490,
+        Data2: 0xe9d2,
+        Data3: 0x495b,
+        Data4: [0x9a, 0x83, 0xab, 0xb4, 0x13, 0xa3, 0x8b, 0x07],
+    };
+}
+
 impl BufferView {
     pub fn desc(&self) -> &BufferViewDesc<'_> {
         let desc_ptr = unsafe_member_call!(self, DeviceObject, GetDesc);
@@ -1,4 +1,12 @@
-use crate::object::Object;
+use std::ops::Deref;
+
+use crate::{
+    device_context::{DeviceContext, ImmediateDeviceContext},
+    object::Object,
+};
+
+#[cfg(feature = "vulkan_interop")]
+use crate::vk::command_queue_vk::CommandQueueVk;
 
 define_ported!(
     CommandQueue,
@@ -20,3 +28,53 @@ impl CommandQueue {
         unsafe_member_call!(self, CommandQueue, WaitForIdle)
     }
 }
+
+/// Keeps the command queue of an immediate context locked. The queue is unlocked when the lock is dropped.
+///
+/// The lock mutably borrows the context, which can not record nor submit commands while the queue is locked.
+#[must_use = "The command queue is unlocked as soon as the lock is dropped"]
+pub struct CommandQueueLock<'context> {
+    context: &'context mut ImmediateDeviceContext,
+    // The queue is owned by the context : locking it does not add a reference
+    command_queue: &'context CommandQueue,
+}
+
+impl<'context> CommandQueueLock<'context> {
+    pub(crate) fn new(
+        context: &'context mut ImmediateDeviceContext,
+        command_queue: &'context CommandQueue,
+    ) -> Self {
+        CommandQueueLock {
+            context,
+            command_queue,
+        }
+    }
+}
+
+impl CommandQueueLock<'_> {
+    /// The Vulkan interface of the locked queue, or `None` if the context was not created by the Vulkan backend.
+    #[cfg(feature = "vulkan_interop")]
+    pub fn as_vk(&self) -> Option<&CommandQueueVk> {
+        let object: &Object = self.command_queue;
+        // The reference added by the query is released right away : the queue is kept alive by the context
+        object
+            .query_interface::<CommandQueueVk>()
+            .map(|command_queue_vk| unsafe {
+                &*(std::ptr::from_ref::<CommandQueueVk>(&command_queue_vk))
+            })
+    }
+}
+
+impl Deref for CommandQueueLock<'_> {
+    type Target = CommandQueue;
+    fn deref(&self) -> &Self::Target {
+        self.command_queue
+    }
+}
+
+impl Drop for CommandQueueLock<'_> {
+    fn drop(&mut self) {
+        let context: &DeviceContext = self.context;
+        unsafe_member_call!(context, DeviceContext, UnlockCommandQueue);
+    }
+}
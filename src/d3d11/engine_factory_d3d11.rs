@@ -3,9 +3,12 @@ use std::ops::{Deref, DerefMut};
 use bitflags::bitflags;
 
 use crate::{
-    Boxed, BoxedFromNulError, Ported,
+    Boxed, Error, Ported,
+    abi::debug_verify_abi,
+    debug_output::install_message_hook,
     device_context::{DeferredDeviceContext, ImmediateDeviceContext},
     engine_factory::{EngineCreateInfo, EngineFactory},
+    error::{MessageCapture, Operation},
     graphics_types::{DisplayModeAttribs, FullScreenModeDesc, TextureFormat, Version},
     platforms::native_window::NativeWindow,
     render_device::RenderDevice,
@@ -20,7 +23,12 @@ define_ported!(
 );
 
 pub fn get_engine_factory_d3d11() -> Boxed<EngineFactoryD3D11> {
-    Boxed::new(unsafe { diligent_sys::Diligent_GetEngineFactoryD3D11() }).unwrap()
+    let engine_factory =
+        Boxed::<EngineFactoryD3D11>::new(unsafe { diligent_sys::Diligent_GetEngineFactoryD3D11() })
+            .unwrap();
+    install_message_hook(&engine_factory);
+    debug_verify_abi(&engine_factory);
+    engine_factory
 }
 
 bitflags! {
@@ -31,34 +39,34 @@ bitflags! {
     }
 }
 
-pub struct EngineD3D11CreateInfo<'immediate_context_info, 'xr_attribs> {
-    engine_create_info: EngineCreateInfo<'immediate_context_info, 'xr_attribs>,
+pub struct EngineD3D11CreateInfo<'immediate_context_info, 'xr_attribs, 'thread_pool> {
+    engine_create_info: EngineCreateInfo<'immediate_context_info, 'xr_attribs, 'thread_pool>,
 
     d3d11_validation_flags: D3D11ValidationFlags,
 }
 
-impl<'immediate_context_info, 'xr_attribs> Deref
-    for EngineD3D11CreateInfo<'immediate_context_info, 'xr_attribs>
+impl<'immediate_context_info, 'xr_attribs, 'thread_pool> Deref
+    for EngineD3D11CreateInfo<'immediate_context_info, 'xr_attribs, 'thread_pool>
 {
-    type Target = EngineCreateInfo<'immediate_context_info, 'xr_attribs>;
+    type Target = EngineCreateInfo<'immediate_context_info, 'xr_attribs, 'thread_pool>;
 
     fn deref(&self) -> &Self::Target {
         &self.engine_create_info
     }
 }
 
-impl DerefMut for EngineD3D11CreateInfo<'_, '_> {
+impl DerefMut for EngineD3D11CreateInfo<'_, '_, '_> {
     fn deref_mut(&mut self) -> &mut Self::Target {
         &mut self.engine_create_info
     }
 }
 
-impl<'immediate_context_info, 'xr_attribs>
-    EngineD3D11CreateInfo<'immediate_context_info, 'xr_attribs>
+impl<'immediate_context_info, 'xr_attribs, 'thread_pool>
+    EngineD3D11CreateInfo<'immediate_context_info, 'xr_attribs, 'thread_pool>
 {
     pub fn new(
         d3d11_validation_flags: D3D11ValidationFlags,
-        engine_create_info: EngineCreateInfo<'immediate_context_info, 'xr_attribs>,
+        engine_create_info: EngineCreateInfo<'immediate_context_info, 'xr_attribs, 'thread_pool>,
     ) -> Self {
         EngineD3D11CreateInfo {
             engine_create_info,
@@ -67,7 +75,7 @@ impl<'immediate_context_info, 'xr_attribs>
     }
 }
 
-impl From<&EngineD3D11CreateInfo<'_, '_>> for diligent_sys::EngineD3D11CreateInfo {
+impl From<&EngineD3D11CreateInfo<'_, '_, '_>> for diligent_sys::EngineD3D11CreateInfo {
     fn from(value: &EngineD3D11CreateInfo) -> Self {
         diligent_sys::EngineD3D11CreateInfo {
             _EngineCreateInfo: value.engine_create_info.0,
@@ -86,7 +94,7 @@ impl EngineFactoryD3D11 {
             Vec<Boxed<ImmediateDeviceContext>>,
             Vec<Boxed<DeferredDeviceContext>>,
         ),
-        BoxedFromNulError,
+        Error,
     > {
         let num_immediate_contexts = engine_ci
             .engine_create_info
@@ -105,6 +113,7 @@ impl EngineFactoryD3D11 {
         )
         .collect();
 
+        let capture = MessageCapture::begin();
         unsafe_member_call!(
             self,
             EngineFactoryD3D11,
@@ -114,24 +123,26 @@ impl EngineFactoryD3D11 {
             device_context_ptrs.as_mut_ptr()
         );
 
-        Boxed::new(render_device_ptr).and_then(|render_device| {
-            device_context_ptrs
-                .iter()
-                .take(num_immediate_contexts)
-                .map(|&dc_ptr| Boxed::new(dc_ptr))
-                .collect::<Result<Vec<_>, _>>()
-                .and_then(|immediate_devices| {
-                    device_context_ptrs
-                        .iter()
-                        .rev()
-                        .take(num_deferred_contexts)
-                        .map(|&dc_ptr| Boxed::new(dc_ptr))
-                        .collect::<Result<Vec<_>, _>>()
-                        .map(|deferred_devices| {
-                            (render_device, immediate_devices, deferred_devices)
-                        })
-                })
-        })
+        Boxed::new(render_device_ptr)
+            .and_then(|render_device| {
+                device_context_ptrs
+                    .iter()
+                    .take(num_immediate_contexts)
+                    .map(|&dc_ptr| Boxed::new(dc_ptr))
+                    .collect::<Option<Vec<_>>>()
+                    .and_then(|immediate_devices| {
+                        device_context_ptrs
+                            .iter()
+                            .rev()
+                            .take(num_deferred_contexts)
+                            .map(|&dc_ptr| Boxed::new(dc_ptr))
+                            .collect::<Option<Vec<_>>>()
+                            .map(|deferred_devices| {
+                                (render_device, immediate_devices, deferred_devices)
+                            })
+                    })
+            })
+            .ok_or_else(|| capture.null_object(Operation::CreateDeviceAndContexts, None))
     }
 
     pub fn create_swap_chain(
@@ -141,10 +152,11 @@ impl EngineFactoryD3D11 {
         swapchain_desc: &SwapChainCreateInfo,
         fs_desc: &FullScreenModeDesc,
         window: &NativeWindow,
-    ) -> Result<Boxed<SwapChain>, BoxedFromNulError> {
+    ) -> Result<Boxed<SwapChain>, Error> {
         let mut swap_chain_ptr = std::ptr::null_mut();
 
         let fs_desc = fs_desc.into();
+        let capture = MessageCapture::begin();
         unsafe_member_call!(
             self,
             EngineFactoryD3D11,
@@ -157,7 +169,7 @@ impl EngineFactoryD3D11 {
             &mut swap_chain_ptr
         );
 
-        Boxed::new(swap_chain_ptr)
+        capture.boxed(swap_chain_ptr, Operation::CreateSwapChain, None)
     }
 
     /// # Safety
@@ -173,7 +185,7 @@ impl EngineFactoryD3D11 {
             Vec<Boxed<ImmediateDeviceContext>>,
             Vec<Boxed<DeferredDeviceContext>>,
         ),
-        BoxedFromNulError,
+        Error,
     > {
         let num_immediate_contexts = engine_ci
             .engine_create_info
@@ -191,6 +203,7 @@ impl EngineFactoryD3D11 {
         .collect();
 
         let engine_ci = engine_ci.into();
+        let capture = MessageCapture::begin();
         unsafe_member_call!(
             self,
             EngineFactoryD3D11,
@@ -202,24 +215,26 @@ impl EngineFactoryD3D11 {
             device_context_ptrs.as_mut_ptr()
         );
 
-        Boxed::new(render_device_ptr).and_then(|render_device| {
-            device_context_ptrs
-                .iter()
-                .take(num_immediate_contexts)
-                .map(|&dc_ptr| Boxed::new(dc_ptr))
-                .collect::<Result<Vec<_>, _>>()
-                .and_then(|immediate_devices| {
-                    device_context_ptrs
-                        .iter()
-                        .rev()
-                        .take(num_deferred_contexts)
-                        .map(|&dc_ptr| Boxed::new(dc_ptr))
-                        .collect::<Result<Vec<_>, _>>()
-                        .map(|deferred_devices| {
-                            (render_device, immediate_devices, deferred_devices)
-                        })
-                })
-        })
+        Boxed::new(render_device_ptr)
+            .and_then(|render_device| {
+                device_context_ptrs
+                    .iter()
+                    .take(num_immediate_contexts)
+                    .map(|&dc_ptr| Boxed::new(dc_ptr))
+                    .collect::<Option<Vec<_>>>()
+                    .and_then(|immediate_devices| {
+                        device_context_ptrs
+                            .iter()
+                            .rev()
+                            .take(num_deferred_contexts)
+                            .map(|&dc_ptr| Boxed::new(dc_ptr))
+                            .collect::<Option<Vec<_>>>()
+                            .map(|deferred_devices| {
+                                (render_device, immediate_devices, deferred_devices)
+                            })
+                    })
+            })
+            .ok_or_else(|| capture.null_object(Operation::CreateDeviceAndContexts, None))
     }
 
     pub fn enumerate_display_modes(
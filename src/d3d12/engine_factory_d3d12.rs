@@ -7,9 +7,12 @@ use std::{
 use bitflags::bitflags;
 
 use crate::{
-    Boxed, BoxedFromNulError, Ported,
+    Boxed, Error, Ported,
+    abi::debug_verify_abi,
+    debug_output::install_message_hook,
     device_context::{DeferredDeviceContext, ImmediateDeviceContext},
     engine_factory::{EngineCreateInfo, EngineFactory},
+    error::{MessageCapture, Operation},
     graphics_types::{DisplayModeAttribs, FullScreenModeDesc, TextureFormat, Version},
     platforms::native_window::NativeWindow,
     render_device::RenderDevice,
@@ -24,7 +27,12 @@ define_ported!(
 );
 
 pub fn get_engine_factory_d3d12() -> Boxed<EngineFactoryD3D12> {
-    Boxed::new(unsafe { diligent_sys::Diligent_GetEngineFactoryD3D12() }).unwrap()
+    let engine_factory =
+        Boxed::<EngineFactoryD3D12>::new(unsafe { diligent_sys::Diligent_GetEngineFactoryD3D12() })
+            .unwrap();
+    install_message_hook(&engine_factory);
+    debug_verify_abi(&engine_factory);
+    engine_factory
 }
 
 bitflags! {
@@ -37,8 +45,8 @@ bitflags! {
     }
 }
 
-pub struct EngineD3D12CreateInfo<'immediate_context_info, 'xr_attribs> {
-    engine_create_info: EngineCreateInfo<'immediate_context_info, 'xr_attribs>,
+pub struct EngineD3D12CreateInfo<'immediate_context_info, 'xr_attribs, 'thread_pool> {
+    engine_create_info: EngineCreateInfo<'immediate_context_info, 'xr_attribs, 'thread_pool>,
 
     d3d12_dll_name: CString,
     d3d12_validation_flags: D3D12ValidationFlags,
@@ -52,26 +60,28 @@ pub struct EngineD3D12CreateInfo<'immediate_context_info, 'xr_attribs> {
     p_dx_compiler_path: CString,
 }
 
-impl<'immediate_context_info, 'xr_attribs> Deref
-    for EngineD3D12CreateInfo<'immediate_context_info, 'xr_attribs>
+impl<'immediate_context_info, 'xr_attribs, 'thread_pool> Deref
+    for EngineD3D12CreateInfo<'immediate_context_info, 'xr_attribs, 'thread_pool>
 {
-    type Target = EngineCreateInfo<'immediate_context_info, 'xr_attribs>;
+    type Target = EngineCreateInfo<'immediate_context_info, 'xr_attribs, 'thread_pool>;
 
     fn deref(&self) -> &Self::Target {
         &self.engine_create_info
     }
 }
 
-impl DerefMut for EngineD3D12CreateInfo<'_, '_> {
+impl DerefMut for EngineD3D12CreateInfo<'_, '_, '_> {
     fn deref_mut(&mut self) -> &mut Self::Target {
         &mut self.engine_create_info
     }
 }
 
-impl<'immediate_context_info, 'xr_attribs>
-    EngineD3D12CreateInfo<'immediate_context_info, 'xr_attribs>
+impl<'immediate_context_info, 'xr_attribs, 'thread_pool>
+    EngineD3D12CreateInfo<'immediate_context_info, 'xr_attribs, 'thread_pool>
 {
-    pub fn new(engine_create_info: EngineCreateInfo<'immediate_context_info, 'xr_attribs>) -> Self {
+    pub fn new(
+        engine_create_info: EngineCreateInfo<'immediate_context_info, 'xr_attribs, 'thread_pool>,
+    ) -> Self {
         EngineD3D12CreateInfo {
             engine_create_info,
             cpu_descriptor_heap_allocation_size: [
@@ -109,7 +119,7 @@ impl<'immediate_context_info, 'xr_attribs>
     }
 }
 
-impl From<&EngineD3D12CreateInfo<'_, '_>> for diligent_sys::EngineD3D12CreateInfo {
+impl From<&EngineD3D12CreateInfo<'_, '_, '_>> for diligent_sys::EngineD3D12CreateInfo {
     fn from(value: &EngineD3D12CreateInfo) -> Self {
         diligent_sys::EngineD3D12CreateInfo {
             _EngineCreateInfo: value.engine_create_info.0,
@@ -146,7 +156,7 @@ impl EngineFactoryD3D12 {
             Vec<Boxed<ImmediateDeviceContext>>,
             Vec<Boxed<DeferredDeviceContext>>,
         ),
-        BoxedFromNulError,
+        Error,
     > {
         let num_immediate_contexts = engine_ci
             .engine_create_info
@@ -165,6 +175,7 @@ impl EngineFactoryD3D12 {
         )
         .collect();
 
+        let capture = MessageCapture::begin();
         unsafe_member_call!(
             self,
             EngineFactoryD3D12,
@@ -174,24 +185,26 @@ impl EngineFactoryD3D12 {
             device_context_ptrs.as_mut_ptr()
         );
 
-        Boxed::new(render_device_ptr).and_then(|render_device| {
-            device_context_ptrs
-                .iter()
-                .take(num_immediate_contexts)
-                .map(|&dc_ptr| Boxed::new(dc_ptr))
-                .collect::<Result<Vec<_>, _>>()
-                .and_then(|immediate_devices| {
-                    device_context_ptrs
-                        .iter()
-                        .rev()
-                        .take(num_deferred_contexts)
-                        .map(|&dc_ptr| Boxed::new(dc_ptr))
-                        .collect::<Result<Vec<_>, _>>()
-                        .map(|deferred_devices| {
-                            (render_device, immediate_devices, deferred_devices)
-                        })
-                })
-        })
+        Boxed::new(render_device_ptr)
+            .and_then(|render_device| {
+                device_context_ptrs
+                    .iter()
+                    .take(num_immediate_contexts)
+                    .map(|&dc_ptr| Boxed::new(dc_ptr))
+                    .collect::<Option<Vec<_>>>()
+                    .and_then(|immediate_devices| {
+                        device_context_ptrs
+                            .iter()
+                            .rev()
+                            .take(num_deferred_contexts)
+                            .map(|&dc_ptr| Boxed::new(dc_ptr))
+                            .collect::<Option<Vec<_>>>()
+                            .map(|deferred_devices| {
+                                (render_device, immediate_devices, deferred_devices)
+                            })
+                    })
+            })
+            .ok_or_else(|| capture.null_object(Operation::CreateDeviceAndContexts, None))
     }
 
     // TODO
@@ -224,10 +237,11 @@ impl EngineFactoryD3D12 {
         swapchain_desc: &SwapChainCreateInfo,
         fs_desc: &FullScreenModeDesc,
         window: &NativeWindow,
-    ) -> Result<Boxed<SwapChain>, BoxedFromNulError> {
+    ) -> Result<Boxed<SwapChain>, Error> {
         let mut swap_chain_ptr = std::ptr::null_mut();
 
         let fs_desc = fs_desc.into();
+        let capture = MessageCapture::begin();
         unsafe_member_call!(
             self,
             EngineFactoryD3D12,
@@ -240,7 +254,7 @@ impl EngineFactoryD3D12 {
             &mut swap_chain_ptr
         );
 
-        Boxed::new(swap_chain_ptr)
+        capture.boxed(swap_chain_ptr, Operation::CreateSwapChain, None)
     }
 
     pub fn enumerate_display_modes(
@@ -1,7 +1,16 @@
 use core::fmt;
-use std::{fmt::Debug, ops::Deref};
+use std::{
+    cell::UnsafeCell,
+    fmt::Debug,
+    io::{Read, Seek, SeekFrom, Write},
+    ops::Deref,
+    os::raw::c_void,
+};
 
-use crate::{Boxed, object::Object};
+use crate::{
+    Boxed,
+    object::{Object, RustObject},
+};
 
 define_ported!(
     DataBlob,
@@ -12,20 +21,7 @@ define_ported!(
 
 impl fmt::Debug for DataBlob {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
-        unsafe {
-            let size = self.get_size();
-
-            write!(
-                f,
-                "{}",
-                String::from_raw_parts(
-                    std::ptr::from_ref(self.get_data::<u8>(0)) as *mut _,
-                    size,
-                    size
-                )
-                .as_str()
-            )
-        }
+        write!(f, "{}", String::from_utf8_lossy(self.as_bytes()))
     }
 }
 
@@ -36,6 +32,42 @@ impl Debug for Boxed<DataBlob> {
 }
 
 impl DataBlob {
+    pub fn as_bytes(&self) -> &[u8] {
+        let size = self.get_size();
+        if size == 0 {
+            return &[];
+        }
+
+        let data_ptr = unsafe_member_call!(self, DataBlob, GetConstDataPtr, 0) as *const u8;
+        unsafe { std::slice::from_raw_parts(data_ptr, size) }
+    }
+
+    pub fn as_bytes_mut(&mut self) -> &mut [u8] {
+        let size = self.get_size();
+        if size == 0 {
+            return &mut [];
+        }
+
+        let data_ptr = unsafe_member_call!(self, DataBlob, GetDataPtr, 0) as *mut u8;
+        unsafe { std::slice::from_raw_parts_mut(data_ptr, size) }
+    }
+
+    /// Returns a cursor that reads the content of the blob from the start.
+    pub fn reader(&self) -> DataBlobReader<'_> {
+        DataBlobReader {
+            data_blob: self,
+            pos: 0,
+        }
+    }
+
+    /// Returns a cursor that writes to the blob from the start. The blob grows when data is written past its end.
+    pub fn writer(&mut self) -> DataBlobWriter<'_> {
+        DataBlobWriter {
+            data_blob: self,
+            pos: 0,
+        }
+    }
+
     pub fn resize(&mut self, new_size: usize) {
         unsafe_member_call!(self, DataBlob, Resize, new_size)
     }
@@ -85,3 +117,129 @@ impl DataBlob {
         unsafe { std::slice::from_raw_parts_mut(ptr, size) }
     }
 }
+
+fn seek_position(pos: u64, size: usize, seek: SeekFrom) -> std::io::Result<u64> {
+    let new_pos = match seek {
+        SeekFrom::Start(offset) => Some(offset),
+        SeekFrom::Current(offset) => pos.checked_add_signed(offset),
+        SeekFrom::End(offset) => (size as u64).checked_add_signed(offset),
+    };
+
+    new_pos.ok_or_else(|| {
+        std::io::Error::new(
+            std::io::ErrorKind::InvalidInput,
+            "invalid seek to a negative or overflowing position",
+        )
+    })
+}
+
+pub struct DataBlobReader<'data_blob> {
+    data_blob: &'data_blob DataBlob,
+    pos: u64,
+}
+
+impl Read for DataBlobReader<'_> {
+    fn read(&mut self, buf: &mut [u8]) -> std::io::Result<usize> {
+        let bytes = self.data_blob.as_bytes();
+        let remaining = bytes.get(self.pos as usize..).unwrap_or_default();
+
+        let count = remaining.len().min(buf.len());
+        buf[..count].copy_from_slice(&remaining[..count]);
+        self.pos += count as u64;
+        Ok(count)
+    }
+}
+
+impl Seek for DataBlobReader<'_> {
+    fn seek(&mut self, pos: SeekFrom) -> std::io::Result<u64> {
+        self.pos = seek_position(self.pos, self.data_blob.get_size(), pos)?;
+        Ok(self.pos)
+    }
+}
+
+pub struct DataBlobWriter<'data_blob> {
+    data_blob: &'data_blob mut DataBlob,
+    pos: u64,
+}
+
+impl Write for DataBlobWriter<'_> {
+    fn write(&mut self, buf: &[u8]) -> std::io::Result<usize> {
+        let start = self.pos as usize;
+        let end = start + buf.len();
+
+        if end > self.data_blob.get_size() {
+            self.data_blob.resize(end);
+        }
+
+        self.data_blob.as_bytes_mut()[start..end].copy_from_slice(buf);
+        self.pos = end as u64;
+        Ok(buf.len())
+    }
+
+    fn flush(&mut self) -> std::io::Result<()> {
+        Ok(())
+    }
+}
+
+impl Seek for DataBlobWriter<'_> {
+    fn seek(&mut self, pos: SeekFrom) -> std::io::Result<u64> {
+        self.pos = seek_position(self.pos, self.data_blob.get_size(), pos)?;
+        Ok(self.pos)
+    }
+}
+
+// The engine only accesses the blob through the pointers of its vtable, never concurrently with Rust
+struct VecDataBlob(UnsafeCell<Vec<u8>>);
+
+type VecDataBlobObject = RustObject<diligent_sys::IDataBlobVtbl, VecDataBlob>;
+
+impl VecDataBlob {
+    unsafe fn data<'a>(this: *mut diligent_sys::IDataBlob) -> &'a mut Vec<u8> {
+        unsafe { &mut *VecDataBlobObject::from_sys(this).value.0.get() }
+    }
+
+    unsafe extern "C" fn resize(this: *mut diligent_sys::IDataBlob, new_size: usize) {
+        unsafe { Self::data(this) }.resize(new_size, 0)
+    }
+
+    unsafe extern "C" fn get_size(this: *mut diligent_sys::IDataBlob) -> usize {
+        unsafe { Self::data(this) }.len()
+    }
+
+    unsafe extern "C" fn get_data_ptr(
+        this: *mut diligent_sys::IDataBlob,
+        offset: usize,
+    ) -> *mut c_void {
+        let data = unsafe { Self::data(this) };
+        data.as_mut_ptr().wrapping_add(offset) as *mut c_void
+    }
+
+    unsafe extern "C" fn get_const_data_ptr(
+        this: *mut diligent_sys::IDataBlob,
+        offset: usize,
+    ) -> *const c_void {
+        let data = unsafe { Self::data(this) };
+        data.as_ptr().wrapping_add(offset) as *const c_void
+    }
+}
+
+impl DataBlob {
+    /// Creates a data blob that takes ownership of `data` without copying it.
+    pub fn from_vec(data: Vec<u8>) -> Boxed<DataBlob> {
+        let data_blob_ptr = VecDataBlobObject::create(
+            diligent_sys::IDataBlobVtbl {
+                Object: VecDataBlobObject::OBJECT_METHODS,
+                DataBlob: diligent_sys::IDataBlobMethods {
+                    Resize: Some(VecDataBlob::resize),
+                    GetSize: Some(VecDataBlob::get_size),
+                    GetDataPtr: Some(VecDataBlob::get_data_ptr),
+                    GetConstDataPtr: Some(VecDataBlob::get_const_data_ptr),
+                },
+            },
+            VecDataBlob(UnsafeCell::new(data)),
+        );
+
+        // The object was just allocated, the pointer can not be null
+        Boxed::new(data_blob_ptr).unwrap()
+    }
+}
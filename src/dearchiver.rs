@@ -1,9 +1,11 @@
 use std::{ffi::CStr, marker::PhantomData};
 
 use crate::{
-    Boxed, BoxedFromNulError, DataBlob, PipelineResourceSignature, PipelineState,
-    PipelineStateCache, PipelineStateCreateInfo, PipelineType, Ported, RenderDevice, RenderPass,
-    RenderPassDesc, Shader, ShaderDesc, object::Object,
+    Boxed, DataBlob, Error, PipelineResourceSignature, PipelineState, PipelineStateCache,
+    PipelineStateCreateInfo, PipelineType, Ported, RenderDevice, RenderPass, RenderPassDesc,
+    Shader, ShaderDesc,
+    error::{MessageCapture, Operation, object_name},
+    object::Object,
 };
 
 #[repr(transparent)]
@@ -180,8 +182,9 @@ impl Dearchiver {
     pub fn unpack_shader<'device, 'user_data, 'name, UserData>(
         &self,
         unpack_info: &ShaderUnpackInfo<'device, 'user_data, 'name, UserData>,
-    ) -> Result<Boxed<Shader>, BoxedFromNulError> {
+    ) -> Result<Boxed<Shader>, Error> {
         let mut shader_ptr = std::ptr::null_mut();
+        let capture = MessageCapture::begin();
         unsafe_member_call!(
             self,
             Dearchiver,
@@ -189,14 +192,19 @@ impl Dearchiver {
             &unpack_info.0,
             &mut shader_ptr
         );
-        Boxed::new(shader_ptr)
+        capture.boxed(
+            shader_ptr,
+            Operation::UnpackShader,
+            object_name(unpack_info.0.Name),
+        )
     }
 
     pub fn unpack_pipeline_state<'user_data, 'device, 'name, UserData>(
         &self,
         unpack_info: &PipelineStateUnpackInfo<'user_data, 'device, 'name, UserData>,
-    ) -> Result<Boxed<PipelineState>, BoxedFromNulError> {
+    ) -> Result<Boxed<PipelineState>, Error> {
         let mut pipeline_ptr = std::ptr::null_mut();
+        let capture = MessageCapture::begin();
         unsafe_member_call!(
             self,
             Dearchiver,
@@ -204,14 +212,19 @@ impl Dearchiver {
             &unpack_info.0,
             &mut pipeline_ptr
         );
-        Boxed::new(pipeline_ptr)
+        capture.boxed(
+            pipeline_ptr,
+            Operation::UnpackPipelineState,
+            object_name(unpack_info.0.Name),
+        )
     }
 
     pub fn unpack_resource_signature(
         &self,
         unpack_info: &ResourceSignatureUnpackInfo,
-    ) -> Result<Boxed<PipelineResourceSignature>, BoxedFromNulError> {
+    ) -> Result<Boxed<PipelineResourceSignature>, Error> {
         let mut rs_ptr = std::ptr::null_mut();
+        let capture = MessageCapture::begin();
         unsafe_member_call!(
             self,
             Dearchiver,
@@ -219,14 +232,19 @@ impl Dearchiver {
             &unpack_info.0,
             &mut rs_ptr
         );
-        Boxed::new(rs_ptr)
+        capture.boxed(
+            rs_ptr,
+            Operation::UnpackResourceSignature,
+            object_name(unpack_info.0.Name),
+        )
     }
 
     pub fn unpack_render_pass<'device, 'user_data, 'name, UserData>(
         &self,
         unpack_info: &RenderPassUnpackInfo<'device, 'user_data, 'name, UserData>,
-    ) -> Result<Boxed<RenderPass>, BoxedFromNulError> {
+    ) -> Result<Boxed<RenderPass>, Error> {
         let mut render_pass_ptr = std::ptr::null_mut();
+        let capture = MessageCapture::begin();
         unsafe_member_call!(
             self,
             Dearchiver,
@@ -234,16 +252,21 @@ impl Dearchiver {
             &unpack_info.0,
             &mut render_pass_ptr
         );
-        Boxed::new(render_pass_ptr)
+        capture.boxed(
+            render_pass_ptr,
+            Operation::UnpackRenderPass,
+            object_name(unpack_info.0.Name),
+        )
     }
 
-    pub fn store(&mut self) -> Result<Boxed<DataBlob>, BoxedFromNulError> {
+    pub fn store(&mut self) -> Result<Boxed<DataBlob>, Error> {
         let mut data_blob_ptr = std::ptr::null_mut();
+        let capture = MessageCapture::begin();
         let success = unsafe_member_call!(self, Dearchiver, Store, &mut data_blob_ptr);
         if success {
-            Boxed::new(data_blob_ptr)
+            capture.boxed(data_blob_ptr, Operation::StoreArchive, None)
         } else {
-            Err(BoxedFromNulError)
+            Err(capture.null_object(Operation::StoreArchive, None))
         }
     }
 
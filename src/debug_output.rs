@@ -0,0 +1,236 @@
+use std::{
+    ffi::CStr,
+    fmt::Display,
+    os::raw::{c_char, c_int},
+    sync::{Arc, Mutex, MutexGuard, Once},
+};
+
+#[cfg(feature = "archiver")]
+use crate::archiver::ArchiverFactory;
+use crate::{
+    engine_factory::EngineFactory,
+    error::{EngineMessage, record_message},
+};
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum DebugMessageSeverity {
+    Info,
+    Warning,
+    Error,
+    FatalError,
+}
+
+impl From<diligent_sys::DEBUG_MESSAGE_SEVERITY> for DebugMessageSeverity {
+    fn from(value: diligent_sys::DEBUG_MESSAGE_SEVERITY) -> Self {
+        match value as _ {
+            diligent_sys::DEBUG_MESSAGE_SEVERITY_INFO => DebugMessageSeverity::Info,
+            diligent_sys::DEBUG_MESSAGE_SEVERITY_WARNING => DebugMessageSeverity::Warning,
+            diligent_sys::DEBUG_MESSAGE_SEVERITY_ERROR => DebugMessageSeverity::Error,
+            _ => DebugMessageSeverity::FatalError,
+        }
+    }
+}
+
+impl From<DebugMessageSeverity> for diligent_sys::DEBUG_MESSAGE_SEVERITY {
+    fn from(value: DebugMessageSeverity) -> Self {
+        (match value {
+            DebugMessageSeverity::Info => diligent_sys::DEBUG_MESSAGE_SEVERITY_INFO,
+            DebugMessageSeverity::Warning => diligent_sys::DEBUG_MESSAGE_SEVERITY_WARNING,
+            DebugMessageSeverity::Error => diligent_sys::DEBUG_MESSAGE_SEVERITY_ERROR,
+            DebugMessageSeverity::FatalError => diligent_sys::DEBUG_MESSAGE_SEVERITY_FATAL_ERROR,
+        }) as _
+    }
+}
+
+impl Display for DebugMessageSeverity {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(match self {
+            DebugMessageSeverity::Info => "Info",
+            DebugMessageSeverity::Warning => "Warning",
+            DebugMessageSeverity::Error => "ERROR",
+            DebugMessageSeverity::FatalError => "CRITICAL ERROR",
+        })
+    }
+}
+
+/// Signature of the closures receiving the engine messages : the severity, the message,
+/// and the function, file and line the message was emitted from, when the engine provides them.
+pub type MessageCallback =
+    dyn Fn(DebugMessageSeverity, &str, Option<&str>, Option<&str>, u32) + Send + Sync;
+
+static MESSAGE_CALLBACK: Mutex<Option<Arc<MessageCallback>>> = Mutex::new(None);
+
+static INSTALL_MESSAGE_HOOK: Once = Once::new();
+
+fn message_callback() -> MutexGuard<'static, Option<Arc<MessageCallback>>> {
+    MESSAGE_CALLBACK
+        .lock()
+        .unwrap_or_else(|poisoned| poisoned.into_inner())
+}
+
+fn optional_string(ptr: *const c_char) -> Option<String> {
+    if ptr.is_null() {
+        None
+    } else {
+        Some(
+            unsafe { CStr::from_ptr(ptr) }
+                .to_string_lossy()
+                .into_owned(),
+        )
+    }
+}
+
+unsafe extern "C" fn message_hook(
+    severity: diligent_sys::DEBUG_MESSAGE_SEVERITY,
+    message: *const c_char,
+    function: *const c_char,
+    file: *const c_char,
+    line: c_int,
+) {
+    let engine_message = EngineMessage {
+        severity: severity.into(),
+        message: optional_string(message).unwrap_or_default(),
+        function: optional_string(function),
+        file: optional_string(file),
+        line: line as u32,
+    };
+
+    record_message(&engine_message);
+
+    // The lock is released before calling the user callback so that the callback itself
+    // can register another callback.
+    let callback = message_callback().clone();
+
+    match callback {
+        Some(callback) => callback(
+            engine_message.severity,
+            &engine_message.message,
+            engine_message.function.as_deref(),
+            engine_message.file.as_deref(),
+            engine_message.line,
+        ),
+        None => default_message_sink(&engine_message),
+    }
+}
+
+/// Where the messages go when no callback is registered, in place of the default output of the engine.
+#[cfg(feature = "log")]
+fn default_message_sink(message: &EngineMessage) {
+    log_message_callback(
+        message.severity,
+        &message.message,
+        message.function.as_deref(),
+        message.file.as_deref(),
+        message.line,
+    );
+}
+
+/// Where the messages go when no callback is registered, in place of the default output of the engine.
+#[cfg(not(feature = "log"))]
+fn default_message_sink(message: &EngineMessage) {
+    eprintln!("Diligent Engine: {message}");
+}
+
+/// Routes the engine messages through the crate so that they can be attached to errors
+/// and forwarded to the registered message callback.
+pub(crate) fn install_message_hook(engine_factory: &EngineFactory) {
+    INSTALL_MESSAGE_HOOK.call_once(|| {
+        unsafe_member_call!(
+            engine_factory,
+            EngineFactory,
+            SetMessageCallback,
+            Some(message_hook)
+        )
+    });
+}
+
+/// Same as `install_message_hook`, for the programs that only use the archiver.
+/// The engine has a single message callback, shared by all its factories.
+#[cfg(feature = "archiver")]
+pub(crate) fn install_archiver_message_hook(archiver_factory: &ArchiverFactory) {
+    INSTALL_MESSAGE_HOOK.call_once(|| {
+        unsafe_member_call!(
+            archiver_factory,
+            ArchiverFactory,
+            SetMessageCallback,
+            Some(message_hook)
+        )
+    });
+}
+
+pub(crate) fn replace_message_callback(
+    callback: Option<Arc<MessageCallback>>,
+) -> Option<Arc<MessageCallback>> {
+    std::mem::replace(&mut *message_callback(), callback)
+}
+
+/// Keeps a message callback registered. The previous callback is restored when the guard is dropped.
+#[must_use = "The message callback is unregistered as soon as the guard is dropped"]
+pub struct MessageCallbackGuard {
+    previous: Option<Arc<MessageCallback>>,
+}
+
+impl MessageCallbackGuard {
+    pub(crate) fn new(previous: Option<Arc<MessageCallback>>) -> Self {
+        MessageCallbackGuard { previous }
+    }
+}
+
+impl Drop for MessageCallbackGuard {
+    fn drop(&mut self) {
+        replace_message_callback(self.previous.take());
+    }
+}
+
+/// Forwards the engine messages to the `log` crate with the `diligent` target.
+#[cfg(feature = "log")]
+pub fn log_message_callback(
+    severity: DebugMessageSeverity,
+    message: &str,
+    _function: Option<&str>,
+    file: Option<&str>,
+    line: u32,
+) {
+    let level = match severity {
+        DebugMessageSeverity::Info => log::Level::Info,
+        DebugMessageSeverity::Warning => log::Level::Warn,
+        DebugMessageSeverity::Error | DebugMessageSeverity::FatalError => log::Level::Error,
+    };
+
+    if log::log_enabled!(target: "diligent", level) {
+        log::logger().log(
+            &log::Record::builder()
+                .args(format_args!("{message}"))
+                .level(level)
+                .target("diligent")
+                .file(file)
+                .line(file.map(|_| line))
+                .build(),
+        );
+    }
+}
+
+/// Forwards the engine messages to the `tracing` crate with the `diligent` target.
+#[cfg(feature = "tracing")]
+pub fn tracing_message_callback(
+    severity: DebugMessageSeverity,
+    message: &str,
+    function: Option<&str>,
+    file: Option<&str>,
+    line: u32,
+) {
+    let function = function.unwrap_or_default();
+    let file = file.unwrap_or_default();
+
+    match severity {
+        DebugMessageSeverity::Info => {
+            tracing::info!(target: "diligent", function, file, line, "{message}")
+        }
+        DebugMessageSeverity::Warning => {
+            tracing::warn!(target: "diligent", function, file, line, "{message}")
+        }
+        DebugMessageSeverity::Error | DebugMessageSeverity::FatalError => {
+            tracing::error!(target: "diligent", function, file, line, "{message}")
+        }
+    }
+}
@@ -4,12 +4,13 @@ use bitflags::bitflags;
 use static_assertions::const_assert_eq;
 
 use crate::{
-    Boxed, BoxedFromNulError, CommandQueueType, DeviceMemory, Ported, PrimitiveTopology,
+    Boxed, CommandQueueType, DeviceMemory, Error, Ported, PrimitiveTopology,
     blas::BottomLevelAS,
     buffer::{Buffer, BufferMapReadToken, BufferMapReadWriteToken, BufferMapWriteToken},
-    command_queue::CommandQueue,
+    command_queue::{CommandQueue, CommandQueueLock},
     device_object::{DeviceObject, ResourceTransition},
-    fence::Fence,
+    error::{MessageCapture, Operation},
+    fence::{Fence, wake_completed_fence_waits},
     frame_buffer::Framebuffer,
     graphics_types::{
         MapFlags, ResourceState, ShadingRate, ShadingRateCombiner, StateTransitionType,
@@ -1453,7 +1454,7 @@ impl Default for StateTransitionFlags {
 #[repr(transparent)]
 #[derive(Clone)]
 pub struct StateTransitionDesc<'resource>(
-    diligent_sys::StateTransitionDesc,
+    pub(crate) diligent_sys::StateTransitionDesc,
     PhantomData<&'resource ()>,
 );
 
@@ -1481,7 +1482,7 @@ impl<'resource> StateTransitionDesc<'resource> {
     ) -> Self {
         Self(
             diligent_sys::StateTransitionDesc {
-                pResourceBefore: std::ptr::null_mut(), // TODO
+                pResourceBefore: std::ptr::null_mut(),
                 pResource: resource.sys_ptr(),
                 FirstMipLevel: first_mip_level,
                 MipLevelsCount: mip_levels_count,
@@ -1499,7 +1500,63 @@ impl<'resource> StateTransitionDesc<'resource> {
     }
 }
 
+#[bon::bon]
+impl<'resource> StateTransitionDesc<'resource> {
+    /// Creates an aliasing barrier between two placed resources that share the same `memory` :
+    /// the content of `resource_before` is lost and `resource_after` can be used from now on.
+    ///
+    /// Without `resource_before`, any resource placed in the memory can be the one before the barrier.
+    #[builder]
+    pub fn aliasing(
+        resource_before: Option<&'resource DeviceObject>,
+
+        resource_after: &'resource mut DeviceObject,
+
+        memory: &DeviceMemory,
+
+        #[builder(default = StateTransitionType::Immediate)] transition_type: StateTransitionType,
+    ) -> Result<Self, Error> {
+        let check_compatible = |resource: &DeviceObject| {
+            if memory.is_compatible(resource) {
+                Ok(())
+            } else {
+                Err(Error::invalid_argument(
+                    Operation::AliasResources,
+                    resource.desc().name(),
+                    "the resource is not compatible with the aliased memory",
+                ))
+            }
+        };
+
+        if let Some(resource_before) = resource_before {
+            check_compatible(resource_before)?;
+        }
+        check_compatible(resource_after)?;
+
+        Ok(Self(
+            diligent_sys::StateTransitionDesc {
+                pResourceBefore: resource_before
+                    .map_or(std::ptr::null_mut(), |resource| resource.sys_ptr()),
+                pResource: resource_after.sys_ptr(),
+                FirstMipLevel: 0,
+                MipLevelsCount: diligent_sys::REMAINING_MIP_LEVELS,
+                FirstArraySlice: 0,
+                ArraySliceCount: diligent_sys::REMAINING_ARRAY_SLICES,
+                OldState: diligent_sys::RESOURCE_STATE_UNKNOWN as _,
+                NewState: diligent_sys::RESOURCE_STATE_UNKNOWN as _,
+                TransitionType: transition_type.into(),
+                Flags: StateTransitionFlags::Aliasing.bits(),
+            },
+            PhantomData,
+        ))
+    }
+}
+
 impl StateTransitionDesc<'_> {
+    /// The resource before an aliasing barrier, if it was specified.
+    pub fn resource_before(&self) -> Option<&DeviceObject> {
+        unsafe { (self.0.pResourceBefore as *const DeviceObject).as_ref() }
+    }
     pub fn resource(&mut self) -> &mut DeviceObject {
         unsafe { &mut *(self.0.pResource as *mut DeviceObject) }
     }
@@ -2462,7 +2519,7 @@ impl DeviceContext {
         &self,
         buffer: &'buffer Buffer,
         map_flags: MapFlags,
-    ) -> Result<BufferMapReadToken<'_, 'buffer, T>, BoxedFromNulError>
+    ) -> Result<BufferMapReadToken<'_, 'buffer, T>, Error>
     where
         Self: Sized,
     {
@@ -2473,7 +2530,7 @@ impl DeviceContext {
         &self,
         buffer: &'buffer Buffer,
         map_flags: MapFlags,
-    ) -> Result<BufferMapWriteToken<'_, 'buffer, T>, BoxedFromNulError>
+    ) -> Result<BufferMapWriteToken<'_, 'buffer, T>, Error>
     where
         Self: Sized,
     {
@@ -2484,7 +2541,7 @@ impl DeviceContext {
         &self,
         buffer: &'buffer Buffer,
         map_flags: MapFlags,
-    ) -> Result<BufferMapReadWriteToken<'_, 'buffer, T>, BoxedFromNulError>
+    ) -> Result<BufferMapReadWriteToken<'_, 'buffer, T>, Error>
     where
         Self: Sized,
     {
@@ -2531,7 +2588,7 @@ impl DeviceContext {
         array_slice: u32,
         map_flags: MapFlags,
         map_region: Option<crate::Box>,
-    ) -> Result<TextureMapReadToken<'_, 'texture, T>, BoxedFromNulError> {
+    ) -> Result<TextureMapReadToken<'_, 'texture, T>, Error> {
         TextureMapReadToken::new(self, texture, mip_level, array_slice, map_flags, map_region)
     }
 
@@ -2543,7 +2600,7 @@ impl DeviceContext {
         array_slice: u32,
         map_flags: MapFlags,
         map_region: Option<crate::Box>,
-    ) -> Result<TextureMapWriteToken<'_, 'texture, T>, BoxedFromNulError> {
+    ) -> Result<TextureMapWriteToken<'_, 'texture, T>, Error> {
         TextureMapWriteToken::new(self, texture, mip_level, array_slice, map_flags, map_region)
     }
 
@@ -2555,7 +2612,7 @@ impl DeviceContext {
         array_slice: u32,
         map_flags: MapFlags,
         map_region: Option<crate::Box>,
-    ) -> Result<TextureMapReadWriteToken<'_, 'texture, T>, BoxedFromNulError> {
+    ) -> Result<TextureMapReadWriteToken<'_, 'texture, T>, Error> {
         TextureMapReadWriteToken::new(self, texture, mip_level, array_slice, map_flags, map_region)
     }
 
@@ -2738,8 +2795,11 @@ impl ImmediateDeviceContext {
         unsafe_member_call!(self.0, DeviceContext, Flush)
     }
 
+    /// Finishes the frame, and wakes the futures of `Fence::wait_async` whose fence was reached.
     pub fn finish_frame(&self) {
-        unsafe_member_call!(self.0, DeviceContext, FinishFrame)
+        unsafe_member_call!(self.0, DeviceContext, FinishFrame);
+
+        wake_completed_fence_waits();
     }
 
     pub fn execute_command_lists(&self, command_lists: &[&CommandList]) {
@@ -2758,13 +2818,15 @@ impl ImmediateDeviceContext {
         unsafe_member_call!(self.0, DeviceContext, WaitForIdle)
     }
 
-    // TODO : make command queue locking RAII
-    pub fn lock_command_queue(&self) -> Result<Boxed<CommandQueue>, BoxedFromNulError> {
-        Boxed::new(unsafe_member_call!(self.0, DeviceContext, LockCommandQueue))
-    }
+    /// Locks the command queue of the context until the returned lock is dropped.
+    pub fn lock_command_queue(&mut self) -> Result<CommandQueueLock<'_>, Error> {
+        let capture = MessageCapture::begin();
+        let command_queue_ptr = unsafe_member_call!(self.0, DeviceContext, LockCommandQueue);
 
-    pub fn unlock_command_queue(&self) {
-        unsafe_member_call!(self.0, DeviceContext, UnlockCommandQueue);
+        match unsafe { (command_queue_ptr as *const CommandQueue).as_ref() } {
+            Some(command_queue) => Ok(CommandQueueLock::new(self, command_queue)),
+            None => Err(capture.null_object(Operation::LockCommandQueue, None)),
+        }
     }
 
     pub fn begin_query<'query, QueryDataType: GetSysQueryType>(
@@ -2811,8 +2873,9 @@ impl DeferredDeviceContext {
         unsafe_member_call!(self.0, DeviceContext, Begin, immediate_context_id)
     }
 
-    pub fn finish_command_list(&self) -> Result<Boxed<CommandList>, BoxedFromNulError> {
+    pub fn finish_command_list(&self) -> Result<Boxed<CommandList>, Error> {
         let mut command_list_ptr = std::ptr::null_mut();
+        let capture = MessageCapture::begin();
         unsafe_member_call!(
             self.0,
             DeviceContext,
@@ -2820,7 +2883,7 @@ impl DeferredDeviceContext {
             &mut command_list_ptr
         );
 
-        Boxed::new(command_list_ptr)
+        capture.boxed(command_list_ptr, Operation::FinishCommandList, None)
     }
 
     /// # Safety
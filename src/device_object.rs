@@ -1,6 +1,16 @@
 use std::{ffi::CStr, marker::PhantomData};
 
-use crate::{Ported, object::Object};
+use crate::{
+    Boxed, Ported,
+    blas::BottomLevelAS,
+    buffer::Buffer,
+    buffer_view::BufferView,
+    object::{Interface, Object},
+    sampler::Sampler,
+    texture::Texture,
+    texture_view::TextureView,
+    tlas::TopLevelAS,
+};
 
 define_ported!(
     DeviceObject,
@@ -9,10 +19,71 @@ define_ported!(
     Object
 );
 
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum DeviceObjectKind {
+    Buffer,
+    BufferView,
+    Texture,
+    TextureView,
+    Sampler,
+    BottomLevelAS,
+    TopLevelAS,
+    Other,
+}
+
 impl DeviceObject {
+    pub fn desc(&self) -> &DeviceObjectAttribs {
+        let desc_ptr = unsafe_member_call!(self, DeviceObject, GetDesc);
+        unsafe { &*(desc_ptr as *const DeviceObjectAttribs) }
+    }
+
     pub fn get_unique_id(&self) -> i32 {
         unsafe_member_call!(self, DeviceObject, GetUniqueID)
     }
+
+    pub fn is<T: Interface>(&self) -> bool {
+        self.downcast_ref::<T>().is_some()
+    }
+
+    /// Returns the object as a `T` if it implements the interface of `T`.
+    pub fn downcast_ref<T: Interface>(&self) -> Option<&T> {
+        let object: &Object = self;
+        object.query_interface::<T>().map(|interface| {
+            let interface_ptr = std::ptr::from_ref(&*interface);
+            // Releases the reference added by QueryInterface : self keeps the object alive
+            drop(interface);
+            unsafe { &*interface_ptr }
+        })
+    }
+
+    pub fn object_kind(&self) -> DeviceObjectKind {
+        if self.is::<Buffer>() {
+            DeviceObjectKind::Buffer
+        } else if self.is::<BufferView>() {
+            DeviceObjectKind::BufferView
+        } else if self.is::<Texture>() {
+            DeviceObjectKind::Texture
+        } else if self.is::<TextureView>() {
+            DeviceObjectKind::TextureView
+        } else if self.is::<Sampler>() {
+            DeviceObjectKind::Sampler
+        } else if self.is::<BottomLevelAS>() {
+            DeviceObjectKind::BottomLevelAS
+        } else if self.is::<TopLevelAS>() {
+            DeviceObjectKind::TopLevelAS
+        } else {
+            DeviceObjectKind::Other
+        }
+    }
+}
+
+impl Boxed<DeviceObject> {
+    /// Converts the object into a `Boxed<T>` if it implements the interface of `T`,
+    /// or gives it back otherwise.
+    pub fn downcast<T: Interface>(self) -> Result<Boxed<T>, Self> {
+        let object: &Object = &self;
+        object.query_interface::<T>().ok_or(self)
+    }
 }
 
 #[repr(transparent)]
@@ -0,0 +1,186 @@
+use std::ffi::CStr;
+
+use crate::{
+    Boxed, Error,
+    buffer::{Buffer, BufferDesc},
+    device_context::DeviceContext,
+    error::Operation,
+    graphics_types::{BindFlags, CpuAccessFlags, MapFlags, SetShaderResourceFlags, Usage},
+    render_device::RenderDevice,
+    shader_resource_variable::ShaderResourceVariable,
+};
+
+/// A chunk of a `DynamicBufferRing`, valid until the end of the frame it was allocated in.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct DynamicAllocation {
+    pub offset: u64,
+    pub size: u64,
+}
+
+/// Suballocates aligned chunks of one dynamic buffer, for data that changes every draw.
+///
+/// The first allocation of a frame, and the first one after the ring wraps around, map the buffer
+/// with `MapFlags::Discard`. The others map it with `MapFlags::NoOverwrite` and leave the chunks
+/// of the previous draws untouched. The memory of the frames in flight is recycled by the engine
+/// when they complete, like for every dynamic buffer.
+pub struct DynamicBufferRing {
+    buffer: Boxed<Buffer>,
+    alignment: u64,
+    offset: u64,
+    discard_next_map: bool,
+}
+
+#[bon::bon]
+impl DynamicBufferRing {
+    #[builder]
+    pub fn new(
+        render_device: &RenderDevice,
+
+        name: Option<&CStr>,
+
+        size: u64,
+
+        #[builder(default = BindFlags::UniformBuffer)] bind_flags: BindFlags,
+    ) -> Result<Self, Error> {
+        // The offsets of the allocations are bound as 32-bit offsets
+        if size > u64::from(u32::MAX) {
+            return Err(Error::invalid_argument(
+                Operation::CreateBuffer,
+                name,
+                format!("the size {size} of a dynamic buffer ring must fit in 32 bits"),
+            ));
+        }
+
+        let buffer = render_device.create_buffer(
+            &BufferDesc::builder()
+                .maybe_name(name)
+                .size(size)
+                .bind_flags(bind_flags)
+                .usage(Usage::Dynamic)
+                .cpu_access_flags(CpuAccessFlags::Write)
+                .build(),
+        )?;
+
+        let buffer_properties = render_device.get_adapter_info().buffer();
+        let alignment = if bind_flags.contains(BindFlags::UniformBuffer) {
+            buffer_properties.constant_buffer_offset_alignment()
+        } else {
+            buffer_properties.structured_buffer_offset_alignment()
+        };
+
+        Ok(DynamicBufferRing {
+            buffer,
+            alignment: u64::from(alignment).max(1),
+            offset: 0,
+            discard_next_map: true,
+        })
+    }
+}
+
+impl DynamicBufferRing {
+    pub fn buffer(&self) -> &Buffer {
+        &self.buffer
+    }
+
+    /// Starts a new frame : the chunks allocated in the previous frames must not be used anymore.
+    pub fn begin_frame(&mut self) {
+        self.offset = 0;
+        self.discard_next_map = true;
+    }
+
+    /// Copies `data` to a new chunk of the buffer.
+    ///
+    /// Fails if `data` is larger than the buffer.
+    pub fn allocate(
+        &mut self,
+        device_context: &DeviceContext,
+        data: &[u8],
+    ) -> Result<DynamicAllocation, Error> {
+        let size = data.len() as u64;
+        let buffer_size = self.buffer.desc().size();
+        if size > buffer_size {
+            return Err(Error::invalid_argument(
+                Operation::AllocateDynamic,
+                self.buffer.desc().name(),
+                format!("{size} bytes do not fit in the {buffer_size} bytes of the dynamic buffer"),
+            ));
+        }
+
+        let mut offset = self.offset.next_multiple_of(self.alignment);
+        if offset + size > buffer_size {
+            offset = 0;
+            self.discard_next_map = true;
+        }
+
+        let map_flags = if self.discard_next_map {
+            MapFlags::Discard
+        } else {
+            MapFlags::NoOverwrite
+        };
+
+        let mut mapped = device_context.map_buffer_write::<u8>(&self.buffer, map_flags)?;
+        mapped[offset as usize..(offset + size) as usize].copy_from_slice(data);
+        drop(mapped);
+
+        self.offset = offset + size;
+        self.discard_next_map = false;
+
+        Ok(DynamicAllocation { offset, size })
+    }
+
+    /// Copies `value` to a new chunk of the buffer.
+    pub fn push<T: bytemuck::NoUninit>(
+        &mut self,
+        device_context: &DeviceContext,
+        value: &T,
+    ) -> Result<DynamicAllocation, Error> {
+        self.allocate(device_context, bytemuck::bytes_of(value))
+    }
+
+    /// Binds the buffer to `variable`, with the range of `allocation`.
+    pub fn bind_range(
+        &self,
+        variable: &ShaderResourceVariable,
+        allocation: DynamicAllocation,
+        array_index: Option<u32>,
+        flags: SetShaderResourceFlags,
+    ) {
+        variable.set_buffer_range(
+            &self.buffer,
+            allocation.offset,
+            allocation.size,
+            array_index,
+            flags,
+        )
+    }
+
+    /// Binds the first `chunk_size` bytes of the buffer to `variable`, so that the chunks
+    /// can then be selected with `bind_offset` without binding the buffer again.
+    pub fn bind_dynamic(
+        &self,
+        variable: &ShaderResourceVariable,
+        chunk_size: u64,
+        array_index: Option<u32>,
+        flags: SetShaderResourceFlags,
+    ) {
+        variable.set_buffer_range(&self.buffer, 0, chunk_size, array_index, flags)
+    }
+
+    /// Selects `allocation` in the range bound to `variable` by `bind_dynamic`.
+    ///
+    /// The allocation must not be larger than the chunk size of `bind_dynamic`.
+    ///
+    /// # Panics
+    /// Panics if the offset of `allocation` does not fit in 32 bits, which can not happen
+    /// for the allocations of the ring since `new` rejects the larger buffers.
+    pub fn bind_offset(
+        &self,
+        variable: &ShaderResourceVariable,
+        allocation: DynamicAllocation,
+        array_index: Option<u32>,
+    ) {
+        let offset = u32::try_from(allocation.offset)
+            .expect("the offset of a dynamic allocation must fit in 32 bits");
+        variable.set_buffer_offset(offset, array_index)
+    }
+}
@@ -1,13 +1,18 @@
-use std::{marker::PhantomData, os::raw::c_void, path::Path};
+use std::{marker::PhantomData, os::raw::c_void, path::Path, sync::Arc};
 
 use crate::{
-    APIInfo, Boxed, BoxedFromNulError, Dearchiver, ImmediateContextCreateInfo, OpenXRAttribs,
+    APIInfo, Boxed, Dearchiver, Error, ImmediateContextCreateInfo, OpenXRAttribs, Ported,
     ValidationFlags,
     data_blob::DataBlob,
+    debug_output::{
+        DebugMessageSeverity, MessageCallbackGuard, install_message_hook, replace_message_callback,
+    },
+    error::{MessageCapture, Operation},
     graphics_types::{DeviceFeatures, GraphicsAdapterInfo, Version},
     memory_allocator::MemoryAllocator,
     object::Object,
     shader::ShaderSourceInputStreamFactory,
+    thread_pool::ThreadPool,
 };
 
 #[repr(transparent)]
@@ -25,13 +30,19 @@ impl DearchiverCreateInfo {
 }
 
 #[repr(transparent)]
-pub struct EngineCreateInfo<'immediate_context_info, 'xr_attribs>(
+pub struct EngineCreateInfo<'immediate_context_info, 'xr_attribs, 'thread_pool>(
     pub(crate) diligent_sys::EngineCreateInfo,
-    PhantomData<(&'immediate_context_info (), &'xr_attribs ())>,
+    PhantomData<(
+        &'immediate_context_info (),
+        &'xr_attribs (),
+        &'thread_pool (),
+    )>,
 );
 
 #[bon::bon]
-impl<'immediate_context_info, 'xr_attribs> EngineCreateInfo<'immediate_context_info, 'xr_attribs> {
+impl<'immediate_context_info, 'xr_attribs, 'thread_pool>
+    EngineCreateInfo<'immediate_context_info, 'xr_attribs, 'thread_pool>
+{
     #[builder]
     pub fn new(
         #[builder(default = diligent_sys::DILIGENT_API_VERSION)] engine_api_version: u32,
@@ -45,8 +56,7 @@ impl<'immediate_context_info, 'xr_attribs> EngineCreateInfo<'immediate_context_i
         #[builder(default = ValidationFlags::None)] validation_flags: ValidationFlags,
         #[builder(default = 0xFFFFFFFF)] num_async_shader_compilation_threads: u32,
         xr_attribs: Option<&'xr_attribs OpenXRAttribs>,
-        // TODO
-        //IThreadPool* pAsyncShaderCompilationThreadPool DEFAULT_INITIALIZER(nullptr);
+        async_shader_compilation_thread_pool: Option<&'thread_pool ThreadPool>,
     ) -> Self {
         EngineCreateInfo(
             diligent_sys::EngineCreateInfo {
@@ -67,7 +77,8 @@ impl<'immediate_context_info, 'xr_attribs> EngineCreateInfo<'immediate_context_i
                 Features: features.0,
                 EnableValidation: enable_validation,
                 ValidationFlags: validation_flags.bits(),
-                pAsyncShaderCompilationThreadPool: std::ptr::null_mut(),
+                pAsyncShaderCompilationThreadPool: async_shader_compilation_thread_pool
+                    .map_or(std::ptr::null_mut(), |thread_pool| thread_pool.sys_ptr()),
                 NumAsyncShaderCompilationThreads: num_async_shader_compilation_threads,
                 pXRAttribs: xr_attribs.map_or(std::ptr::null(), |attribs| &attribs.0),
                 Padding: 0,
@@ -77,7 +88,7 @@ impl<'immediate_context_info, 'xr_attribs> EngineCreateInfo<'immediate_context_i
     }
 }
 
-impl EngineCreateInfo<'_, '_> {
+impl EngineCreateInfo<'_, '_, '_> {
     pub fn engine_api_version(&self) -> u32 {
         self.0.EngineAPIVersion as u32
     }
@@ -136,7 +147,7 @@ impl EngineFactory {
     pub fn create_default_shader_source_stream_factory(
         &self,
         search_directories: &[&Path],
-    ) -> Result<Boxed<ShaderSourceInputStreamFactory>, BoxedFromNulError> {
+    ) -> Result<Boxed<ShaderSourceInputStreamFactory>, Error> {
         let mut search = String::new();
 
         search_directories.iter().for_each(|&dir| {
@@ -147,6 +158,7 @@ impl EngineFactory {
         let search = std::ffi::CString::new(search).unwrap();
 
         let mut stream_factory_ptr = std::ptr::null_mut();
+        let capture = MessageCapture::begin();
         unsafe_member_call!(
             self,
             EngineFactory,
@@ -155,14 +167,16 @@ impl EngineFactory {
             &mut stream_factory_ptr
         );
 
-        Boxed::new(stream_factory_ptr)
+        capture.boxed(
+            stream_factory_ptr,
+            Operation::CreateShaderSourceStreamFactory,
+            None,
+        )
     }
 
-    pub fn create_empty_data_blob(
-        &self,
-        initial_size: usize,
-    ) -> Result<Boxed<DataBlob>, BoxedFromNulError> {
+    pub fn create_empty_data_blob(&self, initial_size: usize) -> Result<Boxed<DataBlob>, Error> {
         let mut data_blob_ptr = std::ptr::null_mut();
+        let capture = MessageCapture::begin();
         unsafe_member_call!(
             self,
             EngineFactory,
@@ -172,11 +186,12 @@ impl EngineFactory {
             &mut data_blob_ptr
         );
 
-        Boxed::new(data_blob_ptr)
+        capture.boxed(data_blob_ptr, Operation::CreateDataBlob, None)
     }
 
-    pub fn create_data_blob<T>(&self, data: &T) -> Result<Boxed<DataBlob>, BoxedFromNulError> {
+    pub fn create_data_blob<T>(&self, data: &T) -> Result<Boxed<DataBlob>, Error> {
         let mut data_blob_ptr = std::ptr::null_mut();
+        let capture = MessageCapture::begin();
         unsafe_member_call!(
             self,
             EngineFactory,
@@ -186,7 +201,7 @@ impl EngineFactory {
             &mut data_blob_ptr
         );
 
-        Boxed::new(data_blob_ptr)
+        capture.boxed(data_blob_ptr, Operation::CreateDataBlob, None)
     }
 
     pub fn enumerate_adapters(&self, version: Version) -> Vec<GraphicsAdapterInfo> {
@@ -232,8 +247,9 @@ impl EngineFactory {
     pub fn create_dearchiver(
         &self,
         create_info: &DearchiverCreateInfo,
-    ) -> Result<Boxed<Dearchiver>, BoxedFromNulError> {
+    ) -> Result<Boxed<Dearchiver>, Error> {
         let mut dearchiver_ptr = std::ptr::null_mut();
+        let capture = MessageCapture::begin();
         unsafe_member_call!(
             self,
             EngineFactory,
@@ -241,18 +257,29 @@ impl EngineFactory {
             &create_info.0,
             &mut dearchiver_ptr
         );
-        Boxed::new(dearchiver_ptr)
+        capture.boxed(dearchiver_ptr, Operation::CreateDearchiver, None)
     }
 
-    pub fn set_message_callback(&mut self, callback: diligent_sys::DebugMessageCallbackType) {
-        unsafe_member_call!(self, EngineFactory, SetMessageCallback, callback)
+    /// Registers a closure receiving every message reported by the engine.
+    /// The previously registered callback is restored when the returned guard is dropped.
+    pub fn set_message_callback(
+        &self,
+        callback: impl Fn(DebugMessageSeverity, &str, Option<&str>, Option<&str>, u32)
+        + Send
+        + Sync
+        + 'static,
+    ) -> MessageCallbackGuard {
+        install_message_hook(self);
+        MessageCallbackGuard::new(replace_message_callback(Some(Arc::new(callback))))
     }
 
     pub fn set_break_on_error(&mut self, break_on_error: bool) {
         unsafe_member_call!(self, EngineFactory, SetBreakOnError, break_on_error)
     }
 
-    pub fn set_memory_allocator(&mut self, allocator: &MemoryAllocator) {
+    /// The engine keeps using the allocator to free the memory it allocated
+    /// until the end of the program, hence the `'static` lifetime.
+    pub fn set_memory_allocator(&mut self, allocator: &'static MemoryAllocator) {
         unsafe_member_call!(
             &self,
             EngineFactory,
@@ -0,0 +1,298 @@
+use std::{cell::RefCell, ffi::CStr, fmt::Display, marker::PhantomData, os::raw::c_char};
+
+use crate::{Boxed, Ported, debug_output::DebugMessageSeverity};
+
+/// A message reported by the engine through its debug message callback.
+#[derive(Clone, Debug)]
+pub struct EngineMessage {
+    pub severity: DebugMessageSeverity,
+    pub message: String,
+    pub function: Option<String>,
+    pub file: Option<String>,
+    pub line: u32,
+}
+
+impl Display for EngineMessage {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match (&self.function, &self.file) {
+            (Some(function), Some(file)) => write!(
+                f,
+                "{} in {}() ({}, {}): {}",
+                self.severity, function, file, self.line, self.message
+            ),
+            _ => write!(f, "{}: {}", self.severity, self.message),
+        }
+    }
+}
+
+/// The engine call that failed.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum Operation {
+    CreateBuffer,
+    CreateBufferView,
+    CreateTexture,
+    CreateTextureView,
+    CreateSampler,
+    CreateResourceMapping,
+    CreateGraphicsPipelineState,
+    CreateComputePipelineState,
+    CreateRayTracingPipelineState,
+    CreateTilePipelineState,
+    CreateFence,
+    CreateQuery,
+    CreateRenderPass,
+    CreateFramebuffer,
+    CreateBLAS,
+    CreateTLAS,
+    CreateSBT,
+    CreatePipelineResourceSignature,
+    CreateDeviceMemory,
+    CreatePipelineStateCache,
+    CreateDeferredContext,
+    CreateShaderResourceBinding,
+    CreateShaderSourceStreamFactory,
+    CreateDataBlob,
+    CreateDearchiver,
+    CreateSerializationDevice,
+    CreateGeometryPrimitive,
+    CreateDeviceAndContexts,
+    CreateSwapChain,
+    UnpackShader,
+    UnpackPipelineState,
+    UnpackResourceSignature,
+    UnpackRenderPass,
+    StoreArchive,
+    MapBuffer,
+    MapTextureSubresource,
+    LockCommandQueue,
+    FinishCommandList,
+    ReadBufferAsync,
+    ScheduleBufferUpdate,
+    ScheduleTextureUpdate,
+    AllocateDynamic,
+    AliasResources,
+    CreateUploadManager,
+}
+
+impl Display for Operation {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(match self {
+            Operation::CreateBuffer => "create_buffer",
+            Operation::CreateBufferView => "create_buffer_view",
+            Operation::CreateTexture => "create_texture",
+            Operation::CreateTextureView => "create_texture_view",
+            Operation::CreateSampler => "create_sampler",
+            Operation::CreateResourceMapping => "create_resource_mapping",
+            Operation::CreateGraphicsPipelineState => "create_graphics_pipeline_state",
+            Operation::CreateComputePipelineState => "create_compute_pipeline_state",
+            Operation::CreateRayTracingPipelineState => "create_ray_tracing_pipeline_state",
+            Operation::CreateTilePipelineState => "create_tile_pipeline_state",
+            Operation::CreateFence => "create_fence",
+            Operation::CreateQuery => "create_query",
+            Operation::CreateRenderPass => "create_render_pass",
+            Operation::CreateFramebuffer => "create_framebuffer",
+            Operation::CreateBLAS => "create_blas",
+            Operation::CreateTLAS => "create_tlas",
+            Operation::CreateSBT => "create_sbt",
+            Operation::CreatePipelineResourceSignature => "create_pipeline_resource_signature",
+            Operation::CreateDeviceMemory => "create_device_memory",
+            Operation::CreatePipelineStateCache => "create_pipeline_state_cache",
+            Operation::CreateDeferredContext => "create_deferred_context",
+            Operation::CreateShaderResourceBinding => "create_shader_resource_binding",
+            Operation::CreateShaderSourceStreamFactory => {
+                "create_default_shader_source_stream_factory"
+            }
+            Operation::CreateDataBlob => "create_data_blob",
+            Operation::CreateDearchiver => "create_dearchiver",
+            Operation::CreateSerializationDevice => "create_serialization_device",
+            Operation::CreateGeometryPrimitive => "create_geometry_primitive",
+            Operation::CreateDeviceAndContexts => "create_device_and_contexts",
+            Operation::CreateSwapChain => "create_swap_chain",
+            Operation::UnpackShader => "unpack_shader",
+            Operation::UnpackPipelineState => "unpack_pipeline_state",
+            Operation::UnpackResourceSignature => "unpack_resource_signature",
+            Operation::UnpackRenderPass => "unpack_render_pass",
+            Operation::StoreArchive => "store",
+            Operation::MapBuffer => "map_buffer",
+            Operation::MapTextureSubresource => "map_texture_subresource",
+            Operation::LockCommandQueue => "lock_command_queue",
+            Operation::FinishCommandList => "finish_command_list",
+            Operation::ReadBufferAsync => "read_buffer_async",
+            Operation::ScheduleBufferUpdate => "schedule_buffer_update",
+            Operation::ScheduleTextureUpdate => "schedule_texture_update",
+            Operation::AllocateDynamic => "allocate",
+            Operation::AliasResources => "StateTransitionDesc::aliasing",
+            Operation::CreateUploadManager => "GpuUploadManager::new",
+        })
+    }
+}
+
+#[derive(Clone, Debug)]
+pub enum Error {
+    /// The engine returned a null object.
+    NullObject {
+        operation: Operation,
+        object_name: Option<String>,
+        messages: Vec<EngineMessage>,
+    },
+    /// The engine returned a null pointer when mapping a resource.
+    MapFailed {
+        operation: Operation,
+        object_name: Option<String>,
+        messages: Vec<EngineMessage>,
+    },
+    /// The arguments were rejected before calling the engine.
+    InvalidArgument {
+        operation: Operation,
+        object_name: Option<String>,
+        reason: String,
+    },
+}
+
+impl Error {
+    pub(crate) fn invalid_argument(
+        operation: Operation,
+        object_name: Option<&CStr>,
+        reason: impl Into<String>,
+    ) -> Self {
+        Error::InvalidArgument {
+            operation,
+            object_name: object_name.map(|name| name.to_string_lossy().into_owned()),
+            reason: reason.into(),
+        }
+    }
+
+    pub fn operation(&self) -> Operation {
+        match self {
+            Error::NullObject { operation, .. }
+            | Error::MapFailed { operation, .. }
+            | Error::InvalidArgument { operation, .. } => *operation,
+        }
+    }
+
+    pub fn object_name(&self) -> Option<&str> {
+        match self {
+            Error::NullObject { object_name, .. }
+            | Error::MapFailed { object_name, .. }
+            | Error::InvalidArgument { object_name, .. } => object_name.as_deref(),
+        }
+    }
+
+    /// The messages the engine reported on the calling thread
+    /// while the failed operation was running.
+    pub fn messages(&self) -> &[EngineMessage] {
+        match self {
+            Error::NullObject { messages, .. } | Error::MapFailed { messages, .. } => messages,
+            Error::InvalidArgument { .. } => &[],
+        }
+    }
+}
+
+impl std::error::Error for Error {}
+
+impl Display for Error {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Error::NullObject { operation, .. } => write!(f, "{operation} failed")?,
+            Error::MapFailed { operation, .. } => write!(f, "{operation} returned a null pointer")?,
+            Error::InvalidArgument { operation, .. } => write!(f, "{operation} was misused")?,
+        }
+
+        if let Some(name) = self.object_name() {
+            write!(f, " for '{name}'")?;
+        }
+
+        if let Error::InvalidArgument { reason, .. } = self {
+            return write!(f, ": {reason}");
+        }
+
+        let mut messages = self
+            .messages()
+            .iter()
+            .filter(|message| message.severity >= DebugMessageSeverity::Warning)
+            .peekable();
+
+        if messages.peek().is_none() {
+            write!(f, " (the engine reported no errors)")
+        } else {
+            messages.try_for_each(|message| write!(f, "\n    {message}"))
+        }
+    }
+}
+
+thread_local! {
+    static CAPTURED_MESSAGES: RefCell<Vec<Vec<EngineMessage>>> = const { RefCell::new(Vec::new()) };
+}
+
+/// Records the engine messages reported on the current thread until it is dropped.
+///
+/// Captures can be nested : the messages recorded by an inner capture are forwarded
+/// to the enclosing one when the inner capture is dropped.
+pub(crate) struct MessageCapture(PhantomData<*const ()>);
+
+impl MessageCapture {
+    pub(crate) fn begin() -> Self {
+        CAPTURED_MESSAGES.with_borrow_mut(|stack| stack.push(Vec::new()));
+        MessageCapture(PhantomData)
+    }
+
+    pub(crate) fn messages(&self) -> Vec<EngineMessage> {
+        CAPTURED_MESSAGES.with_borrow(|stack| stack.last().cloned().unwrap_or_default())
+    }
+
+    pub(crate) fn null_object(self, operation: Operation, object_name: Option<&CStr>) -> Error {
+        Error::NullObject {
+            operation,
+            object_name: object_name.map(|name| name.to_string_lossy().into_owned()),
+            messages: self.messages(),
+        }
+    }
+
+    pub(crate) fn map_failed(self, operation: Operation, object_name: Option<&CStr>) -> Error {
+        Error::MapFailed {
+            operation,
+            object_name: object_name.map(|name| name.to_string_lossy().into_owned()),
+            messages: self.messages(),
+        }
+    }
+
+    pub(crate) fn boxed<T: Ported>(
+        self,
+        ptr: *mut T::SysType,
+        operation: Operation,
+        object_name: Option<&CStr>,
+    ) -> Result<Boxed<T>, Error> {
+        Boxed::new(ptr).ok_or_else(|| self.null_object(operation, object_name))
+    }
+}
+
+impl Drop for MessageCapture {
+    fn drop(&mut self) {
+        CAPTURED_MESSAGES.with_borrow_mut(|stack| {
+            if let Some(messages) = stack.pop()
+                && let Some(parent) = stack.last_mut()
+            {
+                parent.extend(messages);
+            }
+        });
+    }
+}
+
+pub(crate) fn object_name<'a>(name: *const c_char) -> Option<&'a CStr> {
+    if name.is_null() {
+        None
+    } else {
+        Some(unsafe { CStr::from_ptr(name) })
+    }
+}
+
+/// Appends a message to the innermost capture of the current thread, if any.
+pub(crate) fn record_message(message: &EngineMessage) {
+    let _ = CAPTURED_MESSAGES.try_with(|stack| {
+        if let Ok(mut stack) = stack.try_borrow_mut()
+            && let Some(messages) = stack.last_mut()
+        {
+            messages.push(message.clone());
+        }
+    });
+}
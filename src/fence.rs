@@ -1,8 +1,22 @@
-use std::{ffi::CStr, marker::PhantomData, ops::Deref};
+use std::{
+    collections::VecDeque,
+    ffi::CStr,
+    future::Future,
+    marker::PhantomData,
+    ops::Deref,
+    pin::Pin,
+    sync::{Arc, Mutex, Weak},
+    task::{Context, Poll, Waker},
+};
 
 use static_assertions::const_assert_eq;
 
-use crate::device_object::{DeviceObject, DeviceObjectAttribs};
+use crate::{
+    Boxed, Error, SharedBoxed,
+    device_context::DeviceContext,
+    device_object::{DeviceObject, DeviceObjectAttribs},
+    render_device::RenderDevice,
+};
 
 define_ported!(
     Fence,
@@ -80,6 +94,196 @@ impl Fence {
     pub fn wait(&self, value: u64) {
         unsafe_member_call!(self, Fence, Wait, value)
     }
+
+    /// Returns a future that completes when the fence reaches `value`.
+    ///
+    /// The future holds a reference to the fence, and can be awaited from any thread.
+    /// A pending future is woken by `wake_completed_fence_waits`, which `ImmediateDeviceContext::finish_frame`
+    /// calls at the end of every frame.
+    pub fn wait_async(&self, value: u64) -> FenceWait {
+        FenceWait {
+            state: Arc::new(FenceWaitState {
+                fence: Boxed::into_shared(Boxed::from_ref(self)),
+                value,
+                waker: Mutex::new(None),
+            }),
+            registered: false,
+        }
+    }
+}
+
+struct FenceWaitState {
+    fence: SharedBoxed<Fence>,
+    value: u64,
+    waker: Mutex<Option<Waker>>,
+}
+
+impl FenceWaitState {
+    fn is_completed(&self) -> bool {
+        self.fence.get_completed_value() >= self.value
+    }
+}
+
+// The pending waits are only weakly referenced : the fence is released with the future.
+static PENDING_FENCE_WAITS: Mutex<Vec<Weak<FenceWaitState>>> = Mutex::new(Vec::new());
+
+/// A future that completes when a fence reaches a value, created by `Fence::wait_async`.
+pub struct FenceWait {
+    state: Arc<FenceWaitState>,
+    registered: bool,
+}
+
+impl Future for FenceWait {
+    type Output = ();
+
+    fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        let this = self.get_mut();
+
+        if this.state.is_completed() {
+            return Poll::Ready(());
+        }
+
+        *this
+            .state
+            .waker
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner()) = Some(cx.waker().clone());
+
+        if !this.registered {
+            PENDING_FENCE_WAITS
+                .lock()
+                .unwrap_or_else(|poisoned| poisoned.into_inner())
+                .push(Arc::downgrade(&this.state));
+            this.registered = true;
+        }
+
+        // The fence may have been reached, and the waits woken, before the waker was stored
+        if this.state.is_completed() {
+            Poll::Ready(())
+        } else {
+            Poll::Pending
+        }
+    }
+}
+
+/// Wakes the futures returned by `Fence::wait_async` whose fence has reached its value.
+///
+/// This is called by `ImmediateDeviceContext::finish_frame`. Applications that wait for fences
+/// without finishing frames, or that want a lower latency, can call it from any thread,
+/// for instance from a thread that blocks on `Fence::wait`.
+pub fn wake_completed_fence_waits() {
+    let mut wakers = Vec::new();
+
+    PENDING_FENCE_WAITS
+        .lock()
+        .unwrap_or_else(|poisoned| poisoned.into_inner())
+        .retain(|pending_wait| {
+            // The future was dropped
+            let Some(state) = pending_wait.upgrade() else {
+                return false;
+            };
+
+            if !state.is_completed() {
+                return true;
+            }
+
+            wakers.extend(
+                state
+                    .waker
+                    .lock()
+                    .unwrap_or_else(|poisoned| poisoned.into_inner())
+                    .take(),
+            );
+            false
+        });
+
+    // Woken outside of the lock : the executors may poll the futures immediately
+    wakers.into_iter().for_each(Waker::wake);
+}
+
+/// A submission of a `TimelineFence`.
+#[derive(Clone, Copy, Debug)]
+pub struct FenceSubmission {
+    /// The value signaled by the submission.
+    pub value: u64,
+    /// The queue of the context that signaled the value.
+    pub queue_id: u8,
+    /// The frame number of the context when the value was signaled.
+    pub frame_number: u64,
+}
+
+/// A fence whose values are handed out in increasing order, one for each submission.
+pub struct TimelineFence {
+    fence: Boxed<Fence>,
+    last_signaled_value: u64,
+    pending_submissions: VecDeque<FenceSubmission>,
+}
+
+impl TimelineFence {
+    pub fn new(render_device: &RenderDevice, fence_desc: &FenceDesc) -> Result<Self, Error> {
+        Ok(TimelineFence {
+            fence: render_device.create_fence(fence_desc)?,
+            last_signaled_value: 0,
+            pending_submissions: VecDeque::new(),
+        })
+    }
+
+    pub fn fence(&self) -> &Fence {
+        &self.fence
+    }
+
+    /// Enqueues the signal of the next value in `device_context`, and returns the value.
+    pub fn signal(&mut self, device_context: &DeviceContext) -> u64 {
+        self.last_signaled_value += 1;
+
+        device_context.enqueue_signal(&self.fence, self.last_signaled_value);
+
+        self.pending_submissions.push_back(FenceSubmission {
+            value: self.last_signaled_value,
+            queue_id: device_context.desc().queue_id(),
+            frame_number: device_context.get_frame_number(),
+        });
+
+        self.last_signaled_value
+    }
+
+    pub fn last_signaled_value(&self) -> u64 {
+        self.last_signaled_value
+    }
+
+    pub fn completed_value(&self) -> u64 {
+        self.fence.get_completed_value()
+    }
+
+    pub fn is_completed(&self, value: u64) -> bool {
+        self.completed_value() >= value
+    }
+
+    /// The submission that signaled `value`, if it has not been retired yet.
+    pub fn submission(&self, value: u64) -> Option<&FenceSubmission> {
+        self.pending_submissions
+            .binary_search_by_key(&value, |submission| submission.value)
+            .ok()
+            .map(|index| &self.pending_submissions[index])
+    }
+
+    /// Removes the completed submissions, and returns them in the order they were signaled.
+    pub fn retire_completed(&mut self) -> impl Iterator<Item = FenceSubmission> + '_ {
+        let completed_value = self.fence.get_completed_value();
+        let completed_count = self
+            .pending_submissions
+            .partition_point(|submission| submission.value <= completed_value);
+
+        self.pending_submissions.drain(..completed_count)
+    }
+
+    pub fn wait(&self, value: u64) {
+        self.fence.wait(value)
+    }
+
+    pub fn wait_async(&self, value: u64) -> FenceWait {
+        self.fence.wait_async(value)
+    }
 }
 
 // # Safety : Access to Fence can be thread safe
@@ -0,0 +1,198 @@
+use std::{
+    borrow::Cow,
+    os::raw::c_void,
+    sync::atomic::{AtomicUsize, Ordering},
+};
+
+use crate::{
+    data_blob::DataBlob,
+    object::{Object, RustObject},
+};
+
+define_ported!(
+    FileStream,
+    diligent_sys::IFileStream,
+    diligent_sys::IFileStreamMethods : 7,
+    Object
+);
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum SeekOrigin {
+    Start,
+    Current,
+    End,
+}
+
+impl FileStream {
+    /// Fills `data` with the next bytes of the stream.
+    /// Returns false if the stream does not contain enough data.
+    pub fn read(&mut self, data: &mut [u8]) -> bool {
+        unsafe_member_call!(
+            self,
+            FileStream,
+            Read,
+            data.as_mut_ptr() as *mut c_void,
+            data.len()
+        )
+    }
+
+    /// Reads the rest of the stream into `data`, which is resized to fit the content.
+    pub fn read_blob(&mut self, data: &mut DataBlob) {
+        unsafe_member_call!(self, FileStream, ReadBlob, std::ptr::from_mut(&mut data.0))
+    }
+
+    pub fn write(&mut self, data: &[u8]) -> bool {
+        unsafe_member_call!(
+            self,
+            FileStream,
+            Write,
+            data.as_ptr() as *const c_void,
+            data.len()
+        )
+    }
+
+    pub fn get_size(&self) -> usize {
+        unsafe_member_call!(self, FileStream, GetSize)
+    }
+
+    pub fn is_valid(&self) -> bool {
+        unsafe_member_call!(self, FileStream, IsValid)
+    }
+
+    pub fn get_pos(&self) -> usize {
+        unsafe_member_call!(self, FileStream, GetPos)
+    }
+
+    pub fn set_pos(&mut self, offset: usize, origin: SeekOrigin) -> bool {
+        let origin = match origin {
+            SeekOrigin::Start => SEEK_SET,
+            SeekOrigin::Current => SEEK_CUR,
+            SeekOrigin::End => SEEK_END,
+        };
+        unsafe_member_call!(self, FileStream, SetPos, offset, origin)
+    }
+}
+
+// Values of the C standard library, used by the engine for the origin of SetPos
+const SEEK_SET: i32 = 0;
+const SEEK_CUR: i32 = 1;
+const SEEK_END: i32 = 2;
+
+/// A read-only stream over bytes owned by Rust.
+pub(crate) struct RustFileStream {
+    data: Cow<'static, [u8]>,
+    pos: AtomicUsize,
+}
+
+type RustFileStreamObject = RustObject<diligent_sys::IFileStreamVtbl, RustFileStream>;
+
+impl RustFileStream {
+    pub(crate) fn create(data: Cow<'static, [u8]>) -> *mut diligent_sys::IFileStream {
+        RustFileStreamObject::create(
+            diligent_sys::IFileStreamVtbl {
+                Object: RustFileStreamObject::OBJECT_METHODS,
+                FileStream: diligent_sys::IFileStreamMethods {
+                    Read: Some(Self::read),
+                    ReadBlob: Some(Self::read_blob),
+                    Write: Some(Self::write),
+                    GetSize: Some(Self::get_size),
+                    IsValid: Some(Self::is_valid),
+                    GetPos: Some(Self::get_pos),
+                    SetPos: Some(Self::set_pos),
+                },
+            },
+            RustFileStream {
+                data,
+                pos: AtomicUsize::new(0),
+            },
+        )
+    }
+
+    fn remaining(&self) -> &[u8] {
+        let pos = self.pos.load(Ordering::Relaxed);
+        &self.data[pos.min(self.data.len())..]
+    }
+
+    fn advance(&self, count: usize) {
+        self.pos.fetch_add(count, Ordering::Relaxed);
+    }
+
+    unsafe fn from_sys<'a>(this: *mut diligent_sys::IFileStream) -> &'a Self {
+        unsafe { &RustFileStreamObject::from_sys(this).value }
+    }
+
+    unsafe extern "C" fn read(
+        this: *mut diligent_sys::IFileStream,
+        data: *mut c_void,
+        buffer_size: usize,
+    ) -> bool {
+        let this = unsafe { Self::from_sys(this) };
+        let remaining = this.remaining();
+
+        if remaining.len() < buffer_size {
+            return false;
+        }
+
+        unsafe {
+            std::ptr::copy_nonoverlapping(remaining.as_ptr(), data as *mut u8, buffer_size);
+        }
+        this.advance(buffer_size);
+        true
+    }
+
+    unsafe extern "C" fn read_blob(
+        this: *mut diligent_sys::IFileStream,
+        data: *mut diligent_sys::IDataBlob,
+    ) {
+        let this = unsafe { Self::from_sys(this) };
+        let remaining = this.remaining();
+
+        let data_blob = unsafe { &mut *(data as *mut DataBlob) };
+        data_blob.resize(remaining.len());
+        data_blob.as_bytes_mut().copy_from_slice(remaining);
+        this.advance(remaining.len());
+    }
+
+    unsafe extern "C" fn write(
+        _this: *mut diligent_sys::IFileStream,
+        _data: *const c_void,
+        _size: usize,
+    ) -> bool {
+        false
+    }
+
+    unsafe extern "C" fn get_size(this: *mut diligent_sys::IFileStream) -> usize {
+        unsafe { Self::from_sys(this) }.data.len()
+    }
+
+    unsafe extern "C" fn is_valid(_this: *mut diligent_sys::IFileStream) -> bool {
+        true
+    }
+
+    unsafe extern "C" fn get_pos(this: *mut diligent_sys::IFileStream) -> usize {
+        unsafe { Self::from_sys(this) }.pos.load(Ordering::Relaxed)
+    }
+
+    unsafe extern "C" fn set_pos(
+        this: *mut diligent_sys::IFileStream,
+        offset: usize,
+        origin: i32,
+    ) -> bool {
+        let this = unsafe { Self::from_sys(this) };
+
+        let base = match origin {
+            SEEK_SET => 0,
+            SEEK_CUR => this.pos.load(Ordering::Relaxed),
+            SEEK_END => this.data.len(),
+            _ => return false,
+        };
+
+        match base.checked_add(offset) {
+            Some(pos) if pos <= this.data.len() => {
+                this.pos.store(pos, Ordering::Relaxed);
+                true
+            }
+            _ => false,
+        }
+    }
+}
@@ -0,0 +1,157 @@
+use std::{any::Any, num::NonZero};
+
+use crate::{
+    Boxed, Error, Ported,
+    device_context::DeviceContext,
+    fence::{Fence, FenceDesc},
+    render_device::RenderDevice,
+};
+
+struct Frame<T> {
+    value: T,
+    /// The fence value signaled at the end of the last frame that used this copy.
+    fence_value: u64,
+    deferred_drops: Vec<Box<dyn Any>>,
+}
+
+/// N copies of a per-frame resource, used in turn by the frames in flight.
+///
+/// The copy of a frame is chosen from the frame number of the context. `begin_frame` waits
+/// until the GPU has finished the last frame that used that copy, and `end_frame` enqueues
+/// the fence signal that marks the end of the current one.
+///
+/// The fence signal is only submitted when the context is flushed, which `SwapChain::present` does.
+/// The ring keeps a reference to the context of the last `end_frame` : when it is dropped, it flushes
+/// that context and waits for the frames in flight.
+pub struct FrameRing<T> {
+    fence: Boxed<Fence>,
+    frames: Vec<Frame<T>>,
+    current: usize,
+    last_fence_value: u64,
+    signaling_context: Option<Boxed<DeviceContext>>,
+}
+
+impl<T> FrameRing<T> {
+    /// Creates a ring of `frame_count` copies, created by `create_frame` from the index of the copy.
+    pub fn new(
+        render_device: &RenderDevice,
+        frame_count: NonZero<usize>,
+        create_frame: impl FnMut(usize) -> T,
+    ) -> Result<Self, Error> {
+        Self::from_values(
+            render_device,
+            (0..frame_count.get()).map(create_frame).collect(),
+        )
+    }
+
+    /// Same as `new`, for copies whose creation can fail.
+    pub fn try_new<E: From<Error>>(
+        render_device: &RenderDevice,
+        frame_count: NonZero<usize>,
+        create_frame: impl FnMut(usize) -> Result<T, E>,
+    ) -> Result<Self, E> {
+        let values = (0..frame_count.get())
+            .map(create_frame)
+            .collect::<Result<_, _>>()?;
+
+        Ok(Self::from_values(render_device, values)?)
+    }
+
+    fn from_values(render_device: &RenderDevice, values: Vec<T>) -> Result<Self, Error> {
+        let fence =
+            render_device.create_fence(&FenceDesc::builder().name(c"Frame ring fence").build())?;
+
+        Ok(FrameRing {
+            fence,
+            frames: values
+                .into_iter()
+                .map(|value| Frame {
+                    value,
+                    fence_value: 0,
+                    deferred_drops: Vec::new(),
+                })
+                .collect(),
+            current: 0,
+            last_fence_value: 0,
+            signaling_context: None,
+        })
+    }
+
+    pub fn frame_count(&self) -> usize {
+        self.frames.len()
+    }
+
+    /// The index of the copy used by the current frame.
+    pub fn frame_index(&self) -> usize {
+        self.current
+    }
+
+    /// Selects the copy of the current frame of `device_context`, and returns it once the GPU has released it.
+    ///
+    /// The objects whose drop was deferred by the last frame that used this copy are dropped.
+    pub fn begin_frame(&mut self, device_context: &DeviceContext) -> &mut T {
+        self.current = (device_context.get_frame_number() % self.frames.len() as u64) as usize;
+
+        let frame = &mut self.frames[self.current];
+        if self.fence.get_completed_value() < frame.fence_value {
+            self.fence.wait(frame.fence_value);
+        }
+        frame.deferred_drops.clear();
+
+        &mut frame.value
+    }
+
+    /// Enqueues the fence signal that releases the copy of the current frame when the GPU reaches it.
+    pub fn end_frame(&mut self, device_context: &DeviceContext) {
+        self.last_fence_value += 1;
+        device_context.enqueue_signal(&self.fence, self.last_fence_value);
+
+        self.frames[self.current].fence_value = self.last_fence_value;
+
+        if self
+            .signaling_context
+            .as_ref()
+            .is_none_or(|context| context.sys_ptr() != device_context.sys_ptr())
+        {
+            self.signaling_context = Some(Boxed::from_ref(device_context));
+        }
+    }
+
+    pub fn current(&self) -> &T {
+        &self.frames[self.current].value
+    }
+
+    pub fn current_mut(&mut self) -> &mut T {
+        &mut self.frames[self.current].value
+    }
+
+    /// Keeps `object` alive until the GPU has finished the current frame.
+    pub fn defer_drop(&mut self, object: impl Any) {
+        self.frames[self.current]
+            .deferred_drops
+            .push(Box::new(object));
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = &T> {
+        self.frames.iter().map(|frame| &frame.value)
+    }
+
+    pub fn iter_mut(&mut self) -> impl Iterator<Item = &mut T> {
+        self.frames.iter_mut().map(|frame| &mut frame.value)
+    }
+}
+
+impl<T> Drop for FrameRing<T> {
+    /// Waits until the GPU has finished the frames in flight, so that their copies and deferred
+    /// objects are not released while in use.
+    fn drop(&mut self) {
+        if self.fence.get_completed_value() < self.last_fence_value {
+            // The last signal may not have been submitted yet
+            if let Some(context) = &self.signaling_context {
+                let context: &DeviceContext = context;
+                unsafe_member_call!(context, DeviceContext, Flush);
+            }
+            self.fence.wait(self.last_fence_value);
+        }
+    }
+}
@@ -2,7 +2,11 @@ use bitflags::bitflags;
 use bon::Builder;
 use static_assertions::const_assert_eq;
 
-use crate::{Boxed, BoxedFromNulError, data_blob::DataBlob};
+use crate::{
+    Boxed, Error,
+    data_blob::DataBlob,
+    error::{MessageCapture, Operation},
+};
 
 bitflags! {
     #[derive(Clone, Copy)]
@@ -47,7 +51,7 @@ pub struct GeometryPrimitiveInfo {
 
 pub fn create_geometry_primitive(
     attribs: &GeometryPrimitiveAttributes,
-) -> Result<(Boxed<DataBlob>, Boxed<DataBlob>, GeometryPrimitiveInfo), BoxedFromNulError> {
+) -> Result<(Boxed<DataBlob>, Boxed<DataBlob>, GeometryPrimitiveInfo), Error> {
     enum GeometryPrimitiveType {
         Cube(diligent_sys::CubeGeometryPrimitiveAttributes),
         Sphere(diligent_sys::SphereGeometryPrimitiveAttributes),
@@ -81,6 +85,7 @@ pub fn create_geometry_primitive(
 
     let mut info = std::mem::MaybeUninit::<diligent_sys::GeometryPrimitiveInfo>::uninit();
 
+    let capture = MessageCapture::begin();
     unsafe {
         diligent_sys::Diligent_CreateGeometryPrimitive(
             match attribs {
@@ -95,21 +100,16 @@ pub fn create_geometry_primitive(
 
     let info = unsafe { info.assume_init() };
 
-    Boxed::new(vertices_ptr).and_then(|vertices| {
-        Boxed::new(indices_ptr).map(|indices| {
-            (
-                vertices,
-                indices,
-                GeometryPrimitiveInfo {
-                    num_indices: info.NumIndices as usize,
-                    num_vertices: info.NumVertices as usize,
-                    vertex_size: info.VertexSize as usize,
-                },
-            )
-        })
-    })
-}
-
-pub fn get_geometry_primitive_vertex_size(vertex_flags: GeometryPrimitiveVertexFlags) -> u32 {
-    unsafe { diligent_sys::Diligent_GetGeometryPrimitiveVertexSize(vertex_flags.bits()) }
+    match (Boxed::new(vertices_ptr), Boxed::new(indices_ptr)) {
+        (Some(vertices), Some(indices)) => Ok((
+            vertices,
+            indices,
+            GeometryPrimitiveInfo {
+                num_indices: info.NumIndices as usize,
+                num_vertices: info.NumVertices as usize,
+                vertex_size: info.VertexSize as usize,
+            },
+        )),
+        _ => Err(capture.null_object(Operation::CreateGeometryPrimitive, None)),
+    }
 }
@@ -1,45 +1,48 @@
 use std::ops::{Deref, DerefMut};
 
 use crate::{
-    Boxed, BoxedFromNulError,
+    Boxed, Error,
+    abi::debug_verify_abi,
+    debug_output::install_message_hook,
     device_context::ImmediateDeviceContext,
     engine_factory::{EngineCreateInfo, EngineFactory},
+    error::{MessageCapture, Operation},
     graphics_types::AdapterType,
     platforms::native_window::NativeWindow,
     render_device::RenderDevice,
     swap_chain::{SwapChain, SwapChainCreateInfo},
 };
 
-pub struct EngineGLCreateInfo<'immediate_context_info, 'xr_attribs> {
-    engine_create_info: EngineCreateInfo<'immediate_context_info, 'xr_attribs>,
+pub struct EngineGLCreateInfo<'immediate_context_info, 'xr_attribs, 'thread_pool> {
+    engine_create_info: EngineCreateInfo<'immediate_context_info, 'xr_attribs, 'thread_pool>,
 
     window: NativeWindow,
     zero_to_one_ndz: bool,
     preferred_adapter_type: AdapterType,
 }
 
-impl<'immediate_context_info, 'xr_attribs> Deref
-    for EngineGLCreateInfo<'immediate_context_info, 'xr_attribs>
+impl<'immediate_context_info, 'xr_attribs, 'thread_pool> Deref
+    for EngineGLCreateInfo<'immediate_context_info, 'xr_attribs, 'thread_pool>
 {
-    type Target = EngineCreateInfo<'immediate_context_info, 'xr_attribs>;
+    type Target = EngineCreateInfo<'immediate_context_info, 'xr_attribs, 'thread_pool>;
 
     fn deref(&self) -> &Self::Target {
         &self.engine_create_info
     }
 }
 
-impl DerefMut for EngineGLCreateInfo<'_, '_> {
+impl DerefMut for EngineGLCreateInfo<'_, '_, '_> {
     fn deref_mut(&mut self) -> &mut Self::Target {
         &mut self.engine_create_info
     }
 }
 
-impl<'immediate_context_info, 'xr_attribs>
-    EngineGLCreateInfo<'immediate_context_info, 'xr_attribs>
+impl<'immediate_context_info, 'xr_attribs, 'thread_pool>
+    EngineGLCreateInfo<'immediate_context_info, 'xr_attribs, 'thread_pool>
 {
     pub fn new(
         window: NativeWindow,
-        engine_create_info: EngineCreateInfo<'immediate_context_info, 'xr_attribs>,
+        engine_create_info: EngineCreateInfo<'immediate_context_info, 'xr_attribs, 'thread_pool>,
     ) -> Self {
         EngineGLCreateInfo {
             engine_create_info,
@@ -50,7 +53,7 @@ impl<'immediate_context_info, 'xr_attribs>
     }
 }
 
-impl From<&EngineGLCreateInfo<'_, '_>> for diligent_sys::EngineGLCreateInfo {
+impl From<&EngineGLCreateInfo<'_, '_, '_>> for diligent_sys::EngineGLCreateInfo {
     fn from(value: &EngineGLCreateInfo) -> Self {
         diligent_sys::EngineGLCreateInfo {
             _EngineCreateInfo: value.engine_create_info.0,
@@ -79,7 +82,7 @@ impl EngineFactoryOpenGL {
             Boxed<ImmediateDeviceContext>,
             Boxed<SwapChain>,
         ),
-        BoxedFromNulError,
+        Error,
     > {
         let engine_ci = engine_ci.into();
 
@@ -87,6 +90,7 @@ impl EngineFactoryOpenGL {
         let mut device_context_ptr = std::ptr::null_mut();
         let mut swap_chain_ptr = std::ptr::null_mut();
 
+        let capture = MessageCapture::begin();
         unsafe_member_call!(
             self,
             EngineFactoryOpenGL,
@@ -98,12 +102,14 @@ impl EngineFactoryOpenGL {
             &mut swap_chain_ptr
         );
 
-        Boxed::new(render_device_ptr).and_then(|render_device| {
-            Boxed::new(device_context_ptr).and_then(|device_context| {
-                Boxed::new(swap_chain_ptr)
-                    .map(|swap_chain| (render_device, device_context, swap_chain))
+        Boxed::new(render_device_ptr)
+            .and_then(|render_device| {
+                Boxed::new(device_context_ptr).and_then(|device_context| {
+                    Boxed::new(swap_chain_ptr)
+                        .map(|swap_chain| (render_device, device_context, swap_chain))
+                })
             })
-        })
+            .ok_or_else(|| capture.null_object(Operation::CreateDeviceAndContexts, None))
     }
 
     //TODO pub fn create_hlsl2glsl_converter(&self) -> Result<HLSL2GLSLConverter, ()>{}
@@ -111,12 +117,13 @@ impl EngineFactoryOpenGL {
     pub fn attach_to_active_gl_context(
         &self,
         engine_ci: &EngineGLCreateInfo,
-    ) -> Result<(Boxed<RenderDevice>, Boxed<ImmediateDeviceContext>), BoxedFromNulError> {
+    ) -> Result<(Boxed<RenderDevice>, Boxed<ImmediateDeviceContext>), Error> {
         let engine_ci = engine_ci.into();
 
         let mut render_device_ptr = std::ptr::null_mut();
         let mut device_context_ptr = std::ptr::null_mut();
 
+        let capture = MessageCapture::begin();
         unsafe_member_call!(
             self,
             EngineFactoryOpenGL,
@@ -126,12 +133,20 @@ impl EngineFactoryOpenGL {
             &mut device_context_ptr
         );
 
-        Boxed::new(render_device_ptr).and_then(|render_device| {
-            Boxed::new(device_context_ptr).map(|device_context| (render_device, device_context))
-        })
+        Boxed::new(render_device_ptr)
+            .and_then(|render_device| {
+                Boxed::new(device_context_ptr).map(|device_context| (render_device, device_context))
+            })
+            .ok_or_else(|| capture.null_object(Operation::CreateDeviceAndContexts, None))
     }
 }
 
 pub fn get_engine_factory_gl() -> Boxed<EngineFactoryOpenGL> {
-    Boxed::new(unsafe { diligent_sys::Diligent_GetEngineFactoryOpenGL() }).unwrap()
+    let engine_factory = Boxed::<EngineFactoryOpenGL>::new(unsafe {
+        diligent_sys::Diligent_GetEngineFactoryOpenGL()
+    })
+    .unwrap();
+    install_message_hook(&engine_factory);
+    debug_verify_abi(&engine_factory);
+    engine_factory
 }
@@ -1,5 +1,6 @@
 use crate::{
-    Boxed, BoxedFromNulError, Buffer, BufferDesc, ResourceState, Texture, TextureDesc,
+    Boxed, Buffer, BufferDesc, Error, ResourceState, Texture, TextureDesc,
+    error::{MessageCapture, Operation},
     render_device::RenderDevice,
 };
 
@@ -30,8 +31,9 @@ impl RenderDeviceGL {
         gl_bind_target: u32,
         tex_desc: &TextureDesc,
         initial_state: ResourceState,
-    ) -> Result<Boxed<Texture>, BoxedFromNulError> {
+    ) -> Result<Boxed<Texture>, Error> {
         let mut texture_ptr = std::ptr::null_mut();
+        let capture = MessageCapture::begin();
         unsafe_member_call!(
             self,
             RenderDeviceGL,
@@ -43,7 +45,7 @@ impl RenderDeviceGL {
             &mut texture_ptr
         );
 
-        Boxed::new(texture_ptr)
+        capture.boxed(texture_ptr, Operation::CreateTexture, tex_desc.name())
     }
 
     pub fn create_buffer_from_gl_handle(
@@ -51,8 +53,9 @@ impl RenderDeviceGL {
         gl_handle: u32,
         buff_desc: &BufferDesc,
         initial_state: ResourceState,
-    ) -> Result<Boxed<Buffer>, BoxedFromNulError> {
+    ) -> Result<Boxed<Buffer>, Error> {
         let mut buffer_ptr = std::ptr::null_mut();
+        let capture = MessageCapture::begin();
         unsafe_member_call!(
             self,
             RenderDeviceGL,
@@ -63,15 +66,16 @@ impl RenderDeviceGL {
             &mut buffer_ptr
         );
 
-        Boxed::new(buffer_ptr)
+        capture.boxed(buffer_ptr, Operation::CreateBuffer, buff_desc.name())
     }
 
     pub fn create_dummy_texture(
         &self,
         tex_desc: &TextureDesc,
         initial_state: ResourceState,
-    ) -> Result<Boxed<Texture>, BoxedFromNulError> {
+    ) -> Result<Boxed<Texture>, Error> {
         let mut texture_ptr = std::ptr::null_mut();
+        let capture = MessageCapture::begin();
         unsafe_member_call!(
             self,
             RenderDeviceGL,
@@ -81,7 +85,7 @@ impl RenderDeviceGL {
             &mut texture_ptr
         );
 
-        Boxed::new(texture_ptr)
+        capture.boxed(texture_ptr, Operation::CreateTexture, tex_desc.name())
     }
 
     #[cfg(target_os = "windows")]
@@ -0,0 +1,596 @@
+use std::{
+    collections::{BTreeSet, VecDeque},
+    ffi::CStr,
+    sync::Mutex,
+};
+
+use crate::{
+    Boxed, Error, Ported,
+    buffer::{Buffer, BufferDesc},
+    device_context::{DeferredDeviceContext, DeviceContext, ImmediateDeviceContext},
+    error::{MessageCapture, Operation},
+    fence::{Fence, FenceDesc, FenceWait},
+    graphics_types::{BindFlags, ComponentType, CpuAccessFlags, MapFlags, RenderDeviceType, Usage},
+    render_device::RenderDevice,
+    texture::Texture,
+};
+
+const STAGING_PAGE_NAME: &CStr = c"Upload staging page";
+
+// The strictest copy alignments of the backends, required by the texture copies of Direct3D12
+const TEXTURE_OFFSET_ALIGNMENT: u64 = 512;
+const TEXTURE_STRIDE_ALIGNMENT: u64 = 256;
+const BUFFER_OFFSET_ALIGNMENT: u64 = 16;
+
+/// The fence value at which an upload scheduled by `GpuUploadManager` is complete.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct UploadTicket(u64);
+
+impl UploadTicket {
+    pub fn fence_value(&self) -> u64 {
+        self.0
+    }
+}
+
+/// Copies recorded on a deferred context by `GpuUploadManager::record_deferred`.
+///
+/// The batch must be submitted once the command list of the deferred context has been executed.
+#[must_use = "the uploads of the batch never complete if it is not submitted"]
+pub struct UploadBatch {
+    first_id: u64,
+    last_id: u64,
+}
+
+enum UploadDestination {
+    Buffer {
+        buffer: Boxed<Buffer>,
+        offset: u64,
+    },
+    Texture {
+        texture: Boxed<Texture>,
+        mip_level: u32,
+        array_slice: u32,
+        region: crate::Box,
+        stride: u64,
+    },
+}
+
+struct UploadRequest {
+    id: u64,
+    destination: UploadDestination,
+    data: Vec<u8>,
+}
+
+impl UploadRequest {
+    fn alignment(&self) -> u64 {
+        match self.destination {
+            UploadDestination::Buffer { .. } => BUFFER_OFFSET_ALIGNMENT,
+            UploadDestination::Texture { .. } => TEXTURE_OFFSET_ALIGNMENT,
+        }
+    }
+
+    /// The stride of the rows in the staging page, and the number of rows.
+    fn staged_rows(&self) -> Option<(u64, u64)> {
+        match self.destination {
+            UploadDestination::Buffer { .. } => None,
+            UploadDestination::Texture { stride, .. } => Some((
+                stride.next_multiple_of(TEXTURE_STRIDE_ALIGNMENT),
+                self.data.len() as u64 / stride,
+            )),
+        }
+    }
+
+    fn staged_size(&self) -> u64 {
+        self.staged_rows()
+            .map_or(self.data.len() as u64, |(stride, rows)| stride * rows)
+    }
+
+    fn write(&self, page: &mut [u8]) {
+        match self.destination {
+            UploadDestination::Buffer { .. } => page[..self.data.len()].copy_from_slice(&self.data),
+            UploadDestination::Texture { stride, .. } => {
+                let (staged_stride, _) = self.staged_rows().unwrap();
+                self.data
+                    .chunks_exact(stride as usize)
+                    .zip(page.chunks_mut(staged_stride as usize))
+                    .for_each(|(row, staged_row)| staged_row[..row.len()].copy_from_slice(row));
+            }
+        }
+    }
+
+    fn record_copy(&self, device_context: &DeviceContext, page: &Buffer, page_offset: u64) {
+        match &self.destination {
+            UploadDestination::Buffer { buffer, offset } => unsafe_member_call!(
+                device_context,
+                DeviceContext,
+                CopyBuffer,
+                page.sys_ptr(),
+                page_offset,
+                diligent_sys::RESOURCE_STATE_TRANSITION_MODE_TRANSITION as _,
+                buffer.sys_ptr(),
+                *offset,
+                self.data.len() as u64,
+                diligent_sys::RESOURCE_STATE_TRANSITION_MODE_TRANSITION as _
+            ),
+            UploadDestination::Texture {
+                texture,
+                mip_level,
+                array_slice,
+                region,
+                ..
+            } => {
+                let (staged_stride, rows) = self.staged_rows().unwrap();
+                let depth = u64::from(region.0.MaxZ - region.0.MinZ).max(1);
+
+                let subresource = diligent_sys::TextureSubResData {
+                    pData: std::ptr::null(),
+                    pSrcBuffer: page.sys_ptr(),
+                    SrcOffset: page_offset,
+                    Stride: staged_stride,
+                    DepthStride: staged_stride * (rows / depth),
+                };
+
+                unsafe_member_call!(
+                    device_context,
+                    DeviceContext,
+                    UpdateTexture,
+                    texture.sys_ptr(),
+                    *mip_level,
+                    *array_slice,
+                    &region.0,
+                    &subresource,
+                    diligent_sys::RESOURCE_STATE_TRANSITION_MODE_TRANSITION as _,
+                    diligent_sys::RESOURCE_STATE_TRANSITION_MODE_TRANSITION as _
+                )
+            }
+        }
+    }
+}
+
+struct StagingPage {
+    buffer: Boxed<Buffer>,
+    /// The memory of the buffer, mapped when the page is created and never unmapped.
+    data: *mut u8,
+    /// The fence value of the last batch that used the page.
+    fence_value: u64,
+}
+
+impl StagingPage {
+    fn data(&mut self) -> &mut [u8] {
+        unsafe { std::slice::from_raw_parts_mut(self.data, self.buffer.desc().size() as usize) }
+    }
+}
+
+// # Safety
+// The mapped memory is only written by the thread that holds the lock of the pages.
+unsafe impl Send for StagingPage {}
+
+#[derive(Default)]
+struct UploadQueue {
+    last_id: u64,
+    requests: VecDeque<UploadRequest>,
+}
+
+/// The batches that have been recorded, and the fence values signaled for them.
+#[derive(Default)]
+struct Submissions {
+    /// The first request of the recorded batches that have not been submitted yet.
+    unsubmitted_batches: BTreeSet<u64>,
+    last_recorded_id: u64,
+    last_signaled_value: u64,
+}
+
+/// Uploads buffer and texture data through a pool of staging pages.
+///
+/// The uploads can be scheduled from any thread. The render thread writes them to the staging pages
+/// and records the copies with `record` (or `record_deferred`), and the fence of the manager
+/// reaches the value of their ticket when the copies are complete.
+///
+/// The uploads are recorded in the order they were scheduled. A batch stops when all the pages
+/// are in use by the GPU : the remaining uploads wait for the next batch.
+///
+/// The fence value is chosen when a batch is submitted : it is the ticket of the last upload
+/// whose batch and all the batches before it have been submitted, so the fence never reaches
+/// the ticket of an upload that has not been submitted yet.
+///
+/// The staging pages are mapped once, when they are created, and stay mapped until they are
+/// released. This requires a backend whose staging buffers can be mapped persistently :
+/// the manager can only be created with Direct3D12 or Vulkan.
+pub struct GpuUploadManager {
+    render_device: Boxed<RenderDevice>,
+    fence: Boxed<Fence>,
+    page_size: u64,
+    max_page_count: usize,
+    queue: Mutex<UploadQueue>,
+    pages: Mutex<Vec<StagingPage>>,
+    submissions: Mutex<Submissions>,
+}
+
+#[bon::bon]
+impl GpuUploadManager {
+    #[builder]
+    pub fn new(
+        render_device: &RenderDevice,
+
+        #[builder(default = 4 << 20)] page_size: u64,
+
+        /// The number of pages above which the uploads wait for the GPU to release a page.
+        #[builder(default = 16)]
+        max_page_count: usize,
+    ) -> Result<Self, Error> {
+        let device_type = render_device.get_device_info().device_type();
+        let persistent_mapping = match device_type {
+            #[cfg(feature = "d3d12")]
+            RenderDeviceType::D3D12 => true,
+            #[cfg(feature = "vulkan")]
+            RenderDeviceType::VULKAN => true,
+            #[allow(unreachable_patterns)]
+            _ => false,
+        };
+
+        if !persistent_mapping {
+            return Err(Error::invalid_argument(
+                Operation::CreateUploadManager,
+                None,
+                format!("the staging buffers of the {device_type:?} backend can not stay mapped"),
+            ));
+        }
+
+        let fence = render_device
+            .create_fence(&FenceDesc::builder().name(c"Upload manager fence").build())?;
+
+        Ok(GpuUploadManager {
+            render_device: Boxed::from_ref(render_device),
+            fence,
+            page_size,
+            max_page_count,
+            queue: Mutex::new(UploadQueue::default()),
+            pages: Mutex::new(Vec::new()),
+            submissions: Mutex::new(Submissions::default()),
+        })
+    }
+}
+
+impl GpuUploadManager {
+    pub fn fence(&self) -> &Fence {
+        &self.fence
+    }
+
+    fn schedule(&self, destination: UploadDestination, data: Vec<u8>) -> UploadTicket {
+        let mut queue = self.queue.lock().unwrap();
+        queue.last_id += 1;
+
+        let id = queue.last_id;
+        queue.requests.push_back(UploadRequest {
+            id,
+            destination,
+            data,
+        });
+
+        UploadTicket(id)
+    }
+
+    /// Schedules the upload of `data` to `buffer`, at `offset`.
+    ///
+    /// Fails if the data does not fit in the buffer.
+    pub fn schedule_buffer_update(
+        &self,
+        buffer: &Buffer,
+        offset: u64,
+        data: Vec<u8>,
+    ) -> Result<UploadTicket, Error> {
+        let buffer_size = buffer.desc().size();
+        if offset
+            .checked_add(data.len() as u64)
+            .is_none_or(|end| end > buffer_size)
+        {
+            return Err(Error::invalid_argument(
+                Operation::ScheduleBufferUpdate,
+                buffer.desc().name(),
+                format!(
+                    "{} bytes at offset {offset} do not fit in the {buffer_size} bytes of the buffer",
+                    data.len()
+                ),
+            ));
+        }
+
+        Ok(self.schedule(
+            UploadDestination::Buffer {
+                buffer: Boxed::from_ref(buffer),
+                offset,
+            },
+            data,
+        ))
+    }
+
+    /// Schedules the upload of `data` to `region` of a subresource of `texture`.
+    ///
+    /// `data` is made of rows of `stride` bytes, from the first depth slice of the region to the last.
+    /// A row holds a line of blocks for the compressed formats.
+    ///
+    /// Fails if `stride` is smaller than a row of the region, or if `data` is not made of
+    /// exactly one row of `stride` bytes for each row of the region.
+    pub fn schedule_texture_update(
+        &self,
+        texture: &Texture,
+        mip_level: u32,
+        array_slice: u32,
+        region: &crate::Box,
+        data: Vec<u8>,
+        stride: u64,
+    ) -> Result<UploadTicket, Error> {
+        let invalid_argument = |reason: String| {
+            Error::invalid_argument(
+                Operation::ScheduleTextureUpdate,
+                texture.desc().name(),
+                reason,
+            )
+        };
+
+        let width = u64::from(region.0.MaxX.saturating_sub(region.0.MinX));
+        let height = u64::from(region.0.MaxY.saturating_sub(region.0.MinY));
+        let depth = u64::from(region.0.MaxZ.saturating_sub(region.0.MinZ)).max(1);
+
+        let (row_size, row_count) = match texture.desc().format() {
+            Some(format) if matches!(format.component_type(), Some(ComponentType::Compressed)) => (
+                width.div_ceil(u64::from(format.block_width()))
+                    * u64::from(format.component_size()),
+                height.div_ceil(u64::from(format.block_height())),
+            ),
+            Some(format) => (
+                width * u64::from(format.component_size()) * u64::from(format.num_components()),
+                height,
+            ),
+            None => (0, height),
+        };
+
+        if stride == 0 || stride < row_size {
+            return Err(invalid_argument(format!(
+                "the stride {stride} is smaller than the {row_size} bytes of a row"
+            )));
+        }
+
+        if data.len() as u64 != stride * row_count * depth {
+            return Err(invalid_argument(format!(
+                "{} bytes are not {} rows of {stride} bytes",
+                data.len(),
+                row_count * depth
+            )));
+        }
+
+        Ok(self.schedule(
+            UploadDestination::Texture {
+                texture: Boxed::from_ref(texture),
+                mip_level,
+                array_slice,
+                region: region.clone(),
+                stride,
+            },
+            data,
+        ))
+    }
+
+    pub fn is_complete(&self, ticket: UploadTicket) -> bool {
+        self.fence.get_completed_value() >= ticket.0
+    }
+
+    pub fn wait(&self, ticket: UploadTicket) {
+        self.fence.wait(ticket.0)
+    }
+
+    pub fn wait_async(&self, ticket: UploadTicket) -> FenceWait {
+        self.fence.wait_async(ticket.0)
+    }
+
+    /// Writes the scheduled uploads to the staging pages, records their copies on `immediate_context`
+    /// and enqueues the signal of the fence.
+    ///
+    /// Returns the ticket of the last upload of the batch, or `None` if no upload was recorded.
+    /// Fails if no upload could be recorded because a staging page could not be created.
+    pub fn record(
+        &self,
+        immediate_context: &ImmediateDeviceContext,
+    ) -> Result<Option<UploadTicket>, Error> {
+        let batch = self.record_batch(immediate_context, immediate_context)?;
+        Ok(batch.map(|batch| {
+            let ticket = UploadTicket(batch.last_id);
+            self.submit(immediate_context, batch);
+            ticket
+        }))
+    }
+
+    /// Writes the scheduled uploads to the staging pages, and records their copies on `deferred_context`.
+    ///
+    /// The staging pages are created with `immediate_context`.
+    pub fn record_deferred(
+        &self,
+        immediate_context: &ImmediateDeviceContext,
+        deferred_context: &DeferredDeviceContext,
+    ) -> Result<Option<UploadBatch>, Error> {
+        self.record_batch(immediate_context, deferred_context)
+    }
+
+    /// Enqueues the signal of the fence for a batch whose command list has been executed.
+    ///
+    /// The fence is not signaled while a batch recorded before this one has not been submitted.
+    pub fn submit(&self, immediate_context: &ImmediateDeviceContext, batch: UploadBatch) {
+        let mut submissions = self.submissions.lock().unwrap();
+        submissions.unsubmitted_batches.remove(&batch.first_id);
+
+        // The uploads before the first unsubmitted batch have all been submitted
+        let fence_value = submissions
+            .unsubmitted_batches
+            .first()
+            .map_or(submissions.last_recorded_id, |first_id| first_id - 1);
+
+        if fence_value > submissions.last_signaled_value {
+            submissions.last_signaled_value = fence_value;
+            immediate_context.enqueue_signal(&self.fence, fence_value);
+        }
+    }
+
+    /// Releases the staging pages that are not used by the GPU.
+    pub fn release_free_pages(&self) {
+        let completed_value = self.fence.get_completed_value();
+        self.pages
+            .lock()
+            .unwrap()
+            .retain(|page| page.fence_value > completed_value);
+    }
+
+    fn record_batch(
+        &self,
+        mapping_context: &DeviceContext,
+        copy_context: &DeviceContext,
+    ) -> Result<Option<UploadBatch>, Error> {
+        let mut queue = self.queue.lock().unwrap();
+        let mut pages = self.pages.lock().unwrap();
+
+        let completed_value = self.fence.get_completed_value();
+        let mut used_pages = Vec::new();
+        let mut first_id = None;
+        let mut last_id = None;
+
+        let mut page_error = None;
+
+        while let Some(first_request) = queue.requests.front() {
+            let page_index = match self.acquire_page(
+                mapping_context,
+                &mut pages,
+                &used_pages,
+                completed_value,
+                first_request.staged_size(),
+            ) {
+                Ok(Some(page_index)) => page_index,
+                Ok(None) => break,
+                Err(error) => {
+                    page_error = Some(error);
+                    break;
+                }
+            };
+            used_pages.push(page_index);
+
+            let page = &mut pages[page_index];
+            let page_size = page.buffer.desc().size();
+
+            // The requests that fit in the page, with their offset
+            let mut placed = Vec::new();
+            let mut offset = 0;
+            while let Some(request) = queue.requests.front() {
+                let request_offset = offset.next_multiple_of(request.alignment());
+                if request_offset + request.staged_size() > page_size {
+                    break;
+                }
+                offset = request_offset + request.staged_size();
+                placed.push((queue.requests.pop_front().unwrap(), request_offset));
+            }
+
+            for (request, offset) in &placed {
+                request.write(&mut page.data()[*offset as usize..]);
+            }
+
+            for (request, offset) in &placed {
+                request.record_copy(copy_context, &page.buffer, *offset);
+            }
+
+            first_id = first_id.or(placed.first().map(|(request, _)| request.id));
+            last_id = placed.last().map(|(request, _)| request.id);
+        }
+
+        // The requests recorded before a page could not be created still make a batch :
+        // the failed request is tried again by the next one
+        let (Some(first_id), Some(last_id)) = (first_id, last_id) else {
+            return page_error.map_or(Ok(None), Err);
+        };
+
+        // The fence reaches the last upload of the batch once the batch and the ones before are complete
+        for page_index in used_pages {
+            pages[page_index].fence_value = last_id;
+        }
+
+        let mut submissions = self.submissions.lock().unwrap();
+        submissions.unsubmitted_batches.insert(first_id);
+        submissions.last_recorded_id = last_id;
+
+        Ok(Some(UploadBatch { first_id, last_id }))
+    }
+
+    /// Returns a page that is not used by the GPU and can hold `size` bytes, creating it if needed.
+    fn acquire_page(
+        &self,
+        mapping_context: &DeviceContext,
+        pages: &mut Vec<StagingPage>,
+        used_pages: &[usize],
+        completed_value: u64,
+        size: u64,
+    ) -> Result<Option<usize>, Error> {
+        let is_free = |index: usize, page: &StagingPage| {
+            page.fence_value <= completed_value && !used_pages.contains(&index)
+        };
+
+        if let Some((index, _)) = pages
+            .iter()
+            .enumerate()
+            .find(|(index, page)| is_free(*index, page) && page.buffer.desc().size() >= size)
+        {
+            return Ok(Some(index));
+        }
+
+        // When all the pages are allocated, a free page that is too small is replaced by a larger one
+        let replaced_page = if pages.len() < self.max_page_count {
+            None
+        } else {
+            match pages
+                .iter()
+                .enumerate()
+                .find(|(index, page)| is_free(*index, page))
+            {
+                Some((index, _)) => Some(index),
+                None => return Ok(None),
+            }
+        };
+
+        // The uploads larger than a page get a page of their own
+        let buffer = self.render_device.create_buffer(
+            &BufferDesc::builder()
+                .name(STAGING_PAGE_NAME)
+                .size(size.max(self.page_size))
+                .bind_flags(BindFlags::None)
+                .usage(Usage::Staging)
+                .cpu_access_flags(CpuAccessFlags::Write)
+                .build(),
+        )?;
+
+        let mut data = std::ptr::null_mut();
+        let capture = MessageCapture::begin();
+        unsafe_member_call!(
+            mapping_context,
+            DeviceContext,
+            MapBuffer,
+            buffer.sys_ptr(),
+            diligent_sys::MAP_WRITE as diligent_sys::MAP_TYPE,
+            MapFlags::None.bits(),
+            &mut data
+        );
+        if data.is_null() {
+            return Err(capture.map_failed(Operation::MapBuffer, Some(STAGING_PAGE_NAME)));
+        }
+
+        let page = StagingPage {
+            buffer,
+            data: data as *mut u8,
+            fence_value: 0,
+        };
+
+        match replaced_page {
+            Some(index) => {
+                pages[index] = page;
+                Ok(Some(index))
+            }
+            None => {
+                pages.push(page);
+                Ok(Some(pages.len() - 1))
+            }
+        }
+    }
+}
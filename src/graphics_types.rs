@@ -9,6 +9,7 @@ use crate::SparseTextureFlags;
 
 bitflags! {
     #[derive(Clone, Copy)]
+    #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
     pub struct ShaderTypes: diligent_sys::SHADER_TYPE {
         const Vertex          = diligent_sys::SHADER_TYPE_VERTEX as diligent_sys::SHADER_TYPE;
         const Pixel           = diligent_sys::SHADER_TYPE_PIXEL as diligent_sys::SHADER_TYPE;
@@ -35,7 +36,8 @@ bitflags! {
 }
 const_assert_eq!(diligent_sys::SHADER_TYPE_LAST, 16384);
 
-#[derive(Clone, Copy)]
+#[derive(Clone, Copy, Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum ShaderType {
     Vertex,
     Pixel,
@@ -101,6 +103,7 @@ impl ShaderType {
 }
 
 #[derive(Clone, Copy)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum FilterType {
     Point,
     Linear,
@@ -169,6 +172,7 @@ impl FilterType {
 }
 
 #[derive(Clone, Copy)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum TextureAddressMode {
     Wrap,
     Mirror,
@@ -210,6 +214,7 @@ impl TextureAddressMode {
 }
 
 #[derive(Clone, Copy)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum PrimitiveTopology {
     TriangleList,
     TriangleStrip,
@@ -494,6 +499,7 @@ impl TryFrom<diligent_sys::PRIMITIVE_TOPOLOGY> for PrimitiveTopology {
 
 bitflags! {
     #[derive(Clone, Copy)]
+    #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
     pub struct BindFlags: diligent_sys::BIND_FLAGS {
         const None             = diligent_sys::BIND_NONE as diligent_sys::BIND_FLAGS;
         const VertexBuffer     = diligent_sys::BIND_VERTEX_BUFFER as diligent_sys::BIND_FLAGS;
@@ -519,6 +525,7 @@ impl Default for BindFlags {
 }
 
 #[derive(Clone, Copy, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum Usage {
     #[default]
     Default,
@@ -563,6 +570,7 @@ impl TryFrom<diligent_sys::USAGE> for Usage {
 
 bitflags! {
     #[derive(Clone, Copy)]
+    #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
     pub struct CpuAccessFlags: diligent_sys::CPU_ACCESS_FLAGS {
         const None  = diligent_sys::CPU_ACCESS_NONE as diligent_sys::CPU_ACCESS_FLAGS;
         const Read  = diligent_sys::CPU_ACCESS_READ as diligent_sys::CPU_ACCESS_FLAGS;
@@ -579,6 +587,7 @@ impl Default for CpuAccessFlags {
 
 bitflags! {
     #[derive(Clone, Copy)]
+    #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
     pub struct SetShaderResourceFlags: diligent_sys::SET_SHADER_RESOURCE_FLAGS {
         const None          = diligent_sys::SET_SHADER_RESOURCE_FLAG_NONE as diligent_sys::SET_SHADER_RESOURCE_FLAGS;
         const AllowOverrite = diligent_sys::SET_SHADER_RESOURCE_FLAG_ALLOW_OVERWRITE as diligent_sys::SET_SHADER_RESOURCE_FLAGS;
@@ -592,6 +601,7 @@ impl Default for SetShaderResourceFlags {
 }
 
 #[derive(Debug, Clone, Copy)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum RenderDeviceType {
     #[cfg(feature = "d3d11")]
     D3D11,
@@ -608,6 +618,25 @@ pub enum RenderDeviceType {
 }
 const_assert_eq!(diligent_sys::RENDER_DEVICE_TYPE_COUNT, 8);
 
+impl From<RenderDeviceType> for diligent_sys::RENDER_DEVICE_TYPE {
+    fn from(value: RenderDeviceType) -> Self {
+        (match value {
+            #[cfg(feature = "d3d11")]
+            RenderDeviceType::D3D11 => diligent_sys::RENDER_DEVICE_TYPE_D3D11,
+            #[cfg(feature = "d3d12")]
+            RenderDeviceType::D3D12 => diligent_sys::RENDER_DEVICE_TYPE_D3D12,
+            #[cfg(feature = "opengl")]
+            RenderDeviceType::GL => diligent_sys::RENDER_DEVICE_TYPE_GL,
+            #[cfg(feature = "vulkan")]
+            RenderDeviceType::VULKAN => diligent_sys::RENDER_DEVICE_TYPE_VULKAN,
+            #[cfg(feature = "metal")]
+            RenderDeviceType::METAL => diligent_sys::RENDER_DEVICE_TYPE_METAL,
+            #[cfg(feature = "webgpu")]
+            RenderDeviceType::WEBGPU => diligent_sys::RENDER_DEVICE_TYPE_WEBGPU,
+        }) as _
+    }
+}
+
 impl Display for RenderDeviceType {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         let string = match self {
@@ -657,6 +686,7 @@ pub const fn get_prefered_device_type() -> RenderDeviceType {
 }
 
 #[derive(Clone, Copy)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum ValueType {
     Int8,
     Int16,
@@ -686,8 +716,30 @@ impl From<ValueType> for diligent_sys::VALUE_TYPE {
     }
 }
 
+impl TryFrom<diligent_sys::VALUE_TYPE> for ValueType {
+    type Error = std::io::Error;
+    fn try_from(value: diligent_sys::VALUE_TYPE) -> Result<Self, Self::Error> {
+        match value as _ {
+            diligent_sys::VT_INT8 => Ok(ValueType::Int8),
+            diligent_sys::VT_INT16 => Ok(ValueType::Int16),
+            diligent_sys::VT_INT32 => Ok(ValueType::Int32),
+            diligent_sys::VT_UINT8 => Ok(ValueType::Uint8),
+            diligent_sys::VT_UINT16 => Ok(ValueType::Uint16),
+            diligent_sys::VT_UINT32 => Ok(ValueType::Uint32),
+            diligent_sys::VT_FLOAT16 => Ok(ValueType::Float16),
+            diligent_sys::VT_FLOAT32 => Ok(ValueType::Float32),
+            diligent_sys::VT_FLOAT64 => Ok(ValueType::Float64),
+            _ => Err(Self::Error::new(
+                std::io::ErrorKind::InvalidData,
+                "Unknown VALUE_TYPE value",
+            )),
+        }
+    }
+}
+
 bitflags! {
     #[derive(Clone, Copy)]
+    #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
     pub struct MapFlags: diligent_sys::MAP_FLAGS {
         const None        = diligent_sys::MAP_FLAG_NONE as diligent_sys::MAP_FLAGS;
         const DoNotWait   = diligent_sys::MAP_FLAG_DO_NOT_WAIT as diligent_sys::MAP_FLAGS;
@@ -697,6 +749,7 @@ bitflags! {
 }
 
 #[derive(Clone, Copy)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct Version {
     pub major: u32,
     pub minor: u32,
@@ -709,6 +762,7 @@ impl Version {
 }
 
 #[derive(Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum AdapterType {
     Unknown,
     Software,
@@ -768,6 +822,7 @@ impl From<AdapterType> for diligent_sys::ADAPTER_TYPE {
 const_assert_eq!(diligent_sys::ADAPTER_TYPE_COUNT, 4);
 
 #[derive(Clone, Copy)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum AdapterVendor {
     Unknown,
     Nvidia,
@@ -809,6 +864,7 @@ impl AdapterMemoryInfo {
 
 bitflags! {
     #[derive(Clone, Copy)]
+    #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
     pub struct RaytracingCapFlags : diligent_sys::RAY_TRACING_CAP_FLAGS {
         const None               = diligent_sys::RAY_TRACING_CAP_FLAG_NONE as diligent_sys::RAY_TRACING_CAP_FLAGS;
         const StandaloneShaders  = diligent_sys::RAY_TRACING_CAP_FLAG_STANDALONE_SHADERS as diligent_sys::RAY_TRACING_CAP_FLAGS;
@@ -869,6 +925,7 @@ impl RayTracingProperties {
 
 bitflags! {
     #[derive(Clone, Copy)]
+    #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
     pub struct WaveFeature : diligent_sys::WAVE_FEATURE {
         const Unknown         = diligent_sys::WAVE_FEATURE_UNKNOWN as diligent_sys::WAVE_FEATURE;
         const Basic           = diligent_sys::WAVE_FEATURE_BASIC as diligent_sys::WAVE_FEATURE;
@@ -981,6 +1038,7 @@ impl MeshShaderProperties {
 }
 
 #[derive(Clone, Copy)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum ShadingRate {
     _1X1,
     _1X2,
@@ -1012,6 +1070,7 @@ impl From<ShadingRate> for diligent_sys::SHADING_RATE {
 
 bitflags! {
     #[derive(Clone, Copy)]
+    #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
     pub struct SampleCount : diligent_sys::SAMPLE_COUNT {
         const None = diligent_sys::SAMPLE_COUNT_NONE as diligent_sys::SAMPLE_COUNT;
         const _1   = diligent_sys::SAMPLE_COUNT_1 as diligent_sys::SAMPLE_COUNT;
@@ -1049,6 +1108,7 @@ impl ShadingRateMode {
 
 bitflags! {
     #[derive(Clone, Copy)]
+    #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
     pub struct ShadingRateCapFlags : diligent_sys::SHADING_RATE_CAP_FLAGS {
         const None                              = diligent_sys::SHADING_RATE_CAP_FLAG_NONE as diligent_sys::SHADING_RATE_CAP_FLAGS;
         const PerDraw                           = diligent_sys::SHADING_RATE_CAP_FLAG_PER_DRAW as diligent_sys::SHADING_RATE_CAP_FLAGS;
@@ -1069,6 +1129,7 @@ bitflags! {
 
 bitflags! {
     #[derive(Clone, Copy)]
+    #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
     pub struct ShadingRateCombiner : diligent_sys::SHADING_RATE_COMBINER {
         const Passthrough = diligent_sys::SHADING_RATE_COMBINER_PASSTHROUGH as diligent_sys::SHADING_RATE_COMBINER;
         const Override    = diligent_sys::SHADING_RATE_COMBINER_OVERRIDE as diligent_sys::SHADING_RATE_COMBINER;
@@ -1082,6 +1143,7 @@ const_assert_eq!(diligent_sys::SHADING_RATE_COMBINER_LAST, 32);
 
 bitflags! {
     #[derive(Clone, Copy)]
+    #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
     pub struct ShadingRateFormat : diligent_sys::SHADING_RATE_FORMAT {
         const Unknown    = diligent_sys::SHADING_RATE_FORMAT_UNKNOWN as diligent_sys::SHADING_RATE_FORMAT;
         const Palette    = diligent_sys::SHADING_RATE_FORMAT_PALETTE as diligent_sys::SHADING_RATE_FORMAT;
@@ -1092,6 +1154,7 @@ bitflags! {
 
 bitflags! {
     #[derive(Clone, Copy)]
+    #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
     pub struct ShadingRateTextureAccess : diligent_sys::SHADING_RATE_TEXTURE_ACCESS {
         const Unknown  = diligent_sys::SHADING_RATE_TEXTURE_ACCESS_UNKNOWN as diligent_sys::SHADING_RATE_TEXTURE_ACCESS;
         const OnGpu    = diligent_sys::SHADING_RATE_TEXTURE_ACCESS_ON_GPU as diligent_sys::SHADING_RATE_TEXTURE_ACCESS;
@@ -1168,6 +1231,7 @@ impl ComputeShaderProperties {
 
 bitflags! {
     #[derive(Clone, Copy)]
+    #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
     pub struct DrawCommandCapFlags : diligent_sys::DRAW_COMMAND_CAP_FLAGS {
         const None                      = diligent_sys::DRAW_COMMAND_CAP_FLAG_NONE as diligent_sys::DRAW_COMMAND_CAP_FLAGS;
         const BaseVertex                = diligent_sys::DRAW_COMMAND_CAP_FLAG_BASE_VERTEX as diligent_sys::DRAW_COMMAND_CAP_FLAGS;
@@ -1194,6 +1258,7 @@ impl DrawCommandProperties {
 
 bitflags! {
     #[derive(Clone, Copy)]
+    #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
     pub struct SparseResourceCapFlags : diligent_sys::SPARSE_RESOURCE_CAP_FLAGS {
         const None                     = diligent_sys::SPARSE_RESOURCE_CAP_FLAG_NONE as diligent_sys::SPARSE_RESOURCE_CAP_FLAGS;
         const ShaderResourceResidency  = diligent_sys::SPARSE_RESOURCE_CAP_FLAG_SHADER_RESOURCE_RESIDENCY as diligent_sys::SPARSE_RESOURCE_CAP_FLAGS;
@@ -1238,6 +1303,7 @@ impl SparseResourceProperties {
 }
 
 #[derive(Clone, Copy)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum DeviceFeatureState {
     Disabled,
     Enabled,
@@ -1631,6 +1697,7 @@ impl Default for DeviceFeatures {
 
 bitflags! {
     #[derive(Clone,Copy)]
+    #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
     pub struct CommandQueueType : diligent_sys::COMMAND_QUEUE_TYPE {
         const Unknown       = diligent_sys::COMMAND_QUEUE_TYPE_UNKNOWN as diligent_sys::COMMAND_QUEUE_TYPE;
         const Transfer      = diligent_sys::COMMAND_QUEUE_TYPE_TRANSFER as diligent_sys::COMMAND_QUEUE_TYPE;
@@ -1744,6 +1811,7 @@ impl GraphicsAdapterInfo {
 }
 
 #[derive(Clone, Copy)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum SurfaceTransform {
     Optimal,
     Identity,
@@ -1809,6 +1877,7 @@ impl TryFrom<diligent_sys::SURFACE_TRANSFORM> for SurfaceTransform {
 
 bitflags! {
     #[derive(Clone,Copy)]
+    #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
     pub struct ResourceState: diligent_sys::RESOURCE_STATE {
         const Undefined         = diligent_sys::RESOURCE_STATE_UNDEFINED as diligent_sys::RESOURCE_STATE;
         const VertexBuffer      = diligent_sys::RESOURCE_STATE_VERTEX_BUFFER as diligent_sys::RESOURCE_STATE;
@@ -1857,6 +1926,7 @@ impl ResourceState {
 }
 
 #[derive(Clone, Copy)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum QueuePriority {
     Low,
     Medium,
@@ -1866,6 +1936,7 @@ pub enum QueuePriority {
 const_assert_eq!(diligent_sys::QUEUE_PRIORITY_LAST, 4);
 
 #[derive(Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum ComponentType {
     Float,
     Snorm,
@@ -1924,6 +1995,7 @@ impl ComponentType {
 }
 
 #[derive(Clone, Copy)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 #[allow(non_camel_case_types)]
 pub enum TextureFormat {
     RGBA32_TYPELESS,
@@ -2995,6 +3067,7 @@ impl TextureFormat {
 
 bitflags! {
     #[derive(Clone, Copy)]
+    #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
     pub struct ResourceDimensionSupport : diligent_sys::RESOURCE_DIMENSION_SUPPORT {
         const None          = diligent_sys::RESOURCE_DIMENSION_SUPPORT_NONE           as diligent_sys::RESOURCE_DIMENSION_SUPPORT;
         const Buffer        = diligent_sys::RESOURCE_DIMENSION_SUPPORT_BUFFER         as diligent_sys::RESOURCE_DIMENSION_SUPPORT;
@@ -3044,6 +3117,7 @@ impl SparseTextureFormatInfo {
 }
 
 #[derive(Clone, Copy)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum ScalingMode {
     Unspecified,
     Centered,
@@ -3076,6 +3150,7 @@ impl From<ScalingMode> for diligent_sys::SCALING_MODE {
 }
 
 #[derive(Clone, Copy)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum ScanlineOrder {
     Unspecified,
     Progressive,
@@ -3191,6 +3266,7 @@ impl Default for FullScreenModeDesc {
 }
 
 #[derive(Clone, Copy)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum StateTransitionType {
     Immediate,
     Begin,
@@ -3224,6 +3300,7 @@ impl TryFrom<diligent_sys::STATE_TRANSITION_TYPE> for StateTransitionType {
 
 bitflags! {
     #[derive(Clone,Copy)]
+    #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
     pub struct AccessFlags : diligent_sys::ACCESS_FLAGS {
         const None                       = diligent_sys::ACCESS_FLAG_NONE as diligent_sys::ACCESS_FLAGS;
         const IndirectCommandRead        = diligent_sys::ACCESS_FLAG_INDIRECT_COMMAND_READ as diligent_sys::ACCESS_FLAGS;
@@ -3254,6 +3331,7 @@ bitflags! {
 
 bitflags! {
     #[derive(Clone,Copy)]
+    #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
     pub struct PipelineStageFlags : diligent_sys::PIPELINE_STAGE_FLAGS {
         const Undefined                  = diligent_sys::PIPELINE_STAGE_FLAG_UNDEFINED                    as diligent_sys::PIPELINE_STAGE_FLAGS;
         const TopOfPipe                  = diligent_sys::PIPELINE_STAGE_FLAG_TOP_OF_PIPE                  as diligent_sys::PIPELINE_STAGE_FLAGS;
@@ -3283,6 +3361,7 @@ bitflags! {
 }
 
 #[derive(Clone, Copy)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum MemoryProperty {
     HostCoherent,
 }
@@ -3311,6 +3390,7 @@ impl ImmediateContextCreateInfo {
 
 bitflags! {
     #[derive(Clone,Copy)]
+    #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
     pub struct ValidationFlags : diligent_sys::VALIDATION_FLAGS {
         const None                  = diligent_sys::VALIDATION_FLAG_NONE                     as diligent_sys::VALIDATION_FLAGS;
         const CheckShaderBufferSize = diligent_sys::VALIDATION_FLAG_CHECK_SHADER_BUFFER_SIZE as diligent_sys::VALIDATION_FLAGS;
@@ -3362,6 +3442,7 @@ impl Box {
 }
 
 #[derive(Clone, Copy)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum PipelineType {
     Graphics,
     Compute,
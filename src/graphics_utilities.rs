@@ -3,7 +3,7 @@ use std::ffi::{CStr, CString};
 use bon::Builder;
 
 use crate::{
-    Boxed, BoxedFromNulError,
+    Boxed, Error,
     buffer::{Buffer, BufferDesc, BufferMode},
     geometry_primitives::{
         GeometryPrimitive, GeometryPrimitiveAttributes, GeometryPrimitiveInfo,
@@ -57,7 +57,7 @@ pub fn create_geometry_primitive_buffers(
     device: &RenderDevice,
     attribs: &GeometryPrimitiveAttributes,
     buffer_ci: &GeometryPrimitiveBuffersCreateInfo,
-) -> Result<(Boxed<Buffer>, Boxed<Buffer>, GeometryPrimitiveInfo), BoxedFromNulError> {
+) -> Result<(Boxed<Buffer>, Boxed<Buffer>, GeometryPrimitiveInfo), Error> {
     let (vertices, indices, info) = create_geometry_primitive(attribs)?;
 
     let primitive_id = PRIMITIVE_COUNTER.fetch_add(1, std::sync::atomic::Ordering::Relaxed);
@@ -127,7 +127,7 @@ pub fn create_uniform_buffer(
     usage: Usage,
     bind_flags: BindFlags,
     cpu_access_flags: CpuAccessFlags,
-) -> Result<Boxed<Buffer>, BoxedFromNulError> {
+) -> Result<Boxed<Buffer>, Error> {
     let cpu_access_flags = match usage {
         Usage::Default | Usage::Immutable => CpuAccessFlags::None,
         _ => cpu_access_flags,
@@ -152,7 +152,7 @@ pub fn create_uniform_buffer_with_data<T>(
     bind_flags: BindFlags,
     cpu_access_flags: CpuAccessFlags,
     data: &T,
-) -> Result<Boxed<Buffer>, BoxedFromNulError> {
+) -> Result<Boxed<Buffer>, Error> {
     let cpu_access_flags = match usage {
         Usage::Default | Usage::Immutable => CpuAccessFlags::None,
         _ => cpu_access_flags,
@@ -5,6 +5,7 @@ use static_assertions::const_assert_eq;
 use crate::graphics_types::ValueType;
 
 #[derive(Clone, Copy)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum InputElementFrequency {
     PerVertex,
     PerInstance,
@@ -22,6 +23,24 @@ impl From<InputElementFrequency> for diligent_sys::INPUT_ELEMENT_FREQUENCY {
     }
 }
 
+impl TryFrom<diligent_sys::INPUT_ELEMENT_FREQUENCY> for InputElementFrequency {
+    type Error = std::io::Error;
+    fn try_from(value: diligent_sys::INPUT_ELEMENT_FREQUENCY) -> Result<Self, Self::Error> {
+        match value as _ {
+            diligent_sys::INPUT_ELEMENT_FREQUENCY_PER_VERTEX => {
+                Ok(InputElementFrequency::PerVertex)
+            }
+            diligent_sys::INPUT_ELEMENT_FREQUENCY_PER_INSTANCE => {
+                Ok(InputElementFrequency::PerInstance)
+            }
+            _ => Err(Self::Error::new(
+                std::io::ErrorKind::InvalidData,
+                "Unknown INPUT_ELEMENT_FREQUENCY value",
+            )),
+        }
+    }
+}
+
 #[repr(transparent)]
 pub struct LayoutElement(pub(crate) diligent_sys::LayoutElement);
 
@@ -63,6 +82,39 @@ impl LayoutElement {
     }
 }
 
+impl LayoutElement {
+    pub fn input_index(&self) -> u32 {
+        self.0.InputIndex
+    }
+    pub fn slot(&self) -> u32 {
+        self.0.BufferSlot
+    }
+    pub fn num_components(&self) -> u32 {
+        self.0.NumComponents
+    }
+    pub fn value_type(&self) -> ValueType {
+        self.0.ValueType.try_into().unwrap()
+    }
+    pub fn is_normalized(&self) -> bool {
+        self.0.IsNormalized
+    }
+    pub fn hlsl_semantic(&self) -> &CStr {
+        unsafe { CStr::from_ptr(self.0.HLSLSemantic) }
+    }
+    pub fn relative_offset(&self) -> u32 {
+        self.0.RelativeOffset
+    }
+    pub fn stride(&self) -> u32 {
+        self.0.Stride
+    }
+    pub fn frequency(&self) -> InputElementFrequency {
+        self.0.Frequency.try_into().unwrap()
+    }
+    pub fn instance_data_step_rate(&self) -> u32 {
+        self.0.InstanceDataStepRate
+    }
+}
+
 use layout_element_builder::{IsUnset, SetIsNormalized, SetNumComponents, SetValueType, State};
 macro_rules! impl_layout_element_builder {
     ($func_name:ident, $value_type:expr, $num_components:expr, $normalized:expr) => {
@@ -1,5 +1,4 @@
-use std::error::Error;
-use std::fmt::Display;
+use std::marker::PhantomData;
 use std::ops::Deref;
 use std::ops::DerefMut;
 
@@ -78,9 +77,17 @@ pub mod graphics_utilities;
 
 pub mod platforms;
 
+#[cfg(feature = "serde")]
+pub mod serialization;
+
+mod abi;
+mod debug_output;
 mod device_object;
+mod error;
 mod object;
 
+#[cfg(feature = "archiver")]
+mod archiver;
 mod blas;
 mod buffer;
 mod buffer_view;
@@ -89,61 +96,102 @@ mod data_blob;
 mod dearchiver;
 mod device_context;
 mod device_memory;
+mod dynamic_buffer_ring;
 mod engine_factory;
 mod fence;
+mod file_stream;
 mod frame_buffer;
+mod frame_ring;
+mod gpu_upload_manager;
 mod graphics_types;
 mod input_layout;
 mod memory_allocator;
+mod parallel_recorder;
 mod pipeline_resource_signature;
 mod pipeline_state;
 mod pipeline_state_cache;
 mod query;
+mod readback;
 mod render_device;
 mod render_pass;
 mod resource_mapping;
 mod sampler;
 mod shader;
 mod shader_binding_table;
+mod shader_compile_error;
+#[cfg(feature = "hot_reload")]
+mod shader_library;
 mod shader_resource_binding;
 mod shader_resource_variable;
+mod shader_resources;
+mod shader_source;
+mod state_tracker;
 mod swap_chain;
 mod texture;
 mod texture_view;
+mod thread_pool;
 mod tlas;
+mod uniform_buffer;
+mod vertex_layout;
+
+use crate::object::{Object, ReferenceCounters};
 
-use crate::object::Object;
+pub use self::device_object::{DeviceObject, DeviceObjectKind};
+pub use self::object::Interface;
 
+pub use self::abi::*;
+#[cfg(feature = "archiver")]
+pub use self::archiver::*;
 pub use self::blas::*;
 pub use self::buffer::*;
 pub use self::buffer_view::*;
 pub use self::command_queue::*;
 pub use self::data_blob::*;
 pub use self::dearchiver::*;
+pub use self::debug_output::*;
 pub use self::device_context::*;
 pub use self::device_memory::*;
+pub use self::dynamic_buffer_ring::*;
 pub use self::engine_factory::*;
+pub use self::error::*;
 pub use self::fence::*;
+pub use self::file_stream::*;
 pub use self::frame_buffer::*;
+pub use self::frame_ring::*;
+pub use self::gpu_upload_manager::*;
 pub use self::graphics_types::*;
 pub use self::input_layout::*;
 pub use self::memory_allocator::*;
+pub use self::parallel_recorder::*;
 pub use self::pipeline_resource_signature::*;
 pub use self::pipeline_state::*;
 pub use self::pipeline_state_cache::*;
 pub use self::query::*;
+pub use self::readback::*;
 pub use self::render_device::*;
 pub use self::render_pass::*;
 pub use self::resource_mapping::*;
 pub use self::sampler::*;
 pub use self::shader::*;
 pub use self::shader_binding_table::*;
+pub use self::shader_compile_error::*;
+#[cfg(feature = "hot_reload")]
+pub use self::shader_library::*;
 pub use self::shader_resource_binding::*;
 pub use self::shader_resource_variable::*;
+pub use self::shader_resources::*;
+pub use self::shader_source::*;
+pub use self::state_tracker::*;
 pub use self::swap_chain::*;
 pub use self::texture::*;
 pub use self::texture_view::*;
+pub use self::thread_pool::*;
 pub use self::tlas::*;
+pub use self::uniform_buffer::*;
+pub use self::vertex_layout::*;
+
+#[cfg(feature = "derive")]
+pub use diligent_derive::{ShaderResources, VertexLayout};
 
 #[cfg(feature = "vulkan")]
 pub mod vk;
@@ -358,27 +406,16 @@ pub trait Ported {
     fn sys_ptr(&self) -> *mut Self::SysType;
 }
 
-#[derive(Debug)]
-pub struct BoxedFromNulError;
-
-impl Error for BoxedFromNulError {}
-
-impl Display for BoxedFromNulError {
-    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        f.write_str("The Diligent Engine returned a null pointer when creating an object. Please check the last log messages for errors")
-    }
-}
-
 pub struct Boxed<T: Ported> {
     ptr: *mut T,
 }
 
 impl<T: Ported> Boxed<T> {
-    pub(crate) fn new(ptr: *mut T::SysType) -> Result<Boxed<T>, BoxedFromNulError> {
+    pub(crate) fn new(ptr: *mut T::SysType) -> Option<Boxed<T>> {
         if ptr.is_null() {
-            Err(BoxedFromNulError)
+            None
         } else {
-            Ok(Self { ptr: ptr as _ })
+            Some(Self { ptr: ptr as _ })
         }
     }
 
@@ -388,6 +425,33 @@ impl<T: Ported> Boxed<T> {
             ptr: object.sys_ptr() as *mut T,
         }
     }
+
+    fn object(this: &Self) -> &Object {
+        unsafe { &*(this.ptr as *const Object) }
+    }
+
+    /// Number of strong references to the object, held by Rust or by the engine.
+    ///
+    /// Returns `None` for the objects implemented in Rust, which don't expose their reference counters.
+    pub fn strong_count(this: &Self) -> Option<usize> {
+        Self::object(this)
+            .get_reference_counters()
+            .map(ReferenceCounters::get_num_strong_refs)
+    }
+
+    /// Number of weak references to the object.
+    ///
+    /// Returns `None` for the objects implemented in Rust, which don't expose their reference counters.
+    pub fn weak_count(this: &Self) -> Option<usize> {
+        Self::object(this)
+            .get_reference_counters()
+            .map(ReferenceCounters::get_num_weak_refs)
+    }
+
+    /// Gives up the exclusive access to the object, so that it can be shared by cloning the returned handle.
+    pub fn into_shared(this: Self) -> SharedBoxed<T> {
+        SharedBoxed(this)
+    }
 }
 
 impl<T: Ported> Deref for Boxed<T> {
@@ -413,11 +477,128 @@ impl<T: Ported> Drop for Boxed<T> {
 }
 
 // # Safety
-// Boxed can be moved from a thread to another. The underlying pointer is not shared
-// with other Boxeds.
+// Boxed is a counted reference and not a unique owner : `from_ref` and the engine itself can hold
+// other references to the same object. The wrappers have no state of their own, every call goes
+// through the engine, so a `&mut` borrow only selects which methods are available, as for the
+// engine contexts that must be used from one thread at a time. The engine objects can be released
+// and used from any thread, so Boxed can be sent, and shared when the wrapped type is Sync.
 unsafe impl<T: Ported> Send for Boxed<T> {}
 unsafe impl<T: Ported> Sync for Boxed<T> where T: Sync {}
 
+/// A strong reference to an engine object that can be cloned, created by `Boxed::into_shared`.
+///
+/// Each clone adds a reference to the object. Since several handles can point to the same object,
+/// they only give shared access to it.
+pub struct SharedBoxed<T: Ported>(Boxed<T>);
+
+impl<T: Ported> SharedBoxed<T> {
+    /// Number of strong references to the object, held by Rust or by the engine.
+    ///
+    /// Returns `None` for the objects implemented in Rust, which don't expose their reference counters.
+    pub fn strong_count(this: &Self) -> Option<usize> {
+        Boxed::strong_count(&this.0)
+    }
+
+    /// Number of weak references to the object.
+    ///
+    /// Returns `None` for the objects implemented in Rust, which don't expose their reference counters.
+    pub fn weak_count(this: &Self) -> Option<usize> {
+        Boxed::weak_count(&this.0)
+    }
+
+    /// Creates a weak handle to the object.
+    ///
+    /// The objects implemented in Rust don't support weak references : their weak handles never upgrade.
+    pub fn downgrade(this: &Self) -> WeakBoxed<T> {
+        let counters = Boxed::object(&this.0).get_reference_counters();
+        if let Some(counters) = counters {
+            counters.add_weak_ref();
+        }
+
+        WeakBoxed {
+            counters: counters.map_or(std::ptr::null_mut(), Ported::sys_ptr),
+            phantom: PhantomData,
+        }
+    }
+}
+
+impl<T: Ported> Clone for SharedBoxed<T> {
+    fn clone(&self) -> Self {
+        SharedBoxed(Boxed::from_ref(Boxed::object(&self.0)))
+    }
+}
+
+impl<T: Ported> Deref for SharedBoxed<T> {
+    type Target = T;
+    fn deref(&self) -> &Self::Target {
+        &self.0
+    }
+}
+
+impl<T: Ported> From<Boxed<T>> for SharedBoxed<T> {
+    fn from(value: Boxed<T>) -> Self {
+        Boxed::into_shared(value)
+    }
+}
+
+// # Safety
+// The clones of a SharedBoxed can be used from several threads at once, like an Arc :
+// they are only sent to other threads when the object can be shared between threads.
+// The reference counts are atomic, so the clones can be released from any thread.
+unsafe impl<T: Ported> Send for SharedBoxed<T> where T: Sync {}
+unsafe impl<T: Ported> Sync for SharedBoxed<T> where T: Sync {}
+
+/// A weak handle to an engine object, that does not keep the object alive.
+pub struct WeakBoxed<T: Ported> {
+    counters: *mut diligent_sys::IReferenceCounters,
+    phantom: PhantomData<T>,
+}
+
+impl<T: Ported> WeakBoxed<T> {
+    fn counters(&self) -> Option<&ReferenceCounters> {
+        unsafe { (self.counters as *const ReferenceCounters).as_ref() }
+    }
+
+    /// Returns a shared strong reference to the object if it is still alive.
+    pub fn upgrade(&self) -> Option<SharedBoxed<T>> {
+        self.counters()
+            .and_then(|counters| Boxed::new(counters.query_object() as *mut T::SysType))
+            .map(SharedBoxed)
+    }
+
+    pub fn strong_count(&self) -> usize {
+        self.counters()
+            .map_or(0, ReferenceCounters::get_num_strong_refs)
+    }
+}
+
+impl<T: Ported> Clone for WeakBoxed<T> {
+    fn clone(&self) -> Self {
+        if let Some(counters) = self.counters() {
+            counters.add_weak_ref();
+        }
+
+        WeakBoxed {
+            counters: self.counters,
+            phantom: PhantomData,
+        }
+    }
+}
+
+impl<T: Ported> Drop for WeakBoxed<T> {
+    fn drop(&mut self) {
+        if let Some(counters) = self.counters() {
+            counters.release_weak_ref();
+        }
+    }
+}
+
+// # Safety
+// The reference counters of the engine objects are thread safe. WeakBoxed gives no access
+// to the object without upgrading it, and the upgraded SharedBoxed is Send only when T is Sync.
+unsafe impl<T: Ported> Send for WeakBoxed<T> where T: Sync {}
+unsafe impl<T: Ported> Sync for WeakBoxed<T> where T: Sync {}
+
 mod resource_access_states {
     pub struct Read;
     pub struct Write;
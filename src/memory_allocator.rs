@@ -1,4 +1,13 @@
-use std::os::raw::c_void;
+use std::{
+    alloc::{GlobalAlloc, Layout},
+    ffi::CStr,
+    marker::PhantomPinned,
+    ops::Deref,
+    os::raw::{c_char, c_void},
+    pin::Pin,
+};
+
+use crate::error::object_name;
 
 define_ported!(
     MemoryAllocator,
@@ -6,11 +15,43 @@ define_ported!(
     diligent_sys::IMemoryAllocatorMethods : 4
 );
 
+/// Debug information attached to an allocation.
+#[derive(Clone, Copy, Default)]
+pub struct AllocationInfo<'a> {
+    pub description: Option<&'a CStr>,
+    pub file_name: Option<&'a CStr>,
+    pub line_number: i32,
+}
+
+impl AllocationInfo<'_> {
+    fn from_sys(description: *const c_char, file_name: *const c_char, line_number: i32) -> Self {
+        AllocationInfo {
+            description: object_name(description),
+            file_name: object_name(file_name),
+            line_number,
+        }
+    }
+
+    fn description_ptr(&self) -> *const c_char {
+        self.description.map_or(std::ptr::null(), CStr::as_ptr)
+    }
+
+    fn file_name_ptr(&self) -> *const c_char {
+        self.file_name.map_or(std::ptr::null(), CStr::as_ptr)
+    }
+}
+
 pub struct MemoryAllocation<'allocator> {
     allocator: &'allocator MemoryAllocator,
     sys_ptr: *mut c_void,
 }
 
+impl MemoryAllocation<'_> {
+    pub fn as_ptr(&self) -> *mut c_void {
+        self.sys_ptr
+    }
+}
+
 impl Drop for MemoryAllocation<'_> {
     fn drop(&mut self) {
         unsafe {
@@ -29,6 +70,12 @@ pub struct AlignedMemoryAllocation<'allocator> {
     sys_ptr: *mut c_void,
 }
 
+impl AlignedMemoryAllocation<'_> {
+    pub fn as_ptr(&self) -> *mut c_void {
+        self.sys_ptr
+    }
+}
+
 impl Drop for AlignedMemoryAllocation<'_> {
     fn drop(&mut self) {
         unsafe {
@@ -43,16 +90,15 @@ impl Drop for AlignedMemoryAllocation<'_> {
 }
 
 impl MemoryAllocator {
-    pub fn allocate(&self, size: usize) -> MemoryAllocation<'_> {
+    pub fn allocate(&self, size: usize, info: &AllocationInfo) -> MemoryAllocation<'_> {
         let mem_ptr = unsafe_member_call!(
             self,
             MemoryAllocator,
             Allocate,
             size,
-            // TODO
-            std::ptr::null(),
-            std::ptr::null(),
-            line!() as _
+            info.description_ptr(),
+            info.file_name_ptr(),
+            info.line_number
         );
 
         MemoryAllocation {
@@ -61,22 +107,218 @@ impl MemoryAllocator {
         }
     }
 
-    pub fn allocate_aligned(&self, size: usize, alignment: usize) -> MemoryAllocation<'_> {
+    pub fn allocate_aligned(
+        &self,
+        size: usize,
+        alignment: usize,
+        info: &AllocationInfo,
+    ) -> AlignedMemoryAllocation<'_> {
         let mem_ptr = unsafe_member_call!(
             self,
             MemoryAllocator,
             AllocateAligned,
             size,
             alignment,
-            // TODO
-            std::ptr::null(),
-            std::ptr::null(),
-            line!() as _
+            info.description_ptr(),
+            info.file_name_ptr(),
+            info.line_number
         );
 
-        MemoryAllocation {
+        AlignedMemoryAllocation {
             allocator: self,
             sys_ptr: mem_ptr,
         }
     }
 }
+
+/// A memory allocator implemented in Rust that the engine can use through
+/// `EngineFactory::set_memory_allocator`.
+///
+/// The memory returned by `allocate` is released with `free`, and the memory returned by
+/// `allocate_aligned` is released with `free_aligned`. The functions can be called from any thread.
+pub trait RustAllocator: Send + Sync {
+    fn allocate(&self, size: usize, info: &AllocationInfo) -> *mut c_void;
+
+    fn free(&self, ptr: *mut c_void);
+
+    fn allocate_aligned(&self, size: usize, alignment: usize, info: &AllocationInfo)
+    -> *mut c_void;
+
+    fn free_aligned(&self, ptr: *mut c_void);
+}
+
+/// Implements `RustAllocator` on top of any `GlobalAlloc` (the system allocator, jemalloc,
+/// a tracking allocator, ...).
+///
+/// The engine does not provide the size of the memory it frees, so the layout of every
+/// allocation is stored in a small header right before the returned pointer.
+pub struct GlobalAllocAdapter<A: GlobalAlloc + Send + Sync>(pub A);
+
+impl<A: GlobalAlloc + Send + Sync> GlobalAllocAdapter<A> {
+    // Matches the alignment guaranteed by malloc on 64-bit platforms
+    const DEFAULT_ALIGNMENT: usize = 16;
+
+    const HEADER_SIZE: usize = 2 * std::mem::size_of::<usize>();
+
+    fn header_offset(alignment: usize) -> usize {
+        Self::HEADER_SIZE.next_multiple_of(alignment)
+    }
+
+    fn allocate_with_header(&self, size: usize, alignment: usize) -> *mut c_void {
+        let alignment = alignment.max(std::mem::align_of::<usize>());
+        let offset = Self::header_offset(alignment);
+
+        let Some(layout) = offset
+            .checked_add(size)
+            .and_then(|total_size| Layout::from_size_align(total_size, alignment).ok())
+        else {
+            return std::ptr::null_mut();
+        };
+
+        let base_ptr = unsafe { self.0.alloc(layout) };
+        if base_ptr.is_null() {
+            return std::ptr::null_mut();
+        }
+
+        unsafe {
+            let ptr = base_ptr.add(offset);
+            let header = ptr.cast::<usize>().sub(2);
+            header.write(layout.size());
+            header.add(1).write(layout.align());
+            ptr as *mut c_void
+        }
+    }
+
+    fn free_with_header(&self, ptr: *mut c_void) {
+        if ptr.is_null() {
+            return;
+        }
+
+        unsafe {
+            let header = ptr.cast::<usize>().sub(2);
+            let size = header.read();
+            let alignment = header.add(1).read();
+
+            let base_ptr = ptr.cast::<u8>().sub(Self::header_offset(alignment));
+            self.0
+                .dealloc(base_ptr, Layout::from_size_align_unchecked(size, alignment));
+        }
+    }
+}
+
+impl<A: GlobalAlloc + Send + Sync> RustAllocator for GlobalAllocAdapter<A> {
+    fn allocate(&self, size: usize, _info: &AllocationInfo) -> *mut c_void {
+        self.allocate_with_header(size, Self::DEFAULT_ALIGNMENT)
+    }
+
+    fn free(&self, ptr: *mut c_void) {
+        self.free_with_header(ptr)
+    }
+
+    fn allocate_aligned(
+        &self,
+        size: usize,
+        alignment: usize,
+        _info: &AllocationInfo,
+    ) -> *mut c_void {
+        self.allocate_with_header(size, alignment)
+    }
+
+    fn free_aligned(&self, ptr: *mut c_void) {
+        self.free_with_header(ptr)
+    }
+}
+
+#[repr(C)]
+struct RustMemoryAllocatorImpl<A: RustAllocator> {
+    // Must be the first field : the engine only sees a pointer to this member
+    sys: diligent_sys::IMemoryAllocator,
+    vtbl: diligent_sys::IMemoryAllocatorVtbl,
+    allocator: A,
+    _pinned: PhantomPinned,
+}
+
+impl<A: RustAllocator> RustMemoryAllocatorImpl<A> {
+    unsafe fn from_sys<'a>(this: *mut diligent_sys::IMemoryAllocator) -> &'a Self {
+        unsafe { &*(this as *const Self) }
+    }
+
+    unsafe extern "C" fn allocate(
+        this: *mut diligent_sys::IMemoryAllocator,
+        size: usize,
+        dbg_description: *const c_char,
+        dbg_file_name: *const c_char,
+        dbg_line_number: i32,
+    ) -> *mut c_void {
+        let this = unsafe { Self::from_sys(this) };
+        this.allocator.allocate(
+            size,
+            &AllocationInfo::from_sys(dbg_description, dbg_file_name, dbg_line_number),
+        )
+    }
+
+    unsafe extern "C" fn free(this: *mut diligent_sys::IMemoryAllocator, ptr: *mut c_void) {
+        let this = unsafe { Self::from_sys(this) };
+        this.allocator.free(ptr)
+    }
+
+    unsafe extern "C" fn allocate_aligned(
+        this: *mut diligent_sys::IMemoryAllocator,
+        size: usize,
+        alignment: usize,
+        dbg_description: *const c_char,
+        dbg_file_name: *const c_char,
+        dbg_line_number: i32,
+    ) -> *mut c_void {
+        let this = unsafe { Self::from_sys(this) };
+        this.allocator.allocate_aligned(
+            size,
+            alignment,
+            &AllocationInfo::from_sys(dbg_description, dbg_file_name, dbg_line_number),
+        )
+    }
+
+    unsafe extern "C" fn free_aligned(this: *mut diligent_sys::IMemoryAllocator, ptr: *mut c_void) {
+        let this = unsafe { Self::from_sys(this) };
+        this.allocator.free_aligned(ptr)
+    }
+}
+
+/// Exposes a `RustAllocator` to the engine as a `MemoryAllocator`.
+pub struct RustMemoryAllocator<A: RustAllocator>(Pin<Box<RustMemoryAllocatorImpl<A>>>);
+
+impl<A: RustAllocator> RustMemoryAllocator<A> {
+    pub fn new(allocator: A) -> Self {
+        let mut allocator_impl = Box::new(RustMemoryAllocatorImpl {
+            sys: diligent_sys::IMemoryAllocator {
+                pVtbl: std::ptr::null_mut(),
+            },
+            vtbl: diligent_sys::IMemoryAllocatorVtbl {
+                MemoryAllocator: diligent_sys::IMemoryAllocatorMethods {
+                    Allocate: Some(RustMemoryAllocatorImpl::<A>::allocate),
+                    Free: Some(RustMemoryAllocatorImpl::<A>::free),
+                    AllocateAligned: Some(RustMemoryAllocatorImpl::<A>::allocate_aligned),
+                    FreeAligned: Some(RustMemoryAllocatorImpl::<A>::free_aligned),
+                },
+            },
+            allocator,
+            _pinned: PhantomPinned,
+        });
+
+        // The box will never move from now on, so the vtable can be referenced by address
+        allocator_impl.sys.pVtbl = std::ptr::addr_of_mut!(allocator_impl.vtbl) as _;
+
+        RustMemoryAllocator(Box::into_pin(allocator_impl))
+    }
+
+    pub fn allocator(&self) -> &A {
+        &self.0.allocator
+    }
+}
+
+impl<A: RustAllocator> Deref for RustMemoryAllocator<A> {
+    type Target = MemoryAllocator;
+    fn deref(&self) -> &Self::Target {
+        unsafe { &*(std::ptr::from_ref(&self.0.sys) as *const MemoryAllocator) }
+    }
+}
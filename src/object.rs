@@ -1,5 +1,157 @@
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+use crate::{Boxed, Ported};
+
 define_ported!(
     Object,
     diligent_sys::IObject,
     diligent_sys::IObjectMethods : 4
 );
+
+define_ported!(
+    ReferenceCounters,
+    diligent_sys::IReferenceCounters,
+    diligent_sys::IReferenceCountersMethods : 7
+);
+
+impl ReferenceCounters {
+    pub(crate) fn add_weak_ref(&self) {
+        unsafe_member_call!(self, ReferenceCounters, AddWeakRef);
+    }
+
+    pub(crate) fn release_weak_ref(&self) {
+        unsafe_member_call!(self, ReferenceCounters, ReleaseWeakRef);
+    }
+
+    /// Returns a new strong reference to the object, if it is still alive.
+    pub(crate) fn query_object(&self) -> *mut diligent_sys::IObject {
+        let mut object_ptr = std::ptr::null_mut();
+        unsafe_member_call!(self, ReferenceCounters, QueryObject, &mut object_ptr);
+        object_ptr
+    }
+
+    pub(crate) fn get_num_strong_refs(&self) -> usize {
+        unsafe_member_call!(self, ReferenceCounters, GetNumStrongRefs) as usize
+    }
+
+    pub(crate) fn get_num_weak_refs(&self) -> usize {
+        unsafe_member_call!(self, ReferenceCounters, GetNumWeakRefs) as usize
+    }
+}
+
+/// A ported type that can be obtained from another interface of the same object with `QueryInterface`.
+pub trait Interface: Ported {
+    /// The interface ID declared in the engine headers.
+    const IID: diligent_sys::INTERFACE_ID;
+}
+
+impl Object {
+    /// The objects implemented in Rust don't have reference counters.
+    pub(crate) fn get_reference_counters(&self) -> Option<&ReferenceCounters> {
+        let counters_ptr = unsafe_member_call!(self, Object, GetReferenceCounters);
+        unsafe { (counters_ptr as *const ReferenceCounters).as_ref() }
+    }
+
+    pub(crate) fn query_interface<T: Interface>(&self) -> Option<Boxed<T>> {
+        let mut interface_ptr = std::ptr::null_mut();
+        unsafe_member_call!(self, Object, QueryInterface, &T::IID, &mut interface_ptr);
+        Boxed::new(interface_ptr as *mut T::SysType)
+    }
+}
+
+/// Heap layout of the engine interfaces implemented in Rust.
+///
+/// The engine only sees a pointer to the first field, which has the same layout
+/// as the `diligent_sys` interface structs : a single pointer to the vtable.
+#[repr(C)]
+pub(crate) struct RustObject<Vtbl: 'static, T> {
+    vtbl_ptr: *const Vtbl,
+    vtbl: Vtbl,
+    ref_count: AtomicUsize,
+    pub(crate) value: T,
+}
+
+impl<Vtbl: 'static, T> RustObject<Vtbl, T> {
+    pub(crate) const OBJECT_METHODS: diligent_sys::IObjectMethods = diligent_sys::IObjectMethods {
+        QueryInterface: Some(Self::query_interface),
+        AddRef: Some(Self::add_ref),
+        Release: Some(Self::release),
+        GetReferenceCounters: Some(Self::get_reference_counters),
+    };
+
+    /// Allocates the object with a reference count of 1.
+    /// The returned pointer must be released through the `Release` method of its vtable.
+    pub(crate) fn create<SysType>(vtbl: Vtbl, value: T) -> *mut SysType {
+        let object = Box::into_raw(Box::new(RustObject {
+            vtbl_ptr: std::ptr::null(),
+            vtbl,
+            ref_count: AtomicUsize::new(1),
+            value,
+        }));
+
+        // The object will never move from now on, so the vtable can be referenced by address
+        unsafe { (*object).vtbl_ptr = std::ptr::addr_of!((*object).vtbl) };
+
+        object as *mut SysType
+    }
+
+    /// # Safety
+    /// `this` must have been created by `RustObject::<Vtbl, T>::create`.
+    pub(crate) unsafe fn from_sys<'a, SysType>(this: *mut SysType) -> &'a Self {
+        unsafe { &*(this as *const Self) }
+    }
+
+    unsafe extern "C" fn query_interface(
+        this: *mut diligent_sys::IObject,
+        iid: *const diligent_sys::INTERFACE_ID,
+        interface: *mut *mut diligent_sys::IObject,
+    ) {
+        if interface.is_null() {
+            return;
+        }
+
+        let iid = unsafe { &*iid };
+
+        // The objects implemented in Rust only answer to IID_Unknown
+        let is_unknown = iid.Data1 == 0 && iid.Data2 == 0 && iid.Data3 == 0 && iid.Data4 == [0; 8];
+
+        unsafe {
+            if is_unknown {
+                Self::add_ref(this);
+                *interface = this;
+            } else {
+                *interface = std::ptr::null_mut();
+            }
+        }
+    }
+
+    unsafe extern "C" fn add_ref(
+        this: *mut diligent_sys::IObject,
+    ) -> diligent_sys::ReferenceCounterValueType {
+        let this = unsafe { Self::from_sys(this) };
+        (this.ref_count.fetch_add(1, Ordering::Relaxed) + 1) as _
+    }
+
+    unsafe extern "C" fn release(
+        this: *mut diligent_sys::IObject,
+    ) -> diligent_sys::ReferenceCounterValueType {
+        let ref_count = unsafe { Self::from_sys(this) }
+            .ref_count
+            .fetch_sub(1, Ordering::Release)
+            - 1;
+
+        if ref_count == 0 {
+            std::sync::atomic::fence(Ordering::Acquire);
+            drop(unsafe { Box::from_raw(this as *mut Self) });
+        }
+
+        ref_count as _
+    }
+
+    unsafe extern "C" fn get_reference_counters(
+        _this: *mut diligent_sys::IObject,
+    ) -> *mut diligent_sys::IReferenceCounters {
+        // Weak references to the objects implemented in Rust are not supported
+        std::ptr::null_mut()
+    }
+}
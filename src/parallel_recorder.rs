@@ -0,0 +1,150 @@
+use std::{
+    panic::AssertUnwindSafe,
+    sync::{Barrier, mpsc},
+};
+
+use crate::{
+    Boxed, Error,
+    device_context::{CommandList, DeferredDeviceContext, ImmediateDeviceContext},
+};
+
+/// Records commands on deferred contexts from worker threads, and executes them on an immediate context.
+///
+/// Each call to `record` runs one worker per deferred context on a scoped thread.
+/// The command lists are executed in the order of the contexts, whatever the order the workers finish in.
+pub struct ParallelRecorder {
+    contexts: Vec<Boxed<DeferredDeviceContext>>,
+}
+
+impl ParallelRecorder {
+    pub fn new(contexts: Vec<Boxed<DeferredDeviceContext>>) -> Self {
+        ParallelRecorder { contexts }
+    }
+
+    pub fn contexts(&self) -> &[Boxed<DeferredDeviceContext>] {
+        &self.contexts
+    }
+
+    pub fn into_contexts(self) -> Vec<Boxed<DeferredDeviceContext>> {
+        self.contexts
+    }
+
+    /// Calls `record` on a worker thread for each deferred context, with the index of the context,
+    /// then executes the command lists on `immediate_context`.
+    ///
+    /// The data shared by the workers, such as the pipeline states and the shader resource bindings,
+    /// is captured by `record`. The deferred contexts start in the default state : `record` must bind
+    /// everything it uses.
+    ///
+    /// Once the command lists have been executed, the workers finish the frame of their context,
+    /// which releases the dynamic resources it allocated. Nothing is executed if a command list
+    /// could not be finished, or if a worker panicked. The panic of a worker is propagated once
+    /// all the workers are done, and the contexts stay in the recorder.
+    pub fn record<F>(
+        &mut self,
+        immediate_context: &ImmediateDeviceContext,
+        record: F,
+    ) -> Result<(), Error>
+    where
+        F: Fn(usize, &DeferredDeviceContext) + Sync,
+    {
+        let immediate_context_id = u32::from(immediate_context.desc().context_id());
+        let worker_count = self.contexts.len();
+
+        // The workers wait for the command lists to be executed before finishing their frame
+        let executed_barrier = Barrier::new(worker_count + 1);
+        let (command_list_sender, command_list_receiver) = mpsc::channel();
+
+        let record = &record;
+        let executed_barrier = &executed_barrier;
+
+        std::thread::scope(|scope| {
+            let workers: Vec<_> = self
+                .contexts
+                .iter_mut()
+                .enumerate()
+                .map(|(index, context)| {
+                    let command_list_sender = command_list_sender.clone();
+                    scope.spawn(move || {
+                        let context: &DeferredDeviceContext = context;
+
+                        let recorded = std::panic::catch_unwind(AssertUnwindSafe(|| {
+                            context.begin(immediate_context_id);
+                            record(index, context)
+                        }));
+
+                        // The command list is finished even after a panic, so that the context can record again
+                        let finished = std::panic::catch_unwind(AssertUnwindSafe(|| {
+                            context.finish_command_list()
+                        }));
+
+                        let (command_list, panic) = match (recorded, finished) {
+                            (Ok(()), Ok(command_list)) => (Some(command_list), None),
+                            (Err(panic), _) | (Ok(()), Err(panic)) => (None, Some(panic)),
+                        };
+
+                        // A worker that panicked still reports to the thread that executes the command lists
+                        let _ = command_list_sender.send((index, command_list));
+                        drop(command_list_sender);
+
+                        executed_barrier.wait();
+
+                        unsafe { context.finish_frame() };
+
+                        if let Some(panic) = panic {
+                            std::panic::resume_unwind(panic);
+                        }
+                    })
+                })
+                .collect();
+
+            drop(command_list_sender);
+
+            // The workers are released even if the command lists can not be executed
+            let release_workers = ReleaseOnDrop(executed_barrier);
+
+            let mut command_lists: Vec<(usize, Option<Result<Boxed<CommandList>, Error>>)> =
+                command_list_receiver.iter().take(worker_count).collect();
+            command_lists.sort_by_key(|(index, _)| *index);
+
+            let command_lists: Option<Result<Vec<_>, _>> = command_lists
+                .into_iter()
+                .map(|(_, command_list)| command_list)
+                .collect();
+
+            let result = match command_lists {
+                Some(Ok(command_lists)) => {
+                    immediate_context.execute_command_lists(
+                        &command_lists.iter().map(|list| &**list).collect::<Vec<_>>(),
+                    );
+                    Ok(())
+                }
+                Some(Err(error)) => Err(error),
+                // A worker panicked : its panic is propagated when it is joined
+                None => Ok(()),
+            };
+
+            drop(release_workers);
+
+            let panics: Vec<_> = workers
+                .into_iter()
+                .filter_map(|worker| worker.join().err())
+                .collect();
+
+            if let Some(panic) = panics.into_iter().next() {
+                std::panic::resume_unwind(panic);
+            }
+
+            result
+        })
+    }
+}
+
+/// Waits on the barrier when dropped, including while unwinding.
+struct ReleaseOnDrop<'barrier>(&'barrier Barrier);
+
+impl Drop for ReleaseOnDrop<'_> {
+    fn drop(&mut self) {
+        self.0.wait();
+    }
+}
@@ -9,8 +9,9 @@ use bitflags::bitflags;
 use static_assertions::const_assert_eq;
 
 use crate::{
-    Boxed, BoxedFromNulError, Ported,
+    Boxed, Error, Ported,
     device_object::{DeviceObject, DeviceObjectAttribs},
+    error::{MessageCapture, Operation},
     graphics_types::{ShaderType, ShaderTypes},
     resource_mapping::ResourceMapping,
     sampler::SamplerDesc,
@@ -289,8 +290,9 @@ impl PipelineResourceSignature {
     pub fn create_shader_resource_binding(
         &self,
         init_static_resources: bool,
-    ) -> Result<Boxed<ShaderResourceBinding>, BoxedFromNulError> {
+    ) -> Result<Boxed<ShaderResourceBinding>, Error> {
         let mut shader_resource_binding_ptr = std::ptr::null_mut();
+        let capture = MessageCapture::begin();
         unsafe_member_call!(
             self,
             PipelineResourceSignature,
@@ -299,7 +301,11 @@ impl PipelineResourceSignature {
             init_static_resources
         );
 
-        Boxed::new(shader_resource_binding_ptr)
+        capture.boxed(
+            shader_resource_binding_ptr,
+            Operation::CreateShaderResourceBinding,
+            None,
+        )
     }
 
     pub fn bind_static_resources(
@@ -9,7 +9,10 @@ use static_assertions::const_assert_eq;
 
 use crate::device_object::DeviceObject;
 use crate::pipeline_state_cache::PipelineStateCache;
-use crate::{Boxed, BoxedFromNulError, PipelineResourceFlags, PipelineType, Ported};
+use crate::{
+    Boxed, Error, PipelineResourceFlags, PipelineType, Ported,
+    error::{MessageCapture, Operation},
+};
 use crate::{
     graphics_types::{PrimitiveTopology, ShaderType, ShaderTypes, TextureFormat},
     input_layout::LayoutElement,
@@ -25,6 +28,7 @@ use crate::{
 };
 
 #[derive(Clone, Copy)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum BlendFactor {
     Zero,
     One,
@@ -102,6 +106,7 @@ impl TryFrom<diligent_sys::BLEND_FACTOR> for BlendFactor {
 }
 
 #[derive(Clone, Copy)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum BlendOperation {
     Add,
     Subtract,
@@ -141,6 +146,7 @@ impl TryFrom<diligent_sys::BLEND_OPERATION> for BlendOperation {
 }
 
 #[derive(Clone, Copy)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum LogicOperation {
     Clear,
     Set,
@@ -213,6 +219,7 @@ impl TryFrom<diligent_sys::LOGIC_OPERATION> for LogicOperation {
 }
 
 #[derive(Clone, Copy)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum FillMode {
     Wireframe,
     Solid,
@@ -242,6 +249,7 @@ impl TryFrom<diligent_sys::FILL_MODE> for FillMode {
 }
 
 #[derive(Clone, Copy)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum CullMode {
     None,
     Front,
@@ -274,6 +282,7 @@ impl TryFrom<diligent_sys::CULL_MODE> for CullMode {
 }
 
 #[derive(Clone, Copy)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum StencilOperation {
     Keep,
     Zero,
@@ -322,6 +331,7 @@ impl TryFrom<diligent_sys::STENCIL_OP> for StencilOperation {
 }
 
 #[derive(Clone, Copy)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum ComparisonFunction {
     Never,
     Less,
@@ -428,6 +438,7 @@ impl ShaderVariableFlags {
 
 bitflags! {
     #[derive(Clone,Copy)]
+    #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
     pub struct ColorMask: diligent_sys::COLOR_MASK {
         const NONE  = diligent_sys::COLOR_MASK_NONE as diligent_sys::COLOR_MASK;
         const RED   = diligent_sys::COLOR_MASK_RED as diligent_sys::COLOR_MASK;
@@ -1010,6 +1021,20 @@ impl BlendStateDesc {
     }
 }
 
+impl BlendStateDesc {
+    pub fn alpha_to_coverage_enable(&self) -> bool {
+        self.0.AlphaToCoverageEnable
+    }
+    pub fn independent_blend_enable(&self) -> bool {
+        self.0.IndependentBlendEnable
+    }
+    pub fn render_targets(
+        &self,
+    ) -> &[RenderTargetBlendDesc; diligent_sys::DILIGENT_MAX_RENDER_TARGETS as usize] {
+        unsafe { std::mem::transmute(&self.0.RenderTargets) }
+    }
+}
+
 #[derive(Clone)]
 #[repr(transparent)]
 pub struct RasterizerStateDesc(pub(crate) diligent_sys::RasterizerStateDesc);
@@ -1977,8 +2002,9 @@ impl PipelineState {
     pub fn create_shader_resource_binding(
         &self,
         init_static_resources: bool,
-    ) -> Result<Boxed<ShaderResourceBinding>, BoxedFromNulError> {
+    ) -> Result<Boxed<ShaderResourceBinding>, Error> {
         let mut shader_resource_binding_ptr = std::ptr::null_mut();
+        let capture = MessageCapture::begin();
         unsafe_member_call!(
             self,
             PipelineState,
@@ -1987,7 +2013,11 @@ impl PipelineState {
             init_static_resources
         );
 
-        Boxed::new(shader_resource_binding_ptr)
+        capture.boxed(
+            shader_resource_binding_ptr,
+            Operation::CreateShaderResourceBinding,
+            None,
+        )
     }
 
     pub fn initialize_static_srb_resources(
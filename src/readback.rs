@@ -0,0 +1,382 @@
+use std::{
+    cell::{Cell, RefCell},
+    ffi::CStr,
+    future::Future,
+    ops::Range,
+    pin::Pin,
+    task::{Context, Poll},
+};
+
+use crate::{
+    Boxed, Error, Ported,
+    buffer::{Buffer, BufferDesc},
+    device_context::{DeviceContext, ImmediateDeviceContext},
+    device_object::ResourceTransition,
+    error::Operation,
+    fence::{Fence, FenceDesc, FenceWait},
+    graphics_types::{BindFlags, CpuAccessFlags, MapFlags, Usage},
+    render_device::RenderDevice,
+};
+
+#[cfg(any(feature = "d3d11", feature = "d3d12", feature = "vulkan"))]
+use std::num::NonZero;
+
+#[cfg(any(feature = "d3d11", feature = "d3d12", feature = "vulkan"))]
+use crate::texture::{Texture, TextureDesc, TextureDimension};
+
+const STAGING_BUFFER_NAME: &CStr = c"Readback staging buffer";
+
+#[cfg(any(feature = "d3d11", feature = "d3d12", feature = "vulkan"))]
+const STAGING_TEXTURE_NAME: &CStr = c"Readback staging texture";
+
+/// The staging resources and the fence used by the readbacks of an immediate context.
+///
+/// The staging resources are given back to the pool when their data has been read,
+/// and reused by the following readbacks. A pool must only be used with a single context.
+pub struct ReadbackPool {
+    render_device: Boxed<RenderDevice>,
+    fence: Boxed<Fence>,
+    last_fence_value: Cell<u64>,
+    buffers: RefCell<Vec<Boxed<Buffer>>>,
+    #[cfg(any(feature = "d3d11", feature = "d3d12", feature = "vulkan"))]
+    textures: RefCell<Vec<Boxed<Texture>>>,
+}
+
+impl ReadbackPool {
+    pub fn new(render_device: &RenderDevice) -> Result<Self, Error> {
+        let fence =
+            render_device.create_fence(&FenceDesc::builder().name(c"Readback fence").build())?;
+
+        Ok(ReadbackPool {
+            render_device: Boxed::from_ref(render_device),
+            fence,
+            last_fence_value: Cell::new(0),
+            buffers: RefCell::new(Vec::new()),
+            #[cfg(any(feature = "d3d11", feature = "d3d12", feature = "vulkan"))]
+            textures: RefCell::new(Vec::new()),
+        })
+    }
+
+    /// Releases the staging resources that are not used by a pending readback.
+    pub fn clear(&self) {
+        self.buffers.borrow_mut().clear();
+        #[cfg(any(feature = "d3d11", feature = "d3d12", feature = "vulkan"))]
+        self.textures.borrow_mut().clear();
+    }
+
+    fn next_fence_value(&self) -> u64 {
+        let value = self.last_fence_value.get() + 1;
+        self.last_fence_value.set(value);
+        value
+    }
+
+    /// Takes the smallest free staging buffer of at least `size` bytes, or creates a new one.
+    fn staging_buffer(&self, size: u64) -> Result<Boxed<Buffer>, Error> {
+        let mut buffers = self.buffers.borrow_mut();
+
+        let best_fit = buffers
+            .iter()
+            .enumerate()
+            .filter(|(_, buffer)| buffer.desc().size() >= size)
+            .min_by_key(|(_, buffer)| buffer.desc().size())
+            .map(|(index, _)| index);
+
+        if let Some(index) = best_fit {
+            return Ok(buffers.swap_remove(index));
+        }
+
+        // Rounded up so that the buffer can be reused by reads of similar sizes
+        self.render_device.create_buffer(
+            &BufferDesc::builder()
+                .name(STAGING_BUFFER_NAME)
+                .size(size.next_power_of_two())
+                .bind_flags(BindFlags::None)
+                .usage(Usage::Staging)
+                .cpu_access_flags(CpuAccessFlags::Read)
+                .build(),
+        )
+    }
+
+    /// Takes a free staging texture with the layout of `desc`, or creates a new one.
+    #[cfg(any(feature = "d3d11", feature = "d3d12", feature = "vulkan"))]
+    fn staging_texture(&self, desc: &TextureDesc) -> Result<Boxed<Texture>, Error> {
+        let mut textures = self.textures.borrow_mut();
+
+        let same_layout = textures.iter().position(|texture| {
+            let staging_desc = &texture.desc().0;
+            staging_desc.Type == desc.0.Type
+                && staging_desc.Width == desc.0.Width
+                && staging_desc.Height == desc.0.Height
+                && staging_desc.Format == desc.0.Format
+                && texture.desc().depth() == desc.depth()
+        });
+
+        if let Some(index) = same_layout {
+            return Ok(textures.swap_remove(index));
+        }
+
+        self.render_device.create_texture(desc, &[], None)
+    }
+}
+
+enum Staging {
+    Buffer {
+        buffer: Boxed<Buffer>,
+        size: usize,
+    },
+    #[cfg(any(feature = "d3d11", feature = "d3d12", feature = "vulkan"))]
+    Texture {
+        texture: Boxed<Texture>,
+    },
+}
+
+/// The content of a resource that is being copied to a staging resource by the GPU.
+///
+/// The copy is complete when the fence of the pool reaches the value signaled after the copy.
+/// The context is flushed the first time the readback is found incomplete, so waiting for a
+/// readback never depends on the commands submitted afterwards.
+///
+/// As a future, the readback waits for the fence with `Fence::wait_async` : it is woken by
+/// `ImmediateDeviceContext::finish_frame`. Applications can also call `try_get` once per frame.
+pub struct Readback<'context> {
+    device_context: &'context ImmediateDeviceContext,
+    pool: &'context ReadbackPool,
+    fence_value: u64,
+    flushed: bool,
+    fence_wait: Option<FenceWait>,
+    staging: Option<Staging>,
+}
+
+impl Readback<'_> {
+    pub fn is_ready(&self) -> bool {
+        self.pool.fence.get_completed_value() >= self.fence_value
+    }
+
+    /// Returns the data without blocking if the copy is complete.
+    ///
+    /// Returns `None` if the copy is still running, or if the data was already returned.
+    /// Returns an error if the staging resource can not be mapped.
+    pub fn try_get(&mut self) -> Option<Result<Vec<u8>, Error>> {
+        self.staging.as_ref()?;
+
+        if !self.is_ready() {
+            if !self.flushed {
+                self.device_context.flush();
+                self.flushed = true;
+            }
+            return None;
+        }
+
+        let data = match self.staging.take()? {
+            Staging::Buffer { buffer, size } => {
+                let data = self
+                    .device_context
+                    .map_buffer_read::<u8>(&buffer, MapFlags::None)
+                    .map(|mapped| mapped[..size].to_vec());
+
+                self.pool.buffers.borrow_mut().push(buffer);
+                data
+            }
+            #[cfg(any(feature = "d3d11", feature = "d3d12", feature = "vulkan"))]
+            Staging::Texture { texture } => {
+                let data = read_texture(self.device_context, &texture);
+                self.pool.textures.borrow_mut().push(texture);
+                data
+            }
+        };
+
+        Some(data)
+    }
+}
+
+impl Future for Readback<'_> {
+    type Output = Result<Vec<u8>, Error>;
+
+    fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        let this = self.get_mut();
+
+        assert!(this.staging.is_some(), "Readback polled after completion");
+
+        loop {
+            if let Some(data) = this.try_get() {
+                return Poll::Ready(data);
+            }
+
+            // Once the fence wait is ready, the copy is complete and the data can be read
+            let fence_wait = this
+                .fence_wait
+                .get_or_insert_with(|| this.pool.fence.wait_async(this.fence_value));
+
+            if Pin::new(fence_wait).poll(cx).is_pending() {
+                return Poll::Pending;
+            }
+        }
+    }
+}
+
+/// Copies the rows of the first subresource of a staging texture, without the padding of the mapping.
+#[cfg(any(feature = "d3d11", feature = "d3d12", feature = "vulkan"))]
+fn read_texture(device_context: &DeviceContext, texture: &Texture) -> Result<Vec<u8>, Error> {
+    let desc = texture.desc();
+    let row_size = desc.mip_level_row_size(0);
+    let row_count = desc.mip_level_row_count(0);
+    let depth = if desc.dimension().is_3D() {
+        desc.depth() as usize
+    } else {
+        1
+    };
+
+    let mapped =
+        device_context.map_texture_subresource_read::<u8>(texture, 0, 0, MapFlags::None, None)?;
+
+    let mut data = Vec::with_capacity(row_size * row_count * depth);
+    for slice in 0..depth {
+        for row in 0..row_count {
+            let offset = slice * mapped.depth_stride() as usize + row * mapped.stride() as usize;
+            // The mapping spans the padded rows, which can be larger than the slice of the token
+            data.extend_from_slice(unsafe {
+                std::slice::from_raw_parts(mapped.as_ptr().add(offset), row_size)
+            });
+        }
+    }
+    Ok(data)
+}
+
+impl ImmediateDeviceContext {
+    /// Copies `range` of `buffer` to a staging buffer of `pool`, and returns the readback of the copy.
+    ///
+    /// Returns an error if `range` is empty or inverted, or goes past the end of the buffer.
+    pub fn read_buffer_async<'context, 'buffer, BufferTransition>(
+        &'context self,
+        pool: &'context ReadbackPool,
+        buffer: BufferTransition,
+        range: Range<u64>,
+    ) -> Result<Readback<'context>, Error>
+    where
+        BufferTransition: ResourceTransition<'buffer, Buffer>,
+    {
+        let buffer_ptr = buffer.sys_ptr();
+        let buffer_desc = unsafe { &*(buffer_ptr as *const Buffer) }.desc();
+
+        if range.start >= range.end || range.end > buffer_desc.size() {
+            return Err(Error::invalid_argument(
+                Operation::ReadBufferAsync,
+                buffer_desc.name(),
+                format!(
+                    "the range {range:?} is empty or not within the {} bytes of the buffer",
+                    buffer_desc.size()
+                ),
+            ));
+        }
+
+        let size = range.end - range.start;
+        let staging_buffer = pool.staging_buffer(size)?;
+
+        let device_context: &DeviceContext = self;
+        unsafe_member_call!(
+            device_context,
+            DeviceContext,
+            CopyBuffer,
+            buffer_ptr,
+            range.start,
+            BufferTransition::TRANSITION_MODE,
+            staging_buffer.sys_ptr(),
+            0,
+            size,
+            diligent_sys::RESOURCE_STATE_TRANSITION_MODE_TRANSITION as _
+        );
+
+        let fence_value = pool.next_fence_value();
+        self.enqueue_signal(&pool.fence, fence_value);
+
+        Ok(Readback {
+            device_context: self,
+            pool,
+            fence_value,
+            flushed: false,
+            fence_wait: None,
+            staging: Some(Staging::Buffer {
+                buffer: staging_buffer,
+                size: size as usize,
+            }),
+        })
+    }
+
+    /// Copies a subresource of `texture` to a staging texture of `pool`, and returns the readback of the copy.
+    ///
+    /// The data of the readback is tightly packed : its `mip_level_row_count(mip_level)` rows are
+    /// `mip_level_row_size(mip_level)` bytes long, a row being a line of blocks for the compressed formats.
+    ///
+    /// # Panics
+    /// Panics if the format of the texture is unknown.
+    #[cfg(any(feature = "d3d11", feature = "d3d12", feature = "vulkan"))]
+    pub fn read_texture_async<'context, 'texture, TextureTransition>(
+        &'context self,
+        pool: &'context ReadbackPool,
+        texture: TextureTransition,
+        mip_level: u32,
+        array_slice: u32,
+    ) -> Result<Readback<'context>, Error>
+    where
+        TextureTransition: ResourceTransition<'texture, Texture>,
+    {
+        let texture_ptr = texture.sys_ptr();
+        let texture_desc = unsafe { &*(texture_ptr as *const Texture) }.desc();
+
+        let dimension = match texture_desc.dimension() {
+            dimension if dimension.is_1D() => TextureDimension::Texture1D,
+            dimension if dimension.is_3D() => TextureDimension::Texture3D {
+                depth: NonZero::new(texture_desc.mip_level_depth(mip_level) as usize).unwrap(),
+            },
+            _ => TextureDimension::Texture2D,
+        };
+
+        let staging_texture = pool.staging_texture(
+            &TextureDesc::builder()
+                .name(STAGING_TEXTURE_NAME)
+                .dimension(dimension)
+                .width(texture_desc.mip_level_logical_width(mip_level))
+                .height(texture_desc.mip_level_logical_height(mip_level))
+                .format(
+                    texture_desc
+                        .format()
+                        .expect("the format of the texture must be known"),
+                )
+                .usage(Usage::Staging)
+                .cpu_access_flags(CpuAccessFlags::Read)
+                .build(),
+        )?;
+
+        let copy_attribs = diligent_sys::CopyTextureAttribs {
+            pSrcTexture: texture_ptr,
+            SrcMipLevel: mip_level,
+            SrcSlice: array_slice,
+            pSrcBox: std::ptr::null(),
+            SrcTextureTransitionMode: TextureTransition::TRANSITION_MODE,
+            pDstTexture: staging_texture.sys_ptr(),
+            DstMipLevel: 0,
+            DstSlice: 0,
+            DstX: 0,
+            DstY: 0,
+            DstZ: 0,
+            DstTextureTransitionMode: diligent_sys::RESOURCE_STATE_TRANSITION_MODE_TRANSITION as _,
+        };
+
+        let device_context: &DeviceContext = self;
+        unsafe_member_call!(device_context, DeviceContext, CopyTexture, &copy_attribs);
+
+        let fence_value = pool.next_fence_value();
+        self.enqueue_signal(&pool.fence, fence_value);
+
+        Ok(Readback {
+            device_context: self,
+            pool,
+            fence_value,
+            flushed: false,
+            fence_wait: None,
+            staging: Some(Staging::Texture {
+                texture: staging_texture,
+            }),
+        })
+    }
+}
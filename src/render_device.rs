@@ -1,14 +1,15 @@
 use std::{ffi::CStr, mem::MaybeUninit, os::raw::c_void};
 
 use crate::{
-    Boxed, BoxedFromNulError, Ported, ResourceMappingCreateInfo, SparseTextureFormatInfo,
-    TextureFormatInfoExt, TilePipelineStateCreateInfo,
+    Boxed, Error, Ported, ResourceMappingCreateInfo, SparseTextureFormatInfo, TextureFormatInfoExt,
+    TilePipelineStateCreateInfo,
     blas::{BottomLevelAS, BottomLevelASDesc},
     buffer::{Buffer, BufferDesc},
     data_blob::DataBlob,
     device_context::{DeferredDeviceContext, DeviceContext},
     device_memory::{DeviceMemory, DeviceMemoryCreateInfo},
     engine_factory::EngineFactory,
+    error::{MessageCapture, Operation, object_name},
     fence::{Fence, FenceDesc},
     frame_buffer::{Framebuffer, FramebufferDesc},
     graphics_types::{
@@ -31,6 +32,7 @@ use crate::{
     sampler::{Sampler, SamplerDesc},
     shader::{Shader, ShaderCreateInfo, ShaderCreateInfoWrapper},
     shader_binding_table::{ShaderBindingTable, ShaderBindingTableDesc},
+    shader_compile_error::{ShaderCompileError, ShaderCompilerOutput},
     texture::{Texture, TextureDesc, TextureDimension, TextureSubResource},
     tlas::{TopLevelAS, TopLevelASDesc},
 };
@@ -84,12 +86,10 @@ define_ported!(
 );
 
 impl RenderDevice {
-    pub fn create_buffer(
-        &self,
-        buffer_desc: &BufferDesc,
-    ) -> Result<Boxed<Buffer>, BoxedFromNulError> {
+    pub fn create_buffer(&self, buffer_desc: &BufferDesc) -> Result<Boxed<Buffer>, Error> {
         let mut buffer_ptr = std::ptr::null_mut();
 
+        let capture = MessageCapture::begin();
         unsafe_member_call!(
             self,
             RenderDevice,
@@ -99,7 +99,7 @@ impl RenderDevice {
             &mut buffer_ptr
         );
 
-        Boxed::new(buffer_ptr)
+        capture.boxed(buffer_ptr, Operation::CreateBuffer, buffer_desc.name())
     }
 
     pub fn create_buffer_with_data<T: ?Sized>(
@@ -107,7 +107,7 @@ impl RenderDevice {
         buffer_desc: &BufferDesc,
         buffer_data: &T,
         device_context: Option<&DeviceContext>,
-    ) -> Result<Boxed<Buffer>, BoxedFromNulError> {
+    ) -> Result<Boxed<Buffer>, Error> {
         let mut buffer_ptr = std::ptr::null_mut();
 
         let buffer_data = diligent_sys::BufferData {
@@ -116,6 +116,7 @@ impl RenderDevice {
             pContext: device_context.map_or(std::ptr::null_mut(), |context| context.sys_ptr()),
         };
 
+        let capture = MessageCapture::begin();
         unsafe_member_call!(
             self,
             RenderDevice,
@@ -125,18 +126,28 @@ impl RenderDevice {
             &mut buffer_ptr
         );
 
-        Boxed::new(buffer_ptr)
+        capture.boxed(buffer_ptr, Operation::CreateBuffer, buffer_desc.name())
     }
 
     pub fn create_shader(
         &self,
         shader_ci: &ShaderCreateInfo,
-    ) -> Result<Boxed<Shader>, Option<Boxed<DataBlob>>> {
+    ) -> Result<Boxed<Shader>, ShaderCompileError> {
+        self.create_shader_with_output(shader_ci)
+            .map(|(shader, _)| shader)
+    }
+
+    /// Same as `create_shader`, but also returns the output of a successful compilation, with its warnings.
+    pub fn create_shader_with_output(
+        &self,
+        shader_ci: &ShaderCreateInfo,
+    ) -> Result<(Boxed<Shader>, ShaderCompilerOutput), ShaderCompileError> {
         let mut shader_ptr = std::ptr::null_mut();
         let mut data_blob_ptr = std::ptr::null_mut();
 
         let shader_ci_wrapper = ShaderCreateInfoWrapper::from(shader_ci);
 
+        let capture = MessageCapture::begin();
         unsafe_member_call!(
             self,
             RenderDevice,
@@ -146,7 +157,18 @@ impl RenderDevice {
             &mut data_blob_ptr
         );
 
-        Boxed::new(shader_ptr).map_err(|_| Some(Boxed::new(data_blob_ptr).unwrap()))
+        let output = Boxed::<DataBlob>::new(data_blob_ptr)
+            .map(|data_blob| ShaderCompilerOutput::from_data_blob(&data_blob))
+            .unwrap_or_default();
+
+        match Boxed::new(shader_ptr) {
+            Some(shader) => Ok((shader, output)),
+            None => Err(ShaderCompileError::new(
+                shader_ci,
+                output,
+                capture.messages(),
+            )),
+        }
     }
 
     pub fn create_texture(
@@ -154,7 +176,7 @@ impl RenderDevice {
         texture_desc: &TextureDesc,
         subresources: &[TextureSubResource],
         device_context: Option<&DeviceContext>,
-    ) -> Result<Boxed<Texture>, BoxedFromNulError> {
+    ) -> Result<Boxed<Texture>, Error> {
         let mut texture_ptr = std::ptr::null_mut();
 
         let texture_data = diligent_sys::TextureData {
@@ -167,6 +189,7 @@ impl RenderDevice {
             pContext: device_context.map_or(std::ptr::null_mut(), |c| c.sys_ptr()),
         };
 
+        let capture = MessageCapture::begin();
         unsafe_member_call!(
             self,
             RenderDevice,
@@ -180,14 +203,12 @@ impl RenderDevice {
             &mut texture_ptr
         );
 
-        Boxed::new(texture_ptr)
+        capture.boxed(texture_ptr, Operation::CreateTexture, texture_desc.name())
     }
 
-    pub fn create_sampler(
-        &self,
-        sampler_desc: &SamplerDesc,
-    ) -> Result<Boxed<Sampler>, BoxedFromNulError> {
+    pub fn create_sampler(&self, sampler_desc: &SamplerDesc) -> Result<Boxed<Sampler>, Error> {
         let mut sampler_ptr = std::ptr::null_mut();
+        let capture = MessageCapture::begin();
         unsafe_member_call!(
             self,
             RenderDevice,
@@ -196,14 +217,15 @@ impl RenderDevice {
             &mut sampler_ptr
         );
 
-        Boxed::new(sampler_ptr)
+        capture.boxed(sampler_ptr, Operation::CreateSampler, sampler_desc.name())
     }
 
     pub fn create_resource_mapping(
         &self,
         resource_mapping_ci: &ResourceMappingCreateInfo,
-    ) -> Result<Boxed<ResourceMapping>, BoxedFromNulError> {
+    ) -> Result<Boxed<ResourceMapping>, Error> {
         let mut resource_mapping_ptr = std::ptr::null_mut();
+        let capture = MessageCapture::begin();
         unsafe_member_call!(
             self,
             RenderDevice,
@@ -212,15 +234,16 @@ impl RenderDevice {
             &mut resource_mapping_ptr
         );
 
-        Boxed::new(resource_mapping_ptr)
+        capture.boxed(resource_mapping_ptr, Operation::CreateResourceMapping, None)
     }
 
     pub fn create_graphics_pipeline_state(
         &self,
         pipeline_ci: &GraphicsPipelineStateCreateInfo,
-    ) -> Result<Boxed<GraphicsPipelineState>, BoxedFromNulError> {
+    ) -> Result<Boxed<GraphicsPipelineState>, Error> {
         let mut pipeline_state_ptr = std::ptr::null_mut();
 
+        let capture = MessageCapture::begin();
         unsafe_member_call!(
             self,
             RenderDevice,
@@ -229,15 +252,27 @@ impl RenderDevice {
             &mut pipeline_state_ptr
         );
 
-        Boxed::new(pipeline_state_ptr)
+        capture.boxed(
+            pipeline_state_ptr,
+            Operation::CreateGraphicsPipelineState,
+            object_name(
+                pipeline_ci
+                    .0
+                    ._PipelineStateCreateInfo
+                    .PSODesc
+                    ._DeviceObjectAttribs
+                    .Name,
+            ),
+        )
     }
 
     pub fn create_compute_pipeline_state(
         &self,
         pipeline_ci: &ComputePipelineStateCreateInfo,
-    ) -> Result<Boxed<ComputePipelineState>, BoxedFromNulError> {
+    ) -> Result<Boxed<ComputePipelineState>, Error> {
         let mut pipeline_state_ptr = std::ptr::null_mut();
 
+        let capture = MessageCapture::begin();
         unsafe_member_call!(
             self,
             RenderDevice,
@@ -246,15 +281,27 @@ impl RenderDevice {
             &mut pipeline_state_ptr
         );
 
-        Boxed::new(pipeline_state_ptr)
+        capture.boxed(
+            pipeline_state_ptr,
+            Operation::CreateComputePipelineState,
+            object_name(
+                pipeline_ci
+                    .0
+                    ._PipelineStateCreateInfo
+                    .PSODesc
+                    ._DeviceObjectAttribs
+                    .Name,
+            ),
+        )
     }
 
     pub fn create_ray_tracing_pipeline_state(
         &self,
         pipeline_ci: &RayTracingPipelineStateCreateInfo,
-    ) -> Result<Boxed<RayTracingPipelineState>, BoxedFromNulError> {
+    ) -> Result<Boxed<RayTracingPipelineState>, Error> {
         let mut pipeline_state_ptr = std::ptr::null_mut();
 
+        let capture = MessageCapture::begin();
         unsafe_member_call!(
             self,
             RenderDevice,
@@ -263,15 +310,27 @@ impl RenderDevice {
             &mut pipeline_state_ptr
         );
 
-        Boxed::new(pipeline_state_ptr)
+        capture.boxed(
+            pipeline_state_ptr,
+            Operation::CreateRayTracingPipelineState,
+            object_name(
+                pipeline_ci
+                    .0
+                    ._PipelineStateCreateInfo
+                    .PSODesc
+                    ._DeviceObjectAttribs
+                    .Name,
+            ),
+        )
     }
 
     pub fn create_tile_pipeline_state(
         &self,
         pipeline_ci: &TilePipelineStateCreateInfo,
-    ) -> Result<Boxed<TilePipelineState>, BoxedFromNulError> {
+    ) -> Result<Boxed<TilePipelineState>, Error> {
         let mut pipeline_state_ptr = std::ptr::null_mut();
 
+        let capture = MessageCapture::begin();
         unsafe_member_call!(
             self,
             RenderDevice,
@@ -280,11 +339,23 @@ impl RenderDevice {
             &mut pipeline_state_ptr
         );
 
-        Boxed::new(pipeline_state_ptr)
-    }
-
-    pub fn create_fence(&self, fence_desc: &FenceDesc) -> Result<Boxed<Fence>, BoxedFromNulError> {
+        capture.boxed(
+            pipeline_state_ptr,
+            Operation::CreateTilePipelineState,
+            object_name(
+                pipeline_ci
+                    .0
+                    ._PipelineStateCreateInfo
+                    .PSODesc
+                    ._DeviceObjectAttribs
+                    .Name,
+            ),
+        )
+    }
+
+    pub fn create_fence(&self, fence_desc: &FenceDesc) -> Result<Boxed<Fence>, Error> {
         let mut fence_ptr = std::ptr::null_mut();
+        let capture = MessageCapture::begin();
         unsafe_member_call!(
             self,
             RenderDevice,
@@ -293,13 +364,13 @@ impl RenderDevice {
             &mut fence_ptr
         );
 
-        Boxed::new(fence_ptr)
+        capture.boxed(fence_ptr, Operation::CreateFence, fence_desc.name())
     }
 
     fn create_query<QueryDataType: GetSysQueryType>(
         &self,
         name: Option<&CStr>,
-    ) -> Result<Boxed<Query<QueryDataType>>, BoxedFromNulError> {
+    ) -> Result<Boxed<Query<QueryDataType>>, Error> {
         let query_desc = diligent_sys::QueryDesc {
             _DeviceObjectAttribs: diligent_sys::DeviceObjectAttribs {
                 Name: name.map_or(std::ptr::null(), |name| name.as_ptr()),
@@ -309,51 +380,50 @@ impl RenderDevice {
 
         let mut query_ptr = std::ptr::null_mut();
 
+        let capture = MessageCapture::begin();
         unsafe_member_call!(self, RenderDevice, CreateQuery, &query_desc, &mut query_ptr);
 
-        Boxed::new(query_ptr)
+        capture.boxed(query_ptr, Operation::CreateQuery, name)
     }
 
     pub fn create_query_occlusion(
         &self,
         name: Option<&CStr>,
-    ) -> Result<Boxed<Query<QueryDataOcclusion>>, BoxedFromNulError> {
+    ) -> Result<Boxed<Query<QueryDataOcclusion>>, Error> {
         self.create_query(name)
     }
 
     pub fn create_query_binary_occlusion(
         &self,
         name: Option<&CStr>,
-    ) -> Result<Boxed<Query<QueryDataBinaryOcclusion>>, BoxedFromNulError> {
+    ) -> Result<Boxed<Query<QueryDataBinaryOcclusion>>, Error> {
         self.create_query(name)
     }
 
     pub fn create_query_timestamp(
         &self,
         name: Option<&CStr>,
-    ) -> Result<Boxed<Query<QueryDataTimestamp>>, BoxedFromNulError> {
+    ) -> Result<Boxed<Query<QueryDataTimestamp>>, Error> {
         self.create_query(name)
     }
 
     pub fn create_query_pipeline_statistics(
         &self,
         name: Option<&CStr>,
-    ) -> Result<Boxed<Query<QueryDataPipelineStatistics>>, BoxedFromNulError> {
+    ) -> Result<Boxed<Query<QueryDataPipelineStatistics>>, Error> {
         self.create_query(name)
     }
 
     pub fn create_query_duration(
         &self,
         name: Option<&CStr>,
-    ) -> Result<Boxed<Query<QueryDataDuration>>, BoxedFromNulError> {
+    ) -> Result<Boxed<Query<QueryDataDuration>>, Error> {
         self.create_query(name)
     }
 
-    pub fn create_render_pass(
-        &self,
-        desc: &RenderPassDesc,
-    ) -> Result<Boxed<RenderPass>, BoxedFromNulError> {
+    pub fn create_render_pass(&self, desc: &RenderPassDesc) -> Result<Boxed<RenderPass>, Error> {
         let mut render_pass_ptr = std::ptr::null_mut();
+        let capture = MessageCapture::begin();
         unsafe_member_call!(
             self,
             RenderDevice,
@@ -362,14 +432,12 @@ impl RenderDevice {
             &mut render_pass_ptr
         );
 
-        Boxed::new(render_pass_ptr)
+        capture.boxed(render_pass_ptr, Operation::CreateRenderPass, desc.name())
     }
 
-    pub fn create_framebuffer(
-        &self,
-        desc: &FramebufferDesc,
-    ) -> Result<Boxed<Framebuffer>, BoxedFromNulError> {
+    pub fn create_framebuffer(&self, desc: &FramebufferDesc) -> Result<Boxed<Framebuffer>, Error> {
         let mut frame_buffer_ptr = std::ptr::null_mut();
+        let capture = MessageCapture::begin();
         unsafe_member_call!(
             self,
             RenderDevice,
@@ -378,44 +446,42 @@ impl RenderDevice {
             &mut frame_buffer_ptr
         );
 
-        Boxed::new(frame_buffer_ptr)
+        capture.boxed(frame_buffer_ptr, Operation::CreateFramebuffer, desc.name())
     }
 
-    pub fn create_blas(
-        &self,
-        desc: &BottomLevelASDesc,
-    ) -> Result<Boxed<BottomLevelAS>, BoxedFromNulError> {
+    pub fn create_blas(&self, desc: &BottomLevelASDesc) -> Result<Boxed<BottomLevelAS>, Error> {
         let mut blas_ptr = std::ptr::null_mut();
+        let capture = MessageCapture::begin();
         unsafe_member_call!(self, RenderDevice, CreateBLAS, &desc.0, &mut blas_ptr);
 
-        Boxed::new(blas_ptr)
+        capture.boxed(blas_ptr, Operation::CreateBLAS, desc.name())
     }
 
-    pub fn create_tlas(
-        &self,
-        desc: &TopLevelASDesc,
-    ) -> Result<Boxed<TopLevelAS>, BoxedFromNulError> {
+    pub fn create_tlas(&self, desc: &TopLevelASDesc) -> Result<Boxed<TopLevelAS>, Error> {
         let mut tlas_ptr = std::ptr::null_mut();
+        let capture = MessageCapture::begin();
         unsafe_member_call!(self, RenderDevice, CreateTLAS, &desc.0, &mut tlas_ptr);
 
-        Boxed::new(tlas_ptr)
+        capture.boxed(tlas_ptr, Operation::CreateTLAS, desc.name())
     }
 
     pub fn create_sbt(
         &self,
         desc: &ShaderBindingTableDesc,
-    ) -> Result<Boxed<ShaderBindingTable>, BoxedFromNulError> {
+    ) -> Result<Boxed<ShaderBindingTable>, Error> {
         let mut sbt_ptr = std::ptr::null_mut();
+        let capture = MessageCapture::begin();
         unsafe_member_call!(self, RenderDevice, CreateSBT, &desc.0, &mut sbt_ptr);
 
-        Boxed::new(sbt_ptr)
+        capture.boxed(sbt_ptr, Operation::CreateSBT, desc.name())
     }
 
     pub fn create_pipeline_resource_signature(
         &self,
         desc: &PipelineResourceSignatureDesc,
-    ) -> Result<Boxed<PipelineResourceSignature>, BoxedFromNulError> {
+    ) -> Result<Boxed<PipelineResourceSignature>, Error> {
         let mut prs_ptr = std::ptr::null_mut();
+        let capture = MessageCapture::begin();
         unsafe_member_call!(
             self,
             RenderDevice,
@@ -424,14 +490,19 @@ impl RenderDevice {
             &mut prs_ptr
         );
 
-        Boxed::new(prs_ptr)
+        capture.boxed(
+            prs_ptr,
+            Operation::CreatePipelineResourceSignature,
+            desc.name(),
+        )
     }
 
     pub fn create_device_memory(
         &self,
         create_info: &DeviceMemoryCreateInfo,
-    ) -> Result<Boxed<DeviceMemory>, BoxedFromNulError> {
+    ) -> Result<Boxed<DeviceMemory>, Error> {
         let mut device_memory_ptr = std::ptr::null_mut();
+        let capture = MessageCapture::begin();
         unsafe_member_call!(
             self,
             RenderDevice,
@@ -440,14 +511,19 @@ impl RenderDevice {
             &mut device_memory_ptr
         );
 
-        Boxed::new(device_memory_ptr)
+        capture.boxed(
+            device_memory_ptr,
+            Operation::CreateDeviceMemory,
+            object_name(create_info.0.Desc._DeviceObjectAttribs.Name),
+        )
     }
 
     pub fn create_pipeline_state_cache<T>(
         &self,
         create_info: &PipelineStateCacheCreateInfo<T>,
-    ) -> Result<Boxed<PipelineStateCache>, BoxedFromNulError> {
+    ) -> Result<Boxed<PipelineStateCache>, Error> {
         let mut pso_cache_ptr = std::ptr::null_mut();
+        let capture = MessageCapture::begin();
         unsafe_member_call!(
             self,
             RenderDevice,
@@ -456,13 +532,16 @@ impl RenderDevice {
             &mut pso_cache_ptr
         );
 
-        Boxed::new(pso_cache_ptr)
+        capture.boxed(
+            pso_cache_ptr,
+            Operation::CreatePipelineStateCache,
+            create_info.name(),
+        )
     }
 
-    pub fn create_deferred_context(
-        &self,
-    ) -> Result<Boxed<DeferredDeviceContext>, BoxedFromNulError> {
+    pub fn create_deferred_context(&self) -> Result<Boxed<DeferredDeviceContext>, Error> {
         let mut deferred_context_ptr = std::ptr::null_mut();
+        let capture = MessageCapture::begin();
         unsafe_member_call!(
             self,
             RenderDevice,
@@ -470,7 +549,7 @@ impl RenderDevice {
             &mut deferred_context_ptr
         );
 
-        Boxed::new(deferred_context_ptr)
+        capture.boxed(deferred_context_ptr, Operation::CreateDeferredContext, None)
     }
 
     pub fn get_adapter_info(&self) -> &GraphicsAdapterInfo {
@@ -3,7 +3,10 @@ use std::{ffi::CStr, marker::PhantomData, ops::Deref};
 use bitflags::bitflags;
 use static_assertions::const_assert_eq;
 
-use crate::device_object::{DeviceObject, DeviceObjectAttribs};
+use crate::{
+    device_object::{DeviceObject, DeviceObjectAttribs},
+    object::Interface,
+};
 
 use super::{
     graphics_types::{FilterType, TextureAddressMode},
@@ -12,6 +15,7 @@ use super::{
 
 bitflags! {
     #[derive(Clone,Copy)]
+    #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
     pub struct SamplerFlags: diligent_sys::SAMPLER_FLAGS {
         const None                           = diligent_sys::SAMPLER_FLAG_NONE as diligent_sys::SAMPLER_FLAGS;
         const Subsampled                     = diligent_sys::SAMPLER_FLAG_SUBSAMPLED as diligent_sys::SAMPLER_FLAGS;
@@ -149,6 +153,15 @@ impl SamplerDesc<'_> {
 
 define_ported!(Sampler, diligent_sys::ISampler, DeviceObject);
 
+impl Interface for Sampler {
+    const IID: diligent_sys::INTERFACE_ID = diligent_sys::INTERFACE_ID {
+        Data1: 0x595a59bf,
+        Data2: 0xfa81,
+        Data3: 0x4855,
+        Data4: [0xbc, 0x5e, 0xc0, 0xe0, 0x48, 0x74, 0x5a, 0x95],
+    };
+}
+
 impl Sampler {
     pub fn desc(&self) -> &SamplerDesc<'_> {
         let desc_ptr = unsafe_member_call!(self, DeviceObject, GetDesc);
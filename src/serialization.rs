@@ -0,0 +1,746 @@
+//! Owned mirrors of the resource and pipeline descriptions that can be serialized with serde.
+//!
+//! The descriptions of the engine borrow their names from the caller, so they can not be
+//! deserialized directly. Each mirror here has the same name and fields as the description
+//! it stands for, is created from a reference to that description and converts back into it :
+//!
+//! ```ignore
+//! let saved = diligent::serialization::BufferDesc::from(&buffer_desc);
+//! let loaded: diligent::BufferDesc = (&saved).into();
+//! ```
+//!
+//! The fields that have a default value in the builders can be omitted in the serialized data.
+
+use std::ffi::CString;
+
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    BindFlags, BlendFactor, BlendOperation, BufferMode, ColorMask, ComparisonFunction,
+    CpuAccessFlags, CullMode, FillMode, FilterType, InputElementFrequency, LogicOperation,
+    MiscBufferFlags, MiscTextureFlags, SamplerFlags, StencilOperation, TextureAddressMode,
+    TextureComponentSwizzle, TextureDimension, TextureFormat, TextureViewFlags, TextureViewType,
+    UavAccessFlags, Usage, ValueType,
+};
+
+// The C strings are stored as regular strings in the serialized data
+mod cstring {
+    use std::ffi::CString;
+
+    use serde::{
+        Deserialize, Deserializer, Serialize, Serializer, de::Error as _, ser::Error as _,
+    };
+
+    pub fn serialize<S: Serializer>(value: &CString, serializer: S) -> Result<S::Ok, S::Error> {
+        value
+            .to_str()
+            .map_err(S::Error::custom)?
+            .serialize(serializer)
+    }
+
+    pub fn deserialize<'de, D: Deserializer<'de>>(deserializer: D) -> Result<CString, D::Error> {
+        CString::new(String::deserialize(deserializer)?).map_err(D::Error::custom)
+    }
+}
+
+mod optional_cstring {
+    use std::ffi::{CStr, CString};
+
+    use serde::{
+        Deserialize, Deserializer, Serialize, Serializer, de::Error as _, ser::Error as _,
+    };
+
+    pub fn serialize<S: Serializer>(
+        value: &Option<CString>,
+        serializer: S,
+    ) -> Result<S::Ok, S::Error> {
+        value
+            .as_deref()
+            .map(CStr::to_str)
+            .transpose()
+            .map_err(S::Error::custom)?
+            .serialize(serializer)
+    }
+
+    pub fn deserialize<'de, D: Deserializer<'de>>(
+        deserializer: D,
+    ) -> Result<Option<CString>, D::Error> {
+        Option::<String>::deserialize(deserializer)?
+            .map(CString::new)
+            .transpose()
+            .map_err(D::Error::custom)
+    }
+}
+
+fn default_immediate_context_mask() -> u64 {
+    1
+}
+
+fn default_one() -> u32 {
+    1
+}
+
+fn default_clear_depth() -> f32 {
+    1.0
+}
+
+#[derive(Clone, Serialize, Deserialize)]
+pub struct BufferDesc {
+    #[serde(
+        default,
+        skip_serializing_if = "Option::is_none",
+        with = "optional_cstring"
+    )]
+    pub name: Option<CString>,
+
+    pub size: u64,
+
+    #[serde(default)]
+    pub bind_flags: BindFlags,
+
+    #[serde(default)]
+    pub usage: Usage,
+
+    #[serde(default)]
+    pub cpu_access_flags: CpuAccessFlags,
+
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub mode: Option<BufferMode>,
+
+    #[serde(default)]
+    pub misc_flags: MiscBufferFlags,
+
+    #[serde(default)]
+    pub element_byte_stride: u32,
+
+    #[serde(default = "default_immediate_context_mask")]
+    pub immediate_context_mask: u64,
+}
+
+impl From<&crate::BufferDesc<'_>> for BufferDesc {
+    fn from(desc: &crate::BufferDesc<'_>) -> Self {
+        BufferDesc {
+            name: desc.name().map(ToOwned::to_owned),
+            size: desc.size(),
+            bind_flags: desc.bind_flags(),
+            usage: desc.usage(),
+            cpu_access_flags: desc.cpu_access_flags(),
+            mode: desc.mode(),
+            misc_flags: desc.misc_flags(),
+            element_byte_stride: desc.element_byte_stride(),
+            immediate_context_mask: desc.immediate_context_mask(),
+        }
+    }
+}
+
+impl<'name> From<&'name BufferDesc> for crate::BufferDesc<'name> {
+    fn from(desc: &'name BufferDesc) -> Self {
+        crate::BufferDesc::builder()
+            .maybe_name(desc.name.as_deref())
+            .size(desc.size)
+            .bind_flags(desc.bind_flags)
+            .usage(desc.usage)
+            .cpu_access_flags(desc.cpu_access_flags)
+            .maybe_mode(desc.mode)
+            .misc_flags(desc.misc_flags)
+            .element_byte_stride(desc.element_byte_stride)
+            .immediate_context_mask(desc.immediate_context_mask)
+            .build()
+    }
+}
+
+#[derive(Clone, Serialize, Deserialize)]
+pub struct TextureDesc {
+    #[serde(
+        default,
+        skip_serializing_if = "Option::is_none",
+        with = "optional_cstring"
+    )]
+    pub name: Option<CString>,
+
+    pub dimension: TextureDimension,
+
+    pub width: u32,
+
+    pub height: u32,
+
+    pub format: TextureFormat,
+
+    #[serde(default = "default_one")]
+    pub mip_levels: u32,
+
+    #[serde(default = "default_one")]
+    pub sample_count: u32,
+
+    #[serde(default)]
+    pub bind_flags: BindFlags,
+
+    #[serde(default)]
+    pub usage: Usage,
+
+    #[serde(default)]
+    pub cpu_access_flags: CpuAccessFlags,
+
+    #[serde(default)]
+    pub misc_flags: MiscTextureFlags,
+
+    #[serde(default)]
+    pub clear_color: [f32; 4],
+
+    #[serde(default = "default_clear_depth")]
+    pub clear_depth: f32,
+
+    #[serde(default)]
+    pub clear_stencil: u8,
+
+    #[serde(default = "default_immediate_context_mask")]
+    pub immediate_context_mask: u64,
+}
+
+impl From<&crate::TextureDesc<'_>> for TextureDesc {
+    /// # Panics
+    /// Panics if the format of the description is unknown, which the engine never accepts for a texture.
+    fn from(desc: &crate::TextureDesc<'_>) -> Self {
+        TextureDesc {
+            name: desc.name().map(ToOwned::to_owned),
+            dimension: desc.dimension(),
+            width: desc.width(),
+            height: desc.height(),
+            format: desc.format().unwrap(),
+            mip_levels: desc.mip_levels(),
+            sample_count: desc.sample_count(),
+            bind_flags: desc.bind_flags(),
+            usage: desc.usage(),
+            cpu_access_flags: desc.cpu_access_flags(),
+            misc_flags: desc.misc_flags(),
+            clear_color: *desc.clear_color(),
+            clear_depth: desc.clear_depth(),
+            clear_stencil: desc.clear_stencil(),
+            immediate_context_mask: desc.immediate_context_mask(),
+        }
+    }
+}
+
+impl<'name> From<&'name TextureDesc> for crate::TextureDesc<'name> {
+    fn from(desc: &'name TextureDesc) -> Self {
+        crate::TextureDesc::builder()
+            .maybe_name(desc.name.as_deref())
+            .dimension(desc.dimension)
+            .width(desc.width)
+            .height(desc.height)
+            .format(desc.format)
+            .mip_levels(desc.mip_levels)
+            .sample_count(desc.sample_count)
+            .bind_flags(desc.bind_flags)
+            .usage(desc.usage)
+            .cpu_access_flags(desc.cpu_access_flags)
+            .misc_flags(desc.misc_flags)
+            .clear_color(desc.clear_color)
+            .clear_depth(desc.clear_depth)
+            .clear_stencil(desc.clear_stencil)
+            .immediate_context_mask(desc.immediate_context_mask)
+            .build()
+    }
+}
+
+#[derive(Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct TextureComponentMapping {
+    pub r: TextureComponentSwizzle,
+    pub g: TextureComponentSwizzle,
+    pub b: TextureComponentSwizzle,
+    pub a: TextureComponentSwizzle,
+}
+
+impl Default for TextureComponentMapping {
+    fn default() -> Self {
+        (&crate::TextureComponentMapping::builder().build()).into()
+    }
+}
+
+impl From<&crate::TextureComponentMapping> for TextureComponentMapping {
+    fn from(mapping: &crate::TextureComponentMapping) -> Self {
+        TextureComponentMapping {
+            r: mapping.r(),
+            g: mapping.g(),
+            b: mapping.b(),
+            a: mapping.a(),
+        }
+    }
+}
+
+impl From<&TextureComponentMapping> for crate::TextureComponentMapping {
+    fn from(mapping: &TextureComponentMapping) -> Self {
+        crate::TextureComponentMapping::builder()
+            .r(mapping.r)
+            .g(mapping.g)
+            .b(mapping.b)
+            .a(mapping.a)
+            .build()
+    }
+}
+
+fn default_uav_access_flags() -> UavAccessFlags {
+    UavAccessFlags::Unspecified
+}
+
+fn default_texture_view_flags() -> TextureViewFlags {
+    TextureViewFlags::None
+}
+
+#[derive(Clone, Serialize, Deserialize)]
+pub struct TextureViewDesc {
+    #[serde(
+        default,
+        skip_serializing_if = "Option::is_none",
+        with = "optional_cstring"
+    )]
+    pub name: Option<CString>,
+
+    pub view_type: TextureViewType,
+
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub dimension: Option<TextureDimension>,
+
+    #[serde(default)]
+    pub num_array_or_depth_slices: usize,
+
+    #[serde(default)]
+    pub first_array_or_depth_slice: usize,
+
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub format: Option<TextureFormat>,
+
+    #[serde(default)]
+    pub most_detailed_mip: usize,
+
+    #[serde(default)]
+    pub num_mip_levels: usize,
+
+    #[serde(default = "default_uav_access_flags")]
+    pub access_flags: UavAccessFlags,
+
+    #[serde(default = "default_texture_view_flags")]
+    pub flags: TextureViewFlags,
+
+    #[serde(default)]
+    pub swizzle: TextureComponentMapping,
+}
+
+impl From<&crate::TextureViewDesc<'_>> for TextureViewDesc {
+    fn from(desc: &crate::TextureViewDesc<'_>) -> Self {
+        TextureViewDesc {
+            name: desc.name().map(ToOwned::to_owned),
+            view_type: desc.view_type(),
+            dimension: desc.dimension(),
+            num_array_or_depth_slices: desc.num_array_or_depth_slices(),
+            first_array_or_depth_slice: desc.first_array_or_depth_slice(),
+            format: desc.format(),
+            most_detailed_mip: desc.most_detailed_mip(),
+            num_mip_levels: desc.num_mip_levels(),
+            access_flags: desc.access_flags(),
+            flags: desc.flags(),
+            swizzle: desc.swizzle().into(),
+        }
+    }
+}
+
+impl<'name> From<&'name TextureViewDesc> for crate::TextureViewDesc<'name> {
+    fn from(desc: &'name TextureViewDesc) -> Self {
+        crate::TextureViewDesc::builder()
+            .maybe_name(desc.name.as_deref())
+            .view_type(desc.view_type)
+            .maybe_dimension(desc.dimension)
+            .num_array_or_depth_slices(desc.num_array_or_depth_slices)
+            .first_array_or_depth_slice(desc.first_array_or_depth_slice)
+            .maybe_format(desc.format)
+            .most_detailed_mip(desc.most_detailed_mip)
+            .num_mip_levels(desc.num_mip_levels)
+            .access_flags(desc.access_flags)
+            .flags(desc.flags)
+            .swizzle((&desc.swizzle).into())
+            .build()
+    }
+}
+
+#[derive(Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct SamplerDesc {
+    #[serde(skip_serializing_if = "Option::is_none", with = "optional_cstring")]
+    pub name: Option<CString>,
+    pub min_filter: FilterType,
+    pub mag_filter: FilterType,
+    pub mip_filter: FilterType,
+    pub address_u: TextureAddressMode,
+    pub address_v: TextureAddressMode,
+    pub address_w: TextureAddressMode,
+    pub flags: SamplerFlags,
+    pub unnormalized_coords: bool,
+    pub mip_lod_bias: f32,
+    pub max_anisotropy: u32,
+    pub comparison_func: ComparisonFunction,
+    pub border_color: [f32; 4],
+    pub min_lod: f32,
+    pub max_lod: f32,
+}
+
+impl Default for SamplerDesc {
+    fn default() -> Self {
+        (&crate::SamplerDesc::builder().build()).into()
+    }
+}
+
+impl From<&crate::SamplerDesc<'_>> for SamplerDesc {
+    fn from(desc: &crate::SamplerDesc<'_>) -> Self {
+        SamplerDesc {
+            name: desc.name().map(ToOwned::to_owned),
+            min_filter: desc.min_filter(),
+            mag_filter: desc.mag_filter(),
+            mip_filter: desc.mip_filter(),
+            address_u: desc.address_u(),
+            address_v: desc.address_v(),
+            address_w: desc.address_w(),
+            flags: desc.flags(),
+            unnormalized_coords: desc.unnormalized_coords(),
+            mip_lod_bias: desc.mip_lod_bias(),
+            max_anisotropy: desc.max_anisotropy(),
+            comparison_func: desc.comparison_func(),
+            border_color: *desc.border_color(),
+            min_lod: desc.min_lod(),
+            max_lod: desc.max_lod(),
+        }
+    }
+}
+
+impl<'name> From<&'name SamplerDesc> for crate::SamplerDesc<'name> {
+    fn from(desc: &'name SamplerDesc) -> Self {
+        crate::SamplerDesc::builder()
+            .maybe_name(desc.name.as_deref())
+            .min_filter(desc.min_filter)
+            .mag_filter(desc.mag_filter)
+            .mip_filter(desc.mip_filter)
+            .address_u(desc.address_u)
+            .address_v(desc.address_v)
+            .address_w(desc.address_w)
+            .flags(desc.flags)
+            .unnormalized_coords(desc.unnormalized_coords)
+            .mip_lod_bias(desc.mip_lod_bias)
+            .max_anisotropy(desc.max_anisotropy)
+            .comparison_func(desc.comparison_func)
+            .border_color(desc.border_color)
+            .min_lod(desc.min_lod)
+            .max_lod(desc.max_lod)
+            .build()
+    }
+}
+
+#[derive(Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct RenderTargetBlendDesc {
+    pub blend_enable: bool,
+    pub logic_operation_enable: bool,
+    pub src_blend: BlendFactor,
+    pub dest_blend: BlendFactor,
+    pub blend_op: BlendOperation,
+    pub src_blend_alpha: BlendFactor,
+    pub dest_blend_alpha: BlendFactor,
+    pub blend_op_alpha: BlendOperation,
+    pub logic_op: LogicOperation,
+    pub render_target_write_mask: ColorMask,
+}
+
+impl Default for RenderTargetBlendDesc {
+    fn default() -> Self {
+        (&crate::RenderTargetBlendDesc::default()).into()
+    }
+}
+
+impl From<&crate::RenderTargetBlendDesc> for RenderTargetBlendDesc {
+    fn from(desc: &crate::RenderTargetBlendDesc) -> Self {
+        RenderTargetBlendDesc {
+            blend_enable: desc.blend_enable(),
+            logic_operation_enable: desc.logic_operation_enable(),
+            src_blend: desc.src_blend(),
+            dest_blend: desc.dest_blend(),
+            blend_op: desc.blend_op(),
+            src_blend_alpha: desc.src_blend_alpha(),
+            dest_blend_alpha: desc.dest_blend_alpha(),
+            blend_op_alpha: desc.blend_op_alpha(),
+            logic_op: desc.logic_op(),
+            render_target_write_mask: desc.render_target_write_mask(),
+        }
+    }
+}
+
+impl From<&RenderTargetBlendDesc> for crate::RenderTargetBlendDesc {
+    fn from(desc: &RenderTargetBlendDesc) -> Self {
+        crate::RenderTargetBlendDesc::builder()
+            .blend_enable(desc.blend_enable)
+            .logic_operation_enable(desc.logic_operation_enable)
+            .src_blend(desc.src_blend)
+            .dest_blend(desc.dest_blend)
+            .blend_op(desc.blend_op)
+            .src_blend_alpha(desc.src_blend_alpha)
+            .dest_blend_alpha(desc.dest_blend_alpha)
+            .blend_op_alpha(desc.blend_op_alpha)
+            .logic_op(desc.logic_op)
+            .render_target_write_mask(desc.render_target_write_mask)
+            .build()
+    }
+}
+
+#[derive(Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct BlendStateDesc {
+    pub alpha_to_coverage_enable: bool,
+    pub independent_blend_enable: bool,
+    pub render_targets: [RenderTargetBlendDesc; diligent_sys::DILIGENT_MAX_RENDER_TARGETS as usize],
+}
+
+impl Default for BlendStateDesc {
+    fn default() -> Self {
+        (&crate::BlendStateDesc::builder().build()).into()
+    }
+}
+
+impl From<&crate::BlendStateDesc> for BlendStateDesc {
+    fn from(desc: &crate::BlendStateDesc) -> Self {
+        BlendStateDesc {
+            alpha_to_coverage_enable: desc.alpha_to_coverage_enable(),
+            independent_blend_enable: desc.independent_blend_enable(),
+            render_targets: desc.render_targets().each_ref().map(Into::into),
+        }
+    }
+}
+
+impl From<&BlendStateDesc> for crate::BlendStateDesc {
+    fn from(desc: &BlendStateDesc) -> Self {
+        crate::BlendStateDesc::builder()
+            .alpha_to_coverage_enable(desc.alpha_to_coverage_enable)
+            .independent_blend_enable(desc.independent_blend_enable)
+            .render_targets(desc.render_targets.each_ref().map(Into::into))
+            .build()
+    }
+}
+
+#[derive(Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct RasterizerStateDesc {
+    pub fill_mode: FillMode,
+    pub cull_mode: CullMode,
+    pub front_counter_clockwise: bool,
+    pub depth_clip_enable: bool,
+    pub scissor_enable: bool,
+    pub antialiased_line_enable: bool,
+    pub depth_bias: i32,
+    pub depth_bias_clamp: f32,
+    pub slope_scaled_depth_bias: f32,
+}
+
+impl Default for RasterizerStateDesc {
+    fn default() -> Self {
+        (&crate::RasterizerStateDesc::builder().build()).into()
+    }
+}
+
+impl From<&crate::RasterizerStateDesc> for RasterizerStateDesc {
+    fn from(desc: &crate::RasterizerStateDesc) -> Self {
+        RasterizerStateDesc {
+            fill_mode: desc.fill_mode(),
+            cull_mode: desc.cull_mode(),
+            front_counter_clockwise: desc.front_counter_clockwise(),
+            depth_clip_enable: desc.depth_clip_enable(),
+            scissor_enable: desc.scissor_enable(),
+            antialiased_line_enable: desc.antialiased_line_enable(),
+            depth_bias: desc.depth_bias(),
+            depth_bias_clamp: desc.depth_bias_clamp(),
+            slope_scaled_depth_bias: desc.slope_scaled_depth_bias(),
+        }
+    }
+}
+
+impl From<&RasterizerStateDesc> for crate::RasterizerStateDesc {
+    fn from(desc: &RasterizerStateDesc) -> Self {
+        crate::RasterizerStateDesc::builder()
+            .fill_mode(desc.fill_mode)
+            .cull_mode(desc.cull_mode)
+            .front_counter_clockwise(desc.front_counter_clockwise)
+            .depth_clip_enable(desc.depth_clip_enable)
+            .scissor_enable(desc.scissor_enable)
+            .antialiased_line_enable(desc.antialiased_line_enable)
+            .depth_bias(desc.depth_bias)
+            .depth_bias_clamp(desc.depth_bias_clamp)
+            .slope_scaled_depth_bias(desc.slope_scaled_depth_bias)
+            .build()
+    }
+}
+
+#[derive(Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct StencilOperationsDesc {
+    pub stencil_fail_op: StencilOperation,
+    pub stencil_depth_fail_op: StencilOperation,
+    pub stencil_pass_op: StencilOperation,
+    pub stencil_func: ComparisonFunction,
+}
+
+impl Default for StencilOperationsDesc {
+    fn default() -> Self {
+        (&crate::StencilOperationsDesc::builder().build()).into()
+    }
+}
+
+impl From<&crate::StencilOperationsDesc> for StencilOperationsDesc {
+    fn from(desc: &crate::StencilOperationsDesc) -> Self {
+        StencilOperationsDesc {
+            stencil_fail_op: desc.stencil_fail_op(),
+            stencil_depth_fail_op: desc.stencil_depth_fail_op(),
+            stencil_pass_op: desc.stencil_pass_op(),
+            stencil_func: desc.stencil_func(),
+        }
+    }
+}
+
+impl From<&StencilOperationsDesc> for crate::StencilOperationsDesc {
+    fn from(desc: &StencilOperationsDesc) -> Self {
+        crate::StencilOperationsDesc::builder()
+            .stencil_fail_op(desc.stencil_fail_op)
+            .stencil_depth_fail_op(desc.stencil_depth_fail_op)
+            .stencil_pass_op(desc.stencil_pass_op)
+            .stencil_func(desc.stencil_func)
+            .build()
+    }
+}
+
+#[derive(Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct DepthStencilStateDesc {
+    pub depth_enable: bool,
+    pub depth_write_enable: bool,
+    pub depth_func: ComparisonFunction,
+    pub stencil_enable: bool,
+    pub stencil_read_mask: u8,
+    pub stencil_write_mask: u8,
+    pub front_face: StencilOperationsDesc,
+    pub back_face: StencilOperationsDesc,
+}
+
+impl Default for DepthStencilStateDesc {
+    fn default() -> Self {
+        (&crate::DepthStencilStateDesc::builder().build()).into()
+    }
+}
+
+impl From<&crate::DepthStencilStateDesc> for DepthStencilStateDesc {
+    fn from(desc: &crate::DepthStencilStateDesc) -> Self {
+        DepthStencilStateDesc {
+            depth_enable: desc.depth_enable(),
+            depth_write_enable: desc.depth_write_enable(),
+            depth_func: desc.depth_func(),
+            stencil_enable: desc.stencil_enable(),
+            stencil_read_mask: desc.stencil_read_mask(),
+            stencil_write_mask: desc.stencil_write_mask(),
+            front_face: desc.front_face().into(),
+            back_face: desc.back_face().into(),
+        }
+    }
+}
+
+impl From<&DepthStencilStateDesc> for crate::DepthStencilStateDesc {
+    fn from(desc: &DepthStencilStateDesc) -> Self {
+        crate::DepthStencilStateDesc::builder()
+            .depth_enable(desc.depth_enable)
+            .depth_write_enable(desc.depth_write_enable)
+            .depth_func(desc.depth_func)
+            .stencil_enable(desc.stencil_enable)
+            .stencil_read_mask(desc.stencil_read_mask)
+            .stencil_write_mask(desc.stencil_write_mask)
+            .front_face((&desc.front_face).into())
+            .back_face((&desc.back_face).into())
+            .build()
+    }
+}
+
+fn default_is_normalized() -> bool {
+    true
+}
+
+fn default_hlsl_semantic() -> CString {
+    c"ATTRIB".to_owned()
+}
+
+fn default_relative_offset() -> u32 {
+    diligent_sys::LAYOUT_ELEMENT_AUTO_OFFSET
+}
+
+fn default_stride() -> u32 {
+    diligent_sys::LAYOUT_ELEMENT_AUTO_STRIDE
+}
+
+fn default_frequency() -> InputElementFrequency {
+    InputElementFrequency::PerVertex
+}
+
+#[derive(Clone, Serialize, Deserialize)]
+pub struct LayoutElement {
+    pub input_index: u32,
+
+    pub slot: u32,
+
+    pub num_components: u32,
+
+    pub value_type: ValueType,
+
+    #[serde(default = "default_is_normalized")]
+    pub is_normalized: bool,
+
+    #[serde(default = "default_hlsl_semantic", with = "cstring")]
+    pub hlsl_semantic: CString,
+
+    #[serde(default = "default_relative_offset")]
+    pub relative_offset: u32,
+
+    #[serde(default = "default_stride")]
+    pub stride: u32,
+
+    #[serde(default = "default_frequency")]
+    pub frequency: InputElementFrequency,
+
+    #[serde(default = "default_one")]
+    pub instance_data_step_rate: u32,
+}
+
+impl From<&crate::LayoutElement> for LayoutElement {
+    fn from(element: &crate::LayoutElement) -> Self {
+        LayoutElement {
+            input_index: element.input_index(),
+            slot: element.slot(),
+            num_components: element.num_components(),
+            value_type: element.value_type(),
+            is_normalized: element.is_normalized(),
+            hlsl_semantic: element.hlsl_semantic().to_owned(),
+            relative_offset: element.relative_offset(),
+            stride: element.stride(),
+            frequency: element.frequency(),
+            instance_data_step_rate: element.instance_data_step_rate(),
+        }
+    }
+}
+
+/// The converted element points to the semantic of `element` : `element` must outlive it.
+impl From<&LayoutElement> for crate::LayoutElement {
+    fn from(element: &LayoutElement) -> Self {
+        crate::LayoutElement::builder()
+            .input_index(element.input_index)
+            .slot(element.slot)
+            .num_components(element.num_components)
+            .value_type(element.value_type)
+            .is_normalized(element.is_normalized)
+            .hlsl_semantic(&element.hlsl_semantic)
+            .relative_offset(element.relative_offset)
+            .stride(element.stride)
+            .frequency(element.frequency)
+            .instance_data_step_rate(element.instance_data_step_rate)
+            .build()
+    }
+}
@@ -13,8 +13,9 @@ use bon::Builder;
 use static_assertions::const_assert_eq;
 
 use crate::{
-    PipelineResourceFlags, Ported,
+    Boxed, PipelineResourceFlags, Ported,
     device_object::DeviceObject,
+    file_stream::FileStream,
     graphics_types::{ShaderType, Version},
     object::Object,
 };
@@ -97,7 +98,7 @@ impl From<ShaderCompiler> for diligent_sys::SHADER_COMPILER {
 }
 const_assert_eq!(diligent_sys::SHADER_COMPILER_COUNT, 4);
 
-#[derive(Clone, Copy)]
+#[derive(Clone, Copy, Debug)]
 pub enum ShaderResourceType {
     ConstantBuffer,
     TextureSRV,
@@ -276,11 +277,11 @@ impl Default for ShaderCompileFlags {
 #[builder(derive(Clone))]
 pub struct ShaderCreateInfo<'a> {
     #[builder(with =|name : impl AsRef<str>| CString::new(name.as_ref()).unwrap())]
-    name: Option<CString>,
+    pub(crate) name: Option<CString>,
 
-    source: ShaderSource<'a>,
+    pub(crate) source: ShaderSource<'a>,
 
-    shader_type: ShaderType,
+    pub(crate) shader_type: ShaderType,
 
     #[builder(default = false)]
     use_combined_texture_samplers: bool,
@@ -293,7 +294,7 @@ pub struct ShaderCreateInfo<'a> {
 
     #[builder(with =|ep : impl AsRef<str>| ep.as_ref().to_owned())]
     #[builder(default = "main".to_owned())]
-    entry_point: String,
+    pub(crate) entry_point: String,
 
     #[builder(with =|macros: Vec<(impl Into<String>, impl Into<String>)>|
         macros
@@ -329,6 +330,35 @@ pub struct ShaderCreateInfo<'a> {
     web_gpu_emulated_array_index_suffix: Option<CString>,
 }
 
+impl ShaderCreateInfo<'_> {
+    /// Copies the create info, with another source and another stream factory.
+    pub(crate) fn with_source<'a>(
+        &self,
+        source: ShaderSource<'a>,
+        shader_source_input_stream_factory: Option<&'a ShaderSourceInputStreamFactory>,
+    ) -> ShaderCreateInfo<'a> {
+        ShaderCreateInfo {
+            name: self.name.clone(),
+            source,
+            shader_type: self.shader_type,
+            use_combined_texture_samplers: self.use_combined_texture_samplers,
+            combined_sampler_suffix: self.combined_sampler_suffix.clone(),
+            shader_source_input_stream_factory,
+            entry_point: self.entry_point.clone(),
+            macros: self.macros.clone(),
+            source_language: self.source_language,
+            compiler: self.compiler,
+            language_version: self.language_version,
+            compile_flags: self.compile_flags,
+            shader_optimization_level: self.shader_optimization_level,
+            load_constant_buffer_reflection: self.load_constant_buffer_reflection,
+            glsl_extensions: self.glsl_extensions.clone(),
+            #[cfg(feature = "webgpu")]
+            web_gpu_emulated_array_index_suffix: self.web_gpu_emulated_array_index_suffix.clone(),
+        }
+    }
+}
+
 pub(crate) struct ShaderCreateInfoWrapper {
     _macro_strings: Vec<(CString, CString)>,
     _macros: Vec<diligent_sys::ShaderMacro>,
@@ -759,8 +789,47 @@ define_ported!(
     Object
 );
 
+bitflags! {
+    #[derive(Clone,Copy)]
+    pub struct CreateShaderSourceInputStreamFlags : diligent_sys::CREATE_SHADER_SOURCE_INPUT_STREAM_FLAGS {
+        const None   = diligent_sys::CREATE_SHADER_SOURCE_INPUT_STREAM_FLAG_NONE as diligent_sys::CREATE_SHADER_SOURCE_INPUT_STREAM_FLAGS;
+        const Silent = diligent_sys::CREATE_SHADER_SOURCE_INPUT_STREAM_FLAG_SILENT as diligent_sys::CREATE_SHADER_SOURCE_INPUT_STREAM_FLAGS;
+    }
+}
+
+impl Default for CreateShaderSourceInputStreamFlags {
+    fn default() -> Self {
+        CreateShaderSourceInputStreamFlags::None
+    }
+}
+
 impl ShaderSourceInputStreamFactory {
-    //pub fn create_input_stream(&self, name : impl AsRef<str>, IFileStream** ppStream);
+    pub fn create_input_stream(&self, name: impl AsRef<CStr>) -> Option<Boxed<FileStream>> {
+        let mut stream_ptr = std::ptr::null_mut();
+        unsafe_member_call!(
+            self,
+            ShaderSourceInputStreamFactory,
+            CreateInputStream,
+            name.as_ref().as_ptr(),
+            &mut stream_ptr
+        );
+        Boxed::new(stream_ptr)
+    }
 
-    //pub fn create_input_stream2(&self, name : impl AsRef<str>, CREATE_SHADER_SOURCE_INPUT_STREAM_FLAGS Flags, IFileStream** ppStream);
+    pub fn create_input_stream2(
+        &self,
+        name: impl AsRef<CStr>,
+        flags: CreateShaderSourceInputStreamFlags,
+    ) -> Option<Boxed<FileStream>> {
+        let mut stream_ptr = std::ptr::null_mut();
+        unsafe_member_call!(
+            self,
+            ShaderSourceInputStreamFactory,
+            CreateInputStream2,
+            name.as_ref().as_ptr(),
+            flags.bits(),
+            &mut stream_ptr
+        );
+        Boxed::new(stream_ptr)
+    }
 }
@@ -0,0 +1,471 @@
+use std::fmt::Display;
+
+use crate::{
+    data_blob::DataBlob, error::EngineMessage, graphics_types::ShaderType, shader::ShaderCreateInfo,
+};
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum ShaderDiagnosticSeverity {
+    Note,
+    Warning,
+    Error,
+}
+
+impl Display for ShaderDiagnosticSeverity {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(match self {
+            ShaderDiagnosticSeverity::Note => "note",
+            ShaderDiagnosticSeverity::Warning => "warning",
+            ShaderDiagnosticSeverity::Error => "error",
+        })
+    }
+}
+
+/// A message of the shader compiler, with the location it refers to.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ShaderDiagnostic {
+    pub file: Option<String>,
+    pub line: Option<u32>,
+    pub column: Option<u32>,
+    pub severity: ShaderDiagnosticSeverity,
+    pub message: String,
+}
+
+impl Display for ShaderDiagnostic {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match (&self.file, self.line, self.column) {
+            (Some(file), Some(line), Some(column)) => write!(f, "{file}:{line}:{column}: ")?,
+            (Some(file), Some(line), None) => write!(f, "{file}:{line}: ")?,
+            (Some(file), None, _) => write!(f, "{file}: ")?,
+            (None, ..) => {}
+        }
+        write!(f, "{}: {}", self.severity, self.message)
+    }
+}
+
+impl ShaderDiagnostic {
+    /// Parses the diagnostics of a compiler log, in the formats of DXC, FXC and glslang.
+    ///
+    /// The lines that are not diagnostics, such as the source excerpts of DXC, are skipped.
+    pub fn parse_log(log: &str) -> Vec<ShaderDiagnostic> {
+        log.lines()
+            .filter_map(|line| {
+                let line = line.trim_end();
+                Self::parse_glslang(line)
+                    .or_else(|| Self::parse_fxc(line))
+                    .or_else(|| Self::parse_dxc(line))
+                    .or_else(|| Self::parse_without_location(line))
+            })
+            .collect()
+    }
+
+    /// `ERROR: file:12: message`, with an optional column after the line.
+    fn parse_glslang(line: &str) -> Option<ShaderDiagnostic> {
+        let (severity, rest) = [
+            ("ERROR: ", ShaderDiagnosticSeverity::Error),
+            ("WARNING: ", ShaderDiagnosticSeverity::Warning),
+            ("NOTE: ", ShaderDiagnosticSeverity::Note),
+        ]
+        .into_iter()
+        .find_map(|(prefix, severity)| Some((severity, line.strip_prefix(prefix)?)))?;
+
+        Some(match split_location(rest) {
+            Some((file, line, column, message)) => ShaderDiagnostic {
+                file: Some(file.to_owned()),
+                line: Some(line),
+                column,
+                severity,
+                message: message.trim().to_owned(),
+            },
+            // Summaries such as "1 compilation errors. No code generated."
+            None => ShaderDiagnostic {
+                file: None,
+                line: None,
+                column: None,
+                severity,
+                message: rest.trim().to_owned(),
+            },
+        })
+    }
+
+    /// `file(12,5): error X3004: message`, or `file(12,5-9)`, or `file(12)`.
+    fn parse_fxc(line: &str) -> Option<ShaderDiagnostic> {
+        let (location, rest) = line.split_once("): ")?;
+        let (file, position) = location.rsplit_once('(')?;
+
+        let (line_number, column) = match position.split_once(',') {
+            Some((line_number, columns)) => {
+                let first_column = columns.split_once('-').map_or(columns, |(first, _)| first);
+                (line_number.parse().ok()?, Some(first_column.parse().ok()?))
+            }
+            None => (position.parse().ok()?, None),
+        };
+
+        let (severity, message) = parse_severity(rest)?;
+
+        Some(ShaderDiagnostic {
+            file: (!file.is_empty()).then(|| file.to_owned()),
+            line: Some(line_number),
+            column,
+            severity,
+            message: message.trim_start().to_owned(),
+        })
+    }
+
+    /// `file:12:5: error: message`
+    fn parse_dxc(line: &str) -> Option<ShaderDiagnostic> {
+        let (file, line_number, column, rest) = split_location(line)?;
+        let (severity, message) = parse_severity(rest.trim_start())?;
+
+        Some(ShaderDiagnostic {
+            file: Some(file.to_owned()),
+            line: Some(line_number),
+            column,
+            severity,
+            message: message.trim_start_matches(':').trim().to_owned(),
+        })
+    }
+
+    /// `error X3501: message` or `error: message`, for the errors that do not refer to a line.
+    fn parse_without_location(line: &str) -> Option<ShaderDiagnostic> {
+        let (severity, message) = parse_severity(line)?;
+
+        // Only the FXC codes are accepted after a space, not any text that starts with "note "
+        let message = match message.strip_prefix(':') {
+            Some(message) => message,
+            None => {
+                let (code, _) = message.trim_start().split_once(':')?;
+                let is_code = code.strip_prefix('X').is_some_and(|number| {
+                    !number.is_empty() && number.bytes().all(|byte| byte.is_ascii_digit())
+                });
+                if !is_code {
+                    return None;
+                }
+                message
+            }
+        };
+
+        Some(ShaderDiagnostic {
+            file: None,
+            line: None,
+            column: None,
+            severity,
+            message: message.trim().to_owned(),
+        })
+    }
+}
+
+/// Splits `file:line[:column]:rest` at the first `:line:` that follows a non-empty file name,
+/// so that a drive letter is kept in the file name.
+fn split_location(text: &str) -> Option<(&str, u32, Option<u32>, &str)> {
+    text.match_indices(':').find_map(|(index, _)| {
+        if index == 0 {
+            return None;
+        }
+
+        let file = &text[..index];
+        let (line, rest) = text[index + 1..].split_once(':')?;
+        let line = line.parse().ok()?;
+
+        if let Some((column, message)) = rest.split_once(':')
+            && let Ok(column) = column.parse()
+        {
+            Some((file, line, Some(column), message))
+        } else {
+            Some((file, line, None, rest))
+        }
+    })
+}
+
+/// Splits `error: message`, `fatal error: message` or `warning X3206: message`.
+fn parse_severity(text: &str) -> Option<(ShaderDiagnosticSeverity, &str)> {
+    [
+        ("fatal error", ShaderDiagnosticSeverity::Error),
+        ("error", ShaderDiagnosticSeverity::Error),
+        ("warning", ShaderDiagnosticSeverity::Warning),
+        ("note", ShaderDiagnosticSeverity::Note),
+        ("remark", ShaderDiagnosticSeverity::Note),
+    ]
+    .into_iter()
+    .find_map(|(prefix, severity)| {
+        let rest = text.strip_prefix(prefix)?;
+        (rest.starts_with(':') || rest.starts_with(' ')).then_some((severity, rest))
+    })
+}
+
+/// What the shader compiler wrote : the log, its diagnostics, and the full source it compiled.
+#[derive(Clone, Debug, Default)]
+pub struct ShaderCompilerOutput {
+    pub log: String,
+    /// The source after the includes and the macros of `ShaderCreateInfo` were inserted.
+    pub source: Option<String>,
+    pub diagnostics: Vec<ShaderDiagnostic>,
+}
+
+impl ShaderCompilerOutput {
+    /// Reads the compiler output blob of the engine : the log, then the source, each terminated by a null character.
+    pub(crate) fn from_data_blob(data_blob: &DataBlob) -> Self {
+        let mut strings = data_blob
+            .as_bytes()
+            .split(|&byte| byte == 0)
+            .map(|string| String::from_utf8_lossy(string).into_owned());
+
+        let log = strings.next().unwrap_or_default();
+        let source = strings.next().filter(|source| !source.is_empty());
+        let diagnostics = ShaderDiagnostic::parse_log(&log);
+
+        ShaderCompilerOutput {
+            log,
+            source,
+            diagnostics,
+        }
+    }
+
+    pub fn errors(&self) -> impl Iterator<Item = &ShaderDiagnostic> {
+        self.diagnostics
+            .iter()
+            .filter(|diagnostic| diagnostic.severity == ShaderDiagnosticSeverity::Error)
+    }
+
+    pub fn warnings(&self) -> impl Iterator<Item = &ShaderDiagnostic> {
+        self.diagnostics
+            .iter()
+            .filter(|diagnostic| diagnostic.severity == ShaderDiagnosticSeverity::Warning)
+    }
+}
+
+/// The failure of `RenderDevice::create_shader`.
+#[derive(Clone, Debug)]
+pub struct ShaderCompileError {
+    pub name: Option<String>,
+    pub shader_type: ShaderType,
+    pub entry_point: String,
+    /// Empty when the engine failed before running the compiler, for example when the source file was not found.
+    pub output: ShaderCompilerOutput,
+    /// The messages the engine reported on the calling thread while the shader was created.
+    pub messages: Vec<EngineMessage>,
+}
+
+impl ShaderCompileError {
+    pub(crate) fn new(
+        shader_ci: &ShaderCreateInfo,
+        output: ShaderCompilerOutput,
+        messages: Vec<EngineMessage>,
+    ) -> Self {
+        ShaderCompileError {
+            name: shader_ci
+                .name
+                .as_ref()
+                .map(|name| name.to_string_lossy().into_owned()),
+            shader_type: shader_ci.shader_type,
+            entry_point: shader_ci.entry_point.clone(),
+            output,
+            messages,
+        }
+    }
+
+    pub fn log(&self) -> &str {
+        &self.output.log
+    }
+
+    pub fn diagnostics(&self) -> &[ShaderDiagnostic] {
+        &self.output.diagnostics
+    }
+}
+
+impl std::error::Error for ShaderCompileError {}
+
+impl Display for ShaderCompileError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "failed to compile the {:?} shader", self.shader_type)?;
+
+        if let Some(name) = &self.name {
+            write!(f, " '{name}'")?;
+        }
+
+        write!(f, " with entry point '{}'", self.entry_point)?;
+
+        let mut errors = self.output.errors().peekable();
+        if errors.peek().is_some() {
+            return errors.try_for_each(|error| write!(f, "\n    {error}"));
+        }
+
+        if !self.output.log.is_empty() {
+            return self
+                .output
+                .log
+                .lines()
+                .try_for_each(|line| write!(f, "\n    {line}"));
+        }
+
+        self.messages
+            .iter()
+            .try_for_each(|message| write!(f, "\n    {message}"))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{ShaderDiagnostic, ShaderDiagnosticSeverity};
+
+    fn diagnostic(
+        file: Option<&str>,
+        line: Option<u32>,
+        column: Option<u32>,
+        severity: ShaderDiagnosticSeverity,
+        message: &str,
+    ) -> ShaderDiagnostic {
+        ShaderDiagnostic {
+            file: file.map(str::to_owned),
+            line,
+            column,
+            severity,
+            message: message.to_owned(),
+        }
+    }
+
+    #[test]
+    fn dxc_log() {
+        let log = "\
+cube.hlsl:12:16: error: use of undeclared identifier 'Color'
+    return float4(Color, 1.0);
+                  ^
+cube.hlsl:20:12: warning: implicit truncation of vector type [-Wconversion]
+C:\\Shaders\\common.hlsli:3:10: fatal error: 'lighting.hlsli' file not found
+cube.hlsl:8:7: note: previous definition is here
+";
+
+        assert_eq!(
+            ShaderDiagnostic::parse_log(log),
+            [
+                diagnostic(
+                    Some("cube.hlsl"),
+                    Some(12),
+                    Some(16),
+                    ShaderDiagnosticSeverity::Error,
+                    "use of undeclared identifier 'Color'"
+                ),
+                diagnostic(
+                    Some("cube.hlsl"),
+                    Some(20),
+                    Some(12),
+                    ShaderDiagnosticSeverity::Warning,
+                    "implicit truncation of vector type [-Wconversion]"
+                ),
+                diagnostic(
+                    Some("C:\\Shaders\\common.hlsli"),
+                    Some(3),
+                    Some(10),
+                    ShaderDiagnosticSeverity::Error,
+                    "'lighting.hlsli' file not found"
+                ),
+                diagnostic(
+                    Some("cube.hlsl"),
+                    Some(8),
+                    Some(7),
+                    ShaderDiagnosticSeverity::Note,
+                    "previous definition is here"
+                ),
+            ]
+        );
+    }
+
+    #[test]
+    fn fxc_log() {
+        let log = "\
+C:\\Projects\\Shaders\\cube.hlsl(12,5-9): error X3004: undeclared identifier 'Color'
+cube.hlsl(20,12): warning X3206: implicit truncation of vector type
+cube.hlsl(31): error X3000: syntax error: unexpected token '}'
+error X3501: 'main': entrypoint not found
+";
+
+        assert_eq!(
+            ShaderDiagnostic::parse_log(log),
+            [
+                diagnostic(
+                    Some("C:\\Projects\\Shaders\\cube.hlsl"),
+                    Some(12),
+                    Some(5),
+                    ShaderDiagnosticSeverity::Error,
+                    "X3004: undeclared identifier 'Color'"
+                ),
+                diagnostic(
+                    Some("cube.hlsl"),
+                    Some(20),
+                    Some(12),
+                    ShaderDiagnosticSeverity::Warning,
+                    "X3206: implicit truncation of vector type"
+                ),
+                diagnostic(
+                    Some("cube.hlsl"),
+                    Some(31),
+                    None,
+                    ShaderDiagnosticSeverity::Error,
+                    "X3000: syntax error: unexpected token '}'"
+                ),
+                diagnostic(
+                    None,
+                    None,
+                    None,
+                    ShaderDiagnosticSeverity::Error,
+                    "X3501: 'main': entrypoint not found"
+                ),
+            ]
+        );
+    }
+
+    #[test]
+    fn glslang_log() {
+        let log = "\
+ERROR: 0:12: 'Color' : undeclared identifier
+WARNING: 0:20: 'texture2D' : deprecated, use texture instead
+ERROR: C:\\Shaders\\cube.psh:7: '' : missing #endif
+ERROR: 2 compilation errors.  No code generated.
+";
+
+        assert_eq!(
+            ShaderDiagnostic::parse_log(log),
+            [
+                diagnostic(
+                    Some("0"),
+                    Some(12),
+                    None,
+                    ShaderDiagnosticSeverity::Error,
+                    "'Color' : undeclared identifier"
+                ),
+                diagnostic(
+                    Some("0"),
+                    Some(20),
+                    None,
+                    ShaderDiagnosticSeverity::Warning,
+                    "'texture2D' : deprecated, use texture instead"
+                ),
+                diagnostic(
+                    Some("C:\\Shaders\\cube.psh"),
+                    Some(7),
+                    None,
+                    ShaderDiagnosticSeverity::Error,
+                    "'' : missing #endif"
+                ),
+                diagnostic(
+                    None,
+                    None,
+                    None,
+                    ShaderDiagnosticSeverity::Error,
+                    "2 compilation errors.  No code generated."
+                ),
+            ]
+        );
+    }
+
+    #[test]
+    fn text_that_is_not_a_diagnostic() {
+        let log = "\
+note that this line is not a diagnostic
+warning is not followed by a colon here
+compilation failed
+";
+
+        assert_eq!(ShaderDiagnostic::parse_log(log), []);
+    }
+}
@@ -0,0 +1,403 @@
+use std::{
+    any::Any,
+    collections::{BTreeSet, HashSet},
+    fmt::Display,
+    marker::PhantomData,
+    path::{Path, PathBuf},
+    sync::{
+        Arc,
+        mpsc::{self, Receiver},
+    },
+};
+
+use notify::{EventKind, RecommendedWatcher, RecursiveMode, Watcher};
+
+use crate::{
+    Boxed, Error, Ported,
+    render_device::RenderDevice,
+    shader::{Shader, ShaderCreateInfo, ShaderSource, ShaderSourceInputStreamFactory},
+    shader_compile_error::ShaderCompileError,
+    shader_source::TrackingShaderSource,
+};
+
+/// A shader created by a `ShaderLibrary`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct ShaderHandle(usize);
+
+/// A pipeline state created by a `ShaderLibrary`.
+pub struct PipelineHandle<P> {
+    index: usize,
+    phantom: PhantomData<fn() -> P>,
+}
+
+impl<P> Clone for PipelineHandle<P> {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<P> Copy for PipelineHandle<P> {}
+
+#[derive(Debug)]
+pub enum ShaderLibraryError {
+    /// The source of a shader given to `ShaderLibrary::create_shader` is not a file path.
+    NotAFile { name: Option<String> },
+    /// A shader failed to compile : the library keeps the previous version, if any, and keeps
+    /// watching its files.
+    Compile {
+        shader: ShaderHandle,
+        error: ShaderCompileError,
+    },
+    /// A pipeline state uses a shader that has never compiled.
+    NotCompiled(ShaderHandle),
+    /// A pipeline state could not be rebuilt with the modified shaders : the library keeps the previous version.
+    Pipeline(Error),
+    /// The directory of a shader file could not be watched, or the watcher failed.
+    Watch(notify::Error),
+}
+
+impl std::error::Error for ShaderLibraryError {}
+
+impl Display for ShaderLibraryError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ShaderLibraryError::NotAFile { name: Some(name) } => write!(
+                f,
+                "the shader '{name}' of a shader library must be loaded from a file path"
+            ),
+            ShaderLibraryError::NotAFile { name: None } => {
+                f.write_str("the shaders of a shader library must be loaded from a file path")
+            }
+            ShaderLibraryError::Compile { error, .. } => error.fmt(f),
+            ShaderLibraryError::NotCompiled(_) => {
+                f.write_str("a shader of the pipeline state has never compiled")
+            }
+            ShaderLibraryError::Pipeline(error) => error.fmt(f),
+            ShaderLibraryError::Watch(error) => {
+                write!(f, "failed to watch the shader files: {error}")
+            }
+        }
+    }
+}
+
+/// What a call to `ShaderLibrary::update` has reloaded.
+///
+/// The shader resource bindings of a rebuilt pipeline state still refer to the previous version :
+/// they must be created again with `create_shader_resource_binding`, and their resources bound again.
+#[derive(Debug, Default)]
+pub struct ShaderLibraryUpdate {
+    pub reloaded_shaders: Vec<ShaderHandle>,
+    rebuilt_pipelines: Vec<usize>,
+    pub errors: Vec<ShaderLibraryError>,
+}
+
+impl ShaderLibraryUpdate {
+    pub fn is_empty(&self) -> bool {
+        self.reloaded_shaders.is_empty() && self.errors.is_empty()
+    }
+
+    pub fn is_rebuilt<P>(&self, pipeline: PipelineHandle<P>) -> bool {
+        self.rebuilt_pipelines.contains(&pipeline.index)
+    }
+}
+
+struct LibraryShader {
+    /// The create info of the shader, without its source and its stream factory.
+    shader_ci: ShaderCreateInfo<'static>,
+    path: PathBuf,
+    /// `None` until the first compilation succeeds.
+    shader: Option<Boxed<Shader>>,
+    dependencies: BTreeSet<PathBuf>,
+}
+
+type CreatePipelineState = dyn Fn(&RenderDevice, &[&Shader]) -> Result<Box<dyn Any>, Error>;
+
+struct LibraryPipeline {
+    shaders: Vec<ShaderHandle>,
+    create: Box<CreatePipelineState>,
+    /// The `Boxed` pipeline state, of the type of its handle.
+    pipeline_state: Box<dyn Any>,
+}
+
+/// Loads shaders from files, and reloads them when these files or their `#include` files change.
+///
+/// The pipeline states created through the library from its shaders are rebuilt when one of
+/// their shaders is reloaded. The files are watched with `notify` (inotify on Linux), but nothing
+/// is reloaded until `update` is called, usually once per frame on the render thread.
+pub struct ShaderLibrary {
+    render_device: Boxed<RenderDevice>,
+    search_paths: Arc<[PathBuf]>,
+    shaders: Vec<LibraryShader>,
+    pipelines: Vec<LibraryPipeline>,
+    watcher: RecommendedWatcher,
+    events: Receiver<notify::Result<notify::Event>>,
+    watched_directories: HashSet<PathBuf>,
+    pending_errors: Vec<ShaderLibraryError>,
+}
+
+impl ShaderLibrary {
+    /// Creates a library that searches the shader files, and their `#include` files, in `search_paths`.
+    pub fn new(
+        render_device: &RenderDevice,
+        search_paths: impl IntoIterator<Item = impl AsRef<Path>>,
+    ) -> Result<Self, notify::Error> {
+        let (sender, events) = mpsc::channel();
+        let watcher = notify::recommended_watcher(sender)?;
+
+        Ok(ShaderLibrary {
+            render_device: Boxed::from_ref(render_device),
+            search_paths: search_paths
+                .into_iter()
+                .map(|path| path.as_ref().to_path_buf())
+                .collect(),
+            shaders: Vec::new(),
+            pipelines: Vec::new(),
+            watcher,
+            events,
+            watched_directories: HashSet::new(),
+            pending_errors: Vec::new(),
+        })
+    }
+
+    /// Compiles the file of `shader_ci`, searched in the search paths of the library.
+    ///
+    /// The stream factory of `shader_ci` is replaced by the one of the library, which records the included files.
+    ///
+    /// A shader that fails to compile is still added to the library, and its files are watched :
+    /// the error holds its handle, and `update` reports it as reloaded once its files are fixed.
+    /// Fails without adding the shader if its source is not a `ShaderSource::FilePath`.
+    pub fn create_shader(
+        &mut self,
+        shader_ci: &ShaderCreateInfo,
+    ) -> Result<ShaderHandle, ShaderLibraryError> {
+        let ShaderSource::FilePath(path) = shader_ci.source else {
+            return Err(ShaderLibraryError::NotAFile {
+                name: shader_ci
+                    .name
+                    .as_ref()
+                    .map(|name| name.to_string_lossy().into_owned()),
+            });
+        };
+
+        let shader_ci = shader_ci.with_source(ShaderSource::SourceCode(""), None);
+        let path = path.to_path_buf();
+
+        let (shader, dependencies) = self.compile(&shader_ci, &path);
+        let (shader, error) = match shader {
+            Ok(shader) => (Some(shader), None),
+            Err(error) => (None, Some(error)),
+        };
+
+        let handle = ShaderHandle(self.shaders.len());
+        self.shaders.push(LibraryShader {
+            shader_ci,
+            path,
+            shader,
+            dependencies,
+        });
+
+        self.watch_dependencies(handle);
+
+        match error {
+            Some(error) => Err(ShaderLibraryError::Compile {
+                shader: handle,
+                error,
+            }),
+            None => Ok(handle),
+        }
+    }
+
+    /// Creates a pipeline state from shaders of the library with `create`, which receives them in the order of `shaders`.
+    ///
+    /// `create` is called again to rebuild the pipeline state when one of these shaders is reloaded.
+    /// It should also initialize the static variables of the pipeline state.
+    pub fn create_pipeline_state<P: Ported + 'static>(
+        &mut self,
+        shaders: &[ShaderHandle],
+        create: impl Fn(&RenderDevice, &[&Shader]) -> Result<Boxed<P>, Error> + 'static,
+    ) -> Result<PipelineHandle<P>, ShaderLibraryError> {
+        let create: Box<CreatePipelineState> = Box::new(move |render_device, shaders| {
+            create(render_device, shaders)
+                .map(|pipeline_state| Box::new(pipeline_state) as Box<dyn Any>)
+        });
+
+        let pipeline_state = create(&*self.render_device, &self.shader_refs(shaders)?[..])
+            .map_err(ShaderLibraryError::Pipeline)?;
+
+        let index = self.pipelines.len();
+        self.pipelines.push(LibraryPipeline {
+            shaders: shaders.to_vec(),
+            create,
+            pipeline_state,
+        });
+
+        Ok(PipelineHandle {
+            index,
+            phantom: PhantomData,
+        })
+    }
+
+    /// The last version of `shader` that compiled, or `None` if it has never compiled.
+    pub fn shader(&self, shader: ShaderHandle) -> Option<&Shader> {
+        self.shaders[shader.0].shader.as_deref()
+    }
+
+    pub fn pipeline_state<P: Ported + 'static>(&self, pipeline: PipelineHandle<P>) -> &P {
+        self.pipelines[pipeline.index]
+            .pipeline_state
+            .downcast_ref::<Boxed<P>>()
+            .unwrap()
+    }
+
+    /// The files loaded by the last compilation of `shader` : its own file, then the files it includes.
+    pub fn dependencies(&self, shader: ShaderHandle) -> Vec<PathBuf> {
+        self.shaders[shader.0]
+            .dependencies
+            .iter()
+            .cloned()
+            .collect()
+    }
+
+    /// Reloads the shaders whose files changed since the last update, and rebuilds their pipeline states.
+    pub fn update(&mut self) -> ShaderLibraryUpdate {
+        let mut changed_files = HashSet::new();
+        let mut errors = std::mem::take(&mut self.pending_errors);
+
+        self.events.try_iter().for_each(|event| match event {
+            Ok(event) if !matches!(event.kind, EventKind::Access(_)) => {
+                changed_files.extend(event.paths)
+            }
+            Ok(_) => {}
+            Err(error) => errors.push(ShaderLibraryError::Watch(error)),
+        });
+
+        let changed_shaders: Vec<_> = self
+            .shaders
+            .iter()
+            .enumerate()
+            .filter(|(_, shader)| {
+                shader
+                    .dependencies
+                    .iter()
+                    .any(|dependency| changed_files.contains(dependency))
+            })
+            .map(|(index, _)| ShaderHandle(index))
+            .collect();
+
+        let mut update = self.reload_shaders(changed_shaders);
+        errors.append(&mut update.errors);
+        update.errors = errors;
+        update
+    }
+
+    /// Reloads `shaders` whether their files changed or not, and rebuilds their pipeline states.
+    pub fn reload_shaders(
+        &mut self,
+        shaders: impl IntoIterator<Item = ShaderHandle>,
+    ) -> ShaderLibraryUpdate {
+        let mut update = ShaderLibraryUpdate::default();
+
+        for handle in shaders {
+            let library_shader = &self.shaders[handle.0];
+            let (shader, dependencies) =
+                self.compile(&library_shader.shader_ci, &library_shader.path);
+
+            let library_shader = &mut self.shaders[handle.0];
+            match shader {
+                Ok(shader) => {
+                    library_shader.shader = Some(shader);
+                    library_shader.dependencies = dependencies;
+                    update.reloaded_shaders.push(handle);
+                }
+                Err(error) => {
+                    // Keep watching the files the failed compilation has loaded, so that fixing them triggers a reload
+                    library_shader.dependencies.extend(dependencies);
+                    update.errors.push(ShaderLibraryError::Compile {
+                        shader: handle,
+                        error,
+                    });
+                }
+            }
+
+            self.watch_dependencies(handle);
+        }
+
+        for (index, pipeline) in self.pipelines.iter_mut().enumerate() {
+            if !pipeline
+                .shaders
+                .iter()
+                .any(|shader| update.reloaded_shaders.contains(shader))
+            {
+                continue;
+            }
+
+            let shaders: Option<Vec<_>> = pipeline
+                .shaders
+                .iter()
+                .map(|shader| self.shaders[shader.0].shader.as_deref())
+                .collect();
+
+            // The pipeline was created once, so all its shaders have compiled at least once
+            let Some(shaders) = shaders else {
+                continue;
+            };
+
+            match (pipeline.create)(&*self.render_device, &shaders[..]) {
+                Ok(pipeline_state) => {
+                    pipeline.pipeline_state = pipeline_state;
+                    update.rebuilt_pipelines.push(index);
+                }
+                Err(error) => update.errors.push(ShaderLibraryError::Pipeline(error)),
+            }
+        }
+
+        update.errors.append(&mut self.pending_errors);
+        update
+    }
+
+    fn shader_refs(&self, shaders: &[ShaderHandle]) -> Result<Vec<&Shader>, ShaderLibraryError> {
+        shaders
+            .iter()
+            .map(|&shader| {
+                self.shader(shader)
+                    .ok_or(ShaderLibraryError::NotCompiled(shader))
+            })
+            .collect()
+    }
+
+    fn compile(
+        &self,
+        shader_ci: &ShaderCreateInfo,
+        path: &Path,
+    ) -> (Result<Boxed<Shader>, ShaderCompileError>, BTreeSet<PathBuf>) {
+        let source = TrackingShaderSource::new(self.search_paths.clone());
+        let factory = ShaderSourceInputStreamFactory::from_source(source.clone());
+
+        let shader = self
+            .render_device
+            .create_shader(&shader_ci.with_source(ShaderSource::FilePath(path), Some(&*factory)));
+
+        (shader, source.dependencies())
+    }
+
+    /// Watches the directories of the files of `shader`, rather than the files themselves,
+    /// so that the files replaced by the editors that save to a new file are still noticed.
+    fn watch_dependencies(&mut self, shader: ShaderHandle) {
+        let directories: BTreeSet<_> = self.shaders[shader.0]
+            .dependencies
+            .iter()
+            .filter_map(|dependency| dependency.parent())
+            .filter(|directory| !self.watched_directories.contains(*directory))
+            .map(Path::to_path_buf)
+            .collect();
+
+        for directory in directories {
+            match self.watcher.watch(&directory, RecursiveMode::NonRecursive) {
+                Ok(()) => {
+                    self.watched_directories.insert(directory);
+                }
+                Err(error) => self.pending_errors.push(ShaderLibraryError::Watch(error)),
+            }
+        }
+    }
+}
@@ -0,0 +1,294 @@
+use std::fmt::Display;
+
+use crate::{
+    buffer::Buffer,
+    buffer_view::{BufferView, BufferViewType},
+    device_object::DeviceObject,
+    graphics_types::{SetShaderResourceFlags, ShaderType, ShaderTypes},
+    pipeline_state::PipelineState,
+    sampler::Sampler,
+    shader::ShaderResourceType,
+    shader_resource_binding::ShaderResourceBinding,
+    shader_resource_variable::{ShaderResourceVariable, ShaderResourceVariableDesc},
+    texture_view::{TextureView, TextureViewType},
+    tlas::TopLevelAS,
+};
+
+/// An object that can be bound to a shader resource variable.
+pub trait ShaderResource {
+    /// Whether the object can be bound to a variable of type `resource_type`.
+    fn is_compatible(&self, resource_type: ShaderResourceType) -> bool;
+
+    fn device_object(&self) -> &DeviceObject;
+}
+
+impl ShaderResource for TextureView {
+    fn is_compatible(&self, resource_type: ShaderResourceType) -> bool {
+        match self.desc().view_type() {
+            TextureViewType::ShaderResource => matches!(
+                resource_type,
+                ShaderResourceType::TextureSRV | ShaderResourceType::InputAttachment
+            ),
+            TextureViewType::UnorderedAccess => {
+                matches!(resource_type, ShaderResourceType::TextureUAV)
+            }
+            _ => false,
+        }
+    }
+
+    fn device_object(&self) -> &DeviceObject {
+        self
+    }
+}
+
+impl ShaderResource for BufferView {
+    fn is_compatible(&self, resource_type: ShaderResourceType) -> bool {
+        match self.desc().view_type() {
+            BufferViewType::ShaderResource => {
+                matches!(resource_type, ShaderResourceType::BufferSRV)
+            }
+            BufferViewType::UnorderedAccess => {
+                matches!(resource_type, ShaderResourceType::BufferUAV)
+            }
+        }
+    }
+
+    fn device_object(&self) -> &DeviceObject {
+        self
+    }
+}
+
+impl ShaderResource for Buffer {
+    fn is_compatible(&self, resource_type: ShaderResourceType) -> bool {
+        matches!(resource_type, ShaderResourceType::ConstantBuffer)
+    }
+
+    fn device_object(&self) -> &DeviceObject {
+        self
+    }
+}
+
+impl ShaderResource for Sampler {
+    fn is_compatible(&self, resource_type: ShaderResourceType) -> bool {
+        matches!(resource_type, ShaderResourceType::Sampler)
+    }
+
+    fn device_object(&self) -> &DeviceObject {
+        self
+    }
+}
+
+impl ShaderResource for TopLevelAS {
+    fn is_compatible(&self, resource_type: ShaderResourceType) -> bool {
+        matches!(resource_type, ShaderResourceType::AccelStruct)
+    }
+
+    fn device_object(&self) -> &DeviceObject {
+        self
+    }
+}
+
+/// The resources of a field of a `#[derive(ShaderResources)]` struct : one resource, or an array of them.
+pub trait ShaderResourceArray {
+    fn resources(&self) -> Vec<&dyn ShaderResource>;
+}
+
+impl<T: ShaderResource> ShaderResourceArray for &T {
+    fn resources(&self) -> Vec<&dyn ShaderResource> {
+        vec![*self]
+    }
+}
+
+impl<T: ShaderResource, const N: usize> ShaderResourceArray for [&T; N] {
+    fn resources(&self) -> Vec<&dyn ShaderResource> {
+        self.iter()
+            .map(|&resource| resource as &dyn ShaderResource)
+            .collect()
+    }
+}
+
+impl<T: ShaderResource> ShaderResourceArray for &[&T] {
+    fn resources(&self) -> Vec<&dyn ShaderResource> {
+        self.iter()
+            .map(|&resource| resource as &dyn ShaderResource)
+            .collect()
+    }
+}
+
+/// A field of a `#[derive(ShaderResources)]` struct that could not be bound.
+#[derive(Clone, Debug)]
+pub enum ShaderResourceBindError {
+    /// The shader stage has no variable with the name of the field, or its variable has another variable type.
+    MissingVariable {
+        name: &'static str,
+        shader_type: ShaderType,
+    },
+    MismatchedResourceType {
+        name: &'static str,
+        shader_type: ShaderType,
+        variable_type: ShaderResourceType,
+    },
+    /// The field has more resources than the array of the variable.
+    MismatchedArraySize {
+        name: &'static str,
+        shader_type: ShaderType,
+        array_size: usize,
+        resource_count: usize,
+    },
+}
+
+impl std::error::Error for ShaderResourceBindError {}
+
+impl Display for ShaderResourceBindError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ShaderResourceBindError::MissingVariable { name, shader_type } => {
+                write!(
+                    f,
+                    "the {shader_type:?} shader has no variable named '{name}'"
+                )
+            }
+            ShaderResourceBindError::MismatchedResourceType {
+                name,
+                shader_type,
+                variable_type,
+            } => write!(
+                f,
+                "'{name}' is a {variable_type:?} in the {shader_type:?} shader, the bound resource can not be used as such"
+            ),
+            ShaderResourceBindError::MismatchedArraySize {
+                name,
+                shader_type,
+                array_size,
+                resource_count,
+            } => write!(
+                f,
+                "'{name}' is an array of {array_size} in the {shader_type:?} shader, but {resource_count} resources are bound"
+            ),
+        }
+    }
+}
+
+/// A struct whose fields are bound to the shader resource variables of the same name.
+///
+/// It is implemented by `#[derive(ShaderResources)]` with the `derive` feature. Each field is a
+/// `&TextureView`, `&BufferView`, `&Buffer`, `&Sampler`, `&TopLevelAS`, or an array or a slice of them,
+/// annotated with the name of its variable and the stages that use it :
+/// `#[resource(name = "g_Texture", stages = Pixel)]`, or `stages = Vertex | Pixel`.
+/// The variable type is `Mutable` by default, and can be set with `variable_type = Static`.
+pub trait ShaderResources {
+    /// The variable descriptions of the fields, for the resource layout of a pipeline state.
+    fn variable_descs() -> Vec<ShaderResourceVariableDesc<'static>>;
+
+    /// Binds the mutable and dynamic fields to the variables of `srb`, in every stage of each field.
+    ///
+    /// The fields are all bound, even when some of them fail.
+    fn bind(&self, srb: &mut ShaderResourceBinding) -> Result<(), Vec<ShaderResourceBindError>>;
+
+    /// Binds the static fields to the static variables of `pipeline_state`.
+    fn bind_static(
+        &self,
+        pipeline_state: &PipelineState,
+    ) -> Result<(), Vec<ShaderResourceBindError>>;
+}
+
+/// Binds `resources` to the mutable or dynamic variable `name` of `srb`, in each stage of `shader_stages`.
+/// Used by `#[derive(ShaderResources)]`.
+#[doc(hidden)]
+pub fn bind_srb_variable(
+    srb: &ShaderResourceBinding,
+    name: &'static str,
+    shader_stages: ShaderTypes,
+    resources: &[&dyn ShaderResource],
+    errors: &mut Vec<ShaderResourceBindError>,
+) {
+    bind_stages(name, shader_stages, resources, errors, |_, stage| {
+        srb.get_variable_by_name(name, stage)
+    })
+}
+
+/// Binds `resources` to the static variable `name` of `pipeline_state`, in each stage of `shader_stages`.
+/// Used by `#[derive(ShaderResources)]`.
+#[doc(hidden)]
+pub fn bind_static_variable(
+    pipeline_state: &PipelineState,
+    name: &'static str,
+    shader_stages: ShaderTypes,
+    resources: &[&dyn ShaderResource],
+    errors: &mut Vec<ShaderResourceBindError>,
+) {
+    bind_stages(name, shader_stages, resources, errors, |shader_type, _| {
+        pipeline_state.get_static_variable_by_name(shader_type, name)
+    })
+}
+
+fn bind_stages<'variable>(
+    name: &'static str,
+    shader_stages: ShaderTypes,
+    resources: &[&dyn ShaderResource],
+    errors: &mut Vec<ShaderResourceBindError>,
+    get_variable: impl Fn(ShaderType, ShaderTypes) -> Option<&'variable ShaderResourceVariable>,
+) {
+    let mut stage_bits = shader_stages.bits();
+
+    while stage_bits != 0 {
+        let stage_bit = stage_bits & stage_bits.wrapping_neg();
+        stage_bits &= !stage_bit;
+
+        let Some(shader_type) = ShaderType::from_sys(stage_bit) else {
+            continue;
+        };
+
+        let variable = get_variable(shader_type, ShaderTypes::from_bits_retain(stage_bit));
+
+        if let Err(error) = bind_variable(name, shader_type, resources, variable) {
+            errors.push(error);
+        }
+    }
+}
+
+fn bind_variable(
+    name: &'static str,
+    shader_type: ShaderType,
+    resources: &[&dyn ShaderResource],
+    variable: Option<&ShaderResourceVariable>,
+) -> Result<(), ShaderResourceBindError> {
+    let variable =
+        variable.ok_or(ShaderResourceBindError::MissingVariable { name, shader_type })?;
+
+    let resource_desc = variable.get_resource_desc();
+    let variable_type = resource_desc.resource_type();
+
+    if !resources
+        .iter()
+        .all(|resource| resource.is_compatible(variable_type))
+    {
+        return Err(ShaderResourceBindError::MismatchedResourceType {
+            name,
+            shader_type,
+            variable_type,
+        });
+    }
+
+    if resources.len() > resource_desc.array_size() {
+        return Err(ShaderResourceBindError::MismatchedArraySize {
+            name,
+            shader_type,
+            array_size: resource_desc.array_size(),
+            resource_count: resources.len(),
+        });
+    }
+
+    match resources {
+        [resource] => variable.set(resource.device_object(), SetShaderResourceFlags::None),
+        _ => variable.set_array(
+            &resources
+                .iter()
+                .map(|resource| resource.device_object())
+                .collect::<Vec<_>>(),
+            SetShaderResourceFlags::None,
+        ),
+    }
+
+    Ok(())
+}
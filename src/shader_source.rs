@@ -0,0 +1,210 @@
+use std::{
+    borrow::Cow,
+    collections::{BTreeSet, HashMap},
+    ffi::CStr,
+    os::raw::c_char,
+    path::{Path, PathBuf},
+    sync::{Arc, Mutex},
+};
+
+use crate::{
+    Boxed, file_stream::RustFileStream, object::RustObject, shader::ShaderSourceInputStreamFactory,
+};
+
+/// A source of shader files implemented in Rust.
+///
+/// The engine requests the shader sources and their `#include` files by name.
+/// It can do so from several threads when shaders are compiled asynchronously.
+pub trait ShaderSourceProvider: Send + Sync {
+    /// Returns the content of the file `name`, or `None` if this source does not provide it.
+    fn load(&self, name: &str) -> Option<Cow<'static, [u8]>>;
+}
+
+pub(crate) fn normalize_name(name: &str) -> String {
+    let name = name.replace('\\', "/");
+    name.trim_start_matches("./").to_owned()
+}
+
+/// Shader files stored in memory, for example embedded in the binary with `include_bytes!`.
+#[derive(Default)]
+pub struct InMemoryShaderSource {
+    files: HashMap<String, Cow<'static, [u8]>>,
+}
+
+impl InMemoryShaderSource {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn insert(
+        &mut self,
+        name: impl AsRef<str>,
+        content: impl Into<Cow<'static, [u8]>>,
+    ) -> &mut Self {
+        self.files
+            .insert(normalize_name(name.as_ref()), content.into());
+        self
+    }
+}
+
+impl<N: AsRef<str>, C: Into<Cow<'static, [u8]>>> FromIterator<(N, C)> for InMemoryShaderSource {
+    fn from_iter<I: IntoIterator<Item = (N, C)>>(iter: I) -> Self {
+        let mut source = InMemoryShaderSource::new();
+        iter.into_iter().for_each(|(name, content)| {
+            source.insert(name, content);
+        });
+        source
+    }
+}
+
+impl ShaderSourceProvider for InMemoryShaderSource {
+    fn load(&self, name: &str) -> Option<Cow<'static, [u8]>> {
+        self.files.get(&normalize_name(name)).cloned()
+    }
+}
+
+/// Shader files read from a directory of the file system.
+pub struct DirectoryShaderSource {
+    root: PathBuf,
+}
+
+impl DirectoryShaderSource {
+    pub fn new(root: impl AsRef<Path>) -> Self {
+        DirectoryShaderSource {
+            root: root.as_ref().to_path_buf(),
+        }
+    }
+}
+
+impl ShaderSourceProvider for DirectoryShaderSource {
+    fn load(&self, name: &str) -> Option<Cow<'static, [u8]>> {
+        std::fs::read(self.root.join(normalize_name(name)))
+            .ok()
+            .map(Cow::Owned)
+    }
+}
+
+/// Shader files read from a list of directories, that records the path of every file it provides.
+///
+/// The directories are searched in order : the first one that contains a file hides the files with
+/// the same name in the next ones. The clones of a source share its record, which can still be read
+/// once a clone has been given to `ShaderSourceInputStreamFactory::from_source`.
+#[derive(Clone)]
+pub struct TrackingShaderSource {
+    search_paths: Arc<[PathBuf]>,
+    dependencies: Arc<Mutex<BTreeSet<PathBuf>>>,
+}
+
+impl TrackingShaderSource {
+    pub fn new(search_paths: impl Into<Arc<[PathBuf]>>) -> Self {
+        TrackingShaderSource {
+            search_paths: search_paths.into(),
+            dependencies: Arc::default(),
+        }
+    }
+
+    /// The files provided so far, canonicalized when possible.
+    pub fn dependencies(&self) -> BTreeSet<PathBuf> {
+        self.dependencies.lock().unwrap().clone()
+    }
+}
+
+impl ShaderSourceProvider for TrackingShaderSource {
+    fn load(&self, name: &str) -> Option<Cow<'static, [u8]>> {
+        let name = normalize_name(name);
+
+        self.search_paths.iter().find_map(|search_path| {
+            let path = search_path.join(&name);
+            let content = std::fs::read(&path).ok()?;
+
+            self.dependencies
+                .lock()
+                .unwrap()
+                .insert(std::fs::canonicalize(&path).unwrap_or(path));
+
+            Some(Cow::Owned(content))
+        })
+    }
+}
+
+/// Layers several shader sources.
+///
+/// The layers are searched in the order they were added :
+/// the first layer that provides a file hides the files with the same name in the next layers.
+#[derive(Default)]
+pub struct CompositeShaderSource {
+    layers: Vec<Box<dyn ShaderSourceProvider>>,
+}
+
+impl CompositeShaderSource {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn with_layer(mut self, layer: impl ShaderSourceProvider + 'static) -> Self {
+        self.layers.push(Box::new(layer));
+        self
+    }
+}
+
+impl ShaderSourceProvider for CompositeShaderSource {
+    fn load(&self, name: &str) -> Option<Cow<'static, [u8]>> {
+        self.layers.iter().find_map(|layer| layer.load(name))
+    }
+}
+
+type ShaderSourceFactoryObject<P> =
+    RustObject<diligent_sys::IShaderSourceInputStreamFactoryVtbl, P>;
+
+unsafe extern "C" fn create_input_stream<P: ShaderSourceProvider>(
+    this: *mut diligent_sys::IShaderSourceInputStreamFactory,
+    name: *const c_char,
+    stream: *mut *mut diligent_sys::IFileStream,
+) {
+    let source = unsafe { &ShaderSourceFactoryObject::<P>::from_sys(this).value };
+
+    let content = if name.is_null() {
+        None
+    } else {
+        unsafe { CStr::from_ptr(name) }
+            .to_str()
+            .ok()
+            .and_then(|name| source.load(name))
+    };
+
+    unsafe {
+        *stream = content.map_or(std::ptr::null_mut(), RustFileStream::create);
+    }
+}
+
+unsafe extern "C" fn create_input_stream2<P: ShaderSourceProvider>(
+    this: *mut diligent_sys::IShaderSourceInputStreamFactory,
+    name: *const c_char,
+    _flags: diligent_sys::CREATE_SHADER_SOURCE_INPUT_STREAM_FLAGS,
+    stream: *mut *mut diligent_sys::IFileStream,
+) {
+    // The Rust sources never report missing files, so the silent flag has no effect
+    unsafe { create_input_stream::<P>(this, name, stream) }
+}
+
+impl ShaderSourceInputStreamFactory {
+    /// Creates a stream factory that serves the files of a Rust source.
+    /// It can be used wherever the factory created by
+    /// `EngineFactory::create_default_shader_source_stream_factory` is accepted.
+    pub fn from_source<P: ShaderSourceProvider + 'static>(source: P) -> Boxed<Self> {
+        let factory_ptr = ShaderSourceFactoryObject::<P>::create(
+            diligent_sys::IShaderSourceInputStreamFactoryVtbl {
+                Object: ShaderSourceFactoryObject::<P>::OBJECT_METHODS,
+                ShaderSourceInputStreamFactory:
+                    diligent_sys::IShaderSourceInputStreamFactoryMethods {
+                        CreateInputStream: Some(create_input_stream::<P>),
+                        CreateInputStream2: Some(create_input_stream2::<P>),
+                    },
+            },
+            source,
+        );
+
+        // The object was just allocated, the pointer can not be null
+        Boxed::new(factory_ptr).unwrap()
+    }
+}
@@ -0,0 +1,401 @@
+use std::{collections::HashMap, marker::PhantomData, ops::Range};
+
+use crate::{
+    Boxed, Ported,
+    blas::BottomLevelAS,
+    buffer::Buffer,
+    device_context::{DeviceContext, StateTransitionDesc, StateTransitionFlags},
+    graphics_types::{ResourceState, StateTransitionType},
+    texture::Texture,
+    tlas::TopLevelAS,
+};
+
+/// The states in which a resource is only read. Several of them can be combined.
+const READ_ONLY_STATES: ResourceState = ResourceState::GenericRead
+    .union(ResourceState::DepthRead)
+    .union(ResourceState::ResolveSource)
+    .union(ResourceState::Present)
+    .union(ResourceState::BuildAsRead)
+    .union(ResourceState::RayTracing)
+    .union(ResourceState::ShadingRate);
+
+/// A range of mip levels and array slices of a texture.
+///
+/// The ranges are clamped to the subresources of the texture, so `0..u32::MAX` covers all of them.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct TextureSubresourceRange {
+    pub mip_levels: Range<u32>,
+    pub array_slices: Range<u32>,
+}
+
+impl TextureSubresourceRange {
+    pub fn new(mip_levels: Range<u32>, array_slices: Range<u32>) -> Self {
+        TextureSubresourceRange {
+            mip_levels,
+            array_slices,
+        }
+    }
+
+    /// The mip levels `mip_levels` of every array slice.
+    pub fn mip_levels(mip_levels: Range<u32>) -> Self {
+        Self::new(mip_levels, 0..u32::MAX)
+    }
+
+    /// Every mip level of the array slices `array_slices`.
+    pub fn array_slices(array_slices: Range<u32>) -> Self {
+        Self::new(0..u32::MAX, array_slices)
+    }
+}
+
+enum TrackedObject {
+    Buffer(Boxed<Buffer>),
+    Texture(Boxed<Texture>),
+    BottomLevelAS(Boxed<BottomLevelAS>),
+    TopLevelAS(Boxed<TopLevelAS>),
+}
+
+impl TrackedObject {
+    fn sys_ptr(&self) -> *mut diligent_sys::IDeviceObject {
+        match self {
+            TrackedObject::Buffer(buffer) => buffer.sys_ptr() as _,
+            TrackedObject::Texture(texture) => texture.sys_ptr() as _,
+            TrackedObject::BottomLevelAS(blas) => blas.sys_ptr() as _,
+            TrackedObject::TopLevelAS(tlas) => tlas.sys_ptr() as _,
+        }
+    }
+
+    fn get_state(&self) -> ResourceState {
+        match self {
+            TrackedObject::Buffer(buffer) => buffer.get_state(),
+            TrackedObject::Texture(texture) => texture.get_state(),
+            TrackedObject::BottomLevelAS(blas) => blas.get_state(),
+            TrackedObject::TopLevelAS(tlas) => tlas.get_state(),
+        }
+    }
+}
+
+struct TrackedResource {
+    object: TrackedObject,
+    mip_levels: u32,
+    array_size: u32,
+    /// The state of every subresource after the last flush, indexed by `slice * mip_levels + mip`.
+    states: Vec<ResourceState>,
+    /// The states required since the last flush, with the same indexing as `states`.
+    required: Vec<Option<ResourceState>>,
+}
+
+impl TrackedResource {
+    fn new(object: TrackedObject, mip_levels: u32, array_size: u32) -> Self {
+        let num_subresources = (mip_levels * array_size) as usize;
+        TrackedResource {
+            object,
+            mip_levels,
+            array_size,
+            states: vec![ResourceState::Undefined; num_subresources],
+            required: vec![None; num_subresources],
+        }
+    }
+
+    fn require(&mut self, mip_levels: Range<u32>, array_slices: Range<u32>, state: ResourceState) {
+        let mip_levels = mip_levels.start..mip_levels.end.min(self.mip_levels);
+        let array_slices = array_slices.start..array_slices.end.min(self.array_size);
+
+        for slice in array_slices {
+            for mip in mip_levels.clone() {
+                let required = &mut self.required[(slice * self.mip_levels + mip) as usize];
+                *required = Some(match *required {
+                    // Several passes can read the same subresource in one batch
+                    Some(previous)
+                        if READ_ONLY_STATES.contains(previous)
+                            && READ_ONLY_STATES.contains(state) =>
+                    {
+                        previous | state
+                    }
+                    _ => state,
+                });
+            }
+        }
+    }
+
+    fn is_required(&self) -> bool {
+        self.required.iter().any(Option::is_some)
+    }
+}
+
+#[derive(Clone, PartialEq, Eq)]
+struct Run {
+    mip_levels: Range<u32>,
+    old_state: diligent_sys::RESOURCE_STATE,
+    new_state: diligent_sys::RESOURCE_STATE,
+}
+
+fn needs_transition(old_state: ResourceState, new_state: ResourceState) -> bool {
+    // Two consecutive unordered accesses are separated by a UAV barrier,
+    // which the engine inserts for a transition that keeps the unordered access state
+    old_state.bits() != new_state.bits()
+        || (old_state.contains(ResourceState::UnorderedAccess)
+            && new_state.contains(ResourceState::UnorderedAccess))
+}
+
+/// Records the states in which the passes use their resources, and transitions the resources
+/// to these states with a single call to `DeviceContext::transition_resource_states`.
+///
+/// Before each draw or dispatch, the passes declare the state they need for each resource with the
+/// `require_*` functions, then `flush` issues the minimal set of transitions. The draw and dispatch
+/// commands can then use the `verify_state` or `no_state_transition` markers of the resources.
+///
+/// The tracker keeps a reference to the resources until they are removed with `forget`.
+/// A tracked resource whose state is known by the engine starts from that state,
+/// otherwise its content is considered undefined.
+#[derive(Default)]
+pub struct StateTracker {
+    resources: HashMap<*mut diligent_sys::IDeviceObject, TrackedResource>,
+}
+
+// # Safety : The tracked resources are only referenced through Boxed, which is Send
+unsafe impl Send for StateTracker {}
+
+impl StateTracker {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    fn resource(
+        &mut self,
+        sys_ptr: *mut diligent_sys::IDeviceObject,
+        create: impl FnOnce() -> TrackedResource,
+    ) -> &mut TrackedResource {
+        self.resources.entry(sys_ptr).or_insert_with(create)
+    }
+
+    pub fn require_buffer(&mut self, buffer: &Buffer, state: ResourceState) {
+        self.resource(buffer.sys_ptr() as _, || {
+            TrackedResource::new(TrackedObject::Buffer(Boxed::from_ref(buffer)), 1, 1)
+        })
+        .require(0..1, 0..1, state);
+    }
+
+    pub fn require_texture(&mut self, texture: &Texture, state: ResourceState) {
+        self.require_texture_subresources(
+            texture,
+            TextureSubresourceRange::new(0..u32::MAX, 0..u32::MAX),
+            state,
+        );
+    }
+
+    pub fn require_texture_subresources(
+        &mut self,
+        texture: &Texture,
+        range: TextureSubresourceRange,
+        state: ResourceState,
+    ) {
+        self.resource(texture.sys_ptr() as _, || {
+            let desc = texture.desc();
+            // The depth slices of 3D textures are not separate subresources
+            let array_size = if desc.dimension().is_3D() {
+                1
+            } else {
+                unsafe { desc.0.__bindgen_anon_1.ArraySize }
+            };
+            TrackedResource::new(
+                TrackedObject::Texture(Boxed::from_ref(texture)),
+                desc.mip_levels(),
+                array_size,
+            )
+        })
+        .require(range.mip_levels, range.array_slices, state);
+    }
+
+    pub fn require_blas(&mut self, blas: &BottomLevelAS, state: ResourceState) {
+        self.resource(blas.sys_ptr() as _, || {
+            TrackedResource::new(TrackedObject::BottomLevelAS(Boxed::from_ref(blas)), 1, 1)
+        })
+        .require(0..1, 0..1, state);
+    }
+
+    pub fn require_tlas(&mut self, tlas: &TopLevelAS, state: ResourceState) {
+        self.resource(tlas.sys_ptr() as _, || {
+            TrackedResource::new(TrackedObject::TopLevelAS(Boxed::from_ref(tlas)), 1, 1)
+        })
+        .require(0..1, 0..1, state);
+    }
+
+    /// Stops tracking a resource and releases the reference held by the tracker.
+    pub fn forget(&mut self, resource: &impl Ported) {
+        self.resources
+            .remove(&(resource.sys_ptr() as *mut diligent_sys::IDeviceObject));
+    }
+
+    /// Transitions the resources to the states required since the last flush.
+    ///
+    /// The subresources of a texture that end up in different states are transitioned separately.
+    /// The engine can not represent such a texture with a single state, so its state is unknown
+    /// until all its subresources are in the same state again.
+    pub fn flush(&mut self, context: &DeviceContext) {
+        let mut barriers = Vec::new();
+        let mut unknown_before = Vec::new();
+        let mut known_after = Vec::new();
+
+        for resource in self
+            .resources
+            .values_mut()
+            .filter(|resource| resource.is_required())
+        {
+            // The engine state is the most recent one, unless the subresources were in different states
+            let engine_state = resource.object.get_state();
+            if !engine_state.is_empty() {
+                resource.states.fill(engine_state);
+            }
+
+            let slice_runs = (0..resource.array_size)
+                .map(|slice| {
+                    let mut runs = Vec::<Run>::new();
+                    for mip in 0..resource.mip_levels {
+                        let index = (slice * resource.mip_levels + mip) as usize;
+                        let old_state = resource.states[index];
+
+                        let Some(new_state) = resource.required[index] else {
+                            continue;
+                        };
+                        if !needs_transition(old_state, new_state) {
+                            continue;
+                        }
+
+                        match runs.last_mut() {
+                            Some(run)
+                                if run.mip_levels.end == mip
+                                    && run.old_state == old_state.bits()
+                                    && run.new_state == new_state.bits() =>
+                            {
+                                run.mip_levels.end += 1
+                            }
+                            _ => runs.push(Run {
+                                mip_levels: mip..mip + 1,
+                                old_state: old_state.bits(),
+                                new_state: new_state.bits(),
+                            }),
+                        }
+                    }
+                    runs
+                })
+                .collect::<Vec<_>>();
+
+            for (index, required) in resource.required.iter_mut().enumerate() {
+                if let Some(state) = required.take() {
+                    resource.states[index] = state;
+                }
+            }
+
+            // Merge the consecutive array slices that need the same transitions
+            let mut groups: Vec<(Range<u32>, &[Run])> = Vec::new();
+            for (slice, runs) in (0..resource.array_size).zip(slice_runs.iter()) {
+                match groups.last_mut() {
+                    Some((slices, group_runs)) if *group_runs == runs.as_slice() => {
+                        slices.end = slice + 1
+                    }
+                    _ => groups.push((slice..slice + 1, runs)),
+                }
+            }
+
+            let is_whole_resource = matches!(
+                groups.as_slice(),
+                [(slices, [run])] if *slices == (0..resource.array_size)
+                    && run.mip_levels == (0..resource.mip_levels)
+            );
+
+            let sys_ptr = resource.object.sys_ptr();
+
+            if is_whole_resource {
+                let run = &groups[0].1[0];
+                barriers.push(transition(
+                    sys_ptr,
+                    0..diligent_sys::REMAINING_MIP_LEVELS,
+                    0..diligent_sys::REMAINING_ARRAY_SLICES,
+                    run,
+                    StateTransitionFlags::UpdateState,
+                ));
+                continue;
+            }
+
+            let num_barriers = barriers.len();
+            for (slices, runs) in groups {
+                barriers.extend(runs.iter().map(|run| {
+                    transition(
+                        sys_ptr,
+                        run.mip_levels.clone(),
+                        slices.clone(),
+                        run,
+                        StateTransitionFlags::None,
+                    )
+                }));
+            }
+
+            if let TrackedObject::Texture(_) = &resource.object {
+                let transitioned = barriers.len() > num_barriers;
+                if transitioned && !engine_state.is_empty() {
+                    unknown_before.push(sys_ptr);
+                }
+
+                let first_state = resource.states[0];
+                if (transitioned || engine_state.is_empty())
+                    && resource
+                        .states
+                        .iter()
+                        .all(|state| state.bits() == first_state.bits())
+                {
+                    known_after.push((sys_ptr, first_state));
+                }
+            }
+        }
+
+        for sys_ptr in unknown_before {
+            self.record_texture_state(sys_ptr, ResourceState::empty());
+        }
+
+        if !barriers.is_empty() {
+            context.transition_resource_states(&barriers);
+        }
+
+        for (sys_ptr, state) in known_after {
+            self.record_texture_state(sys_ptr, state);
+        }
+    }
+
+    fn record_texture_state(
+        &self,
+        sys_ptr: *mut diligent_sys::IDeviceObject,
+        state: ResourceState,
+    ) {
+        if let Some(TrackedResource {
+            object: TrackedObject::Texture(texture),
+            ..
+        }) = self.resources.get(&sys_ptr)
+        {
+            texture.record_state(state);
+        }
+    }
+}
+
+fn transition<'a>(
+    sys_ptr: *mut diligent_sys::IDeviceObject,
+    mip_levels: Range<u32>,
+    array_slices: Range<u32>,
+    run: &Run,
+    flags: StateTransitionFlags,
+) -> StateTransitionDesc<'a> {
+    StateTransitionDesc(
+        diligent_sys::StateTransitionDesc {
+            pResourceBefore: std::ptr::null_mut(),
+            pResource: sys_ptr,
+            FirstMipLevel: mip_levels.start,
+            MipLevelsCount: mip_levels.end - mip_levels.start,
+            FirstArraySlice: array_slices.start,
+            ArraySliceCount: array_slices.end - array_slices.start,
+            OldState: run.old_state,
+            NewState: run.new_state,
+            TransitionType: StateTransitionType::Immediate.into(),
+            Flags: flags.bits(),
+        },
+        PhantomData,
+    )
+}
@@ -9,19 +9,22 @@ use bitflags::bitflags;
 use static_assertions::const_assert_eq;
 
 use crate::{
-    Boxed, BoxedFromNulError, MapType, Ported,
+    Boxed, Error, MapType, Ported,
     buffer::Buffer,
     device_context::DeviceContext,
     device_object::{
         DeviceObject, DeviceObjectAttribs, ResourceStateNoTransition, ResourceStateTransition,
         ResourceStateVerify, ResourceTransition,
     },
+    error::{MessageCapture, Operation},
     graphics_types::{BindFlags, CpuAccessFlags, ResourceState, TextureFormat, Usage},
+    object::Interface,
     resource_access_states,
     texture_view::{TextureView, TextureViewDesc, TextureViewType},
 };
 
 #[derive(Clone, Copy)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum TextureDimension {
     Texture1D,
     Texture1DArray { array_size: NonZero<usize> },
@@ -132,6 +135,7 @@ impl TextureDimension {
 
 bitflags! {
     #[derive(Clone,Copy)]
+    #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
     pub struct MiscTextureFlags: diligent_sys::MISC_TEXTURE_FLAGS {
         const None           = diligent_sys::MISC_TEXTURE_FLAG_NONE as diligent_sys::MISC_TEXTURE_FLAGS;
         const GenerateMips   = diligent_sys::MISC_TEXTURE_FLAG_GENERATE_MIPS as diligent_sys::MISC_TEXTURE_FLAGS;
@@ -423,23 +427,31 @@ impl TextureDesc<'_> {
         u32::max(self.depth() >> mip_level, 1)
     }
 
+    /// The width of the mip level, rounded up to a whole number of blocks for the compressed formats.
     pub fn mip_level_storage_width(&self, mip_level: u32) -> usize {
-        if let Some(format) = self.format()
-            && let Some(crate::ComponentType::Compressed) = format.component_type()
-        {
-            todo!()
-        } else {
-            self.mip_level_logical_width(mip_level) as usize
+        let width = self.mip_level_logical_width(mip_level) as usize;
+        match self.compressed_format() {
+            Some(format) => width.next_multiple_of(format.block_width() as usize),
+            None => width,
         }
     }
 
+    /// The height of the mip level, rounded up to a whole number of blocks for the compressed formats.
     pub fn mip_level_storage_height(&self, mip_level: u32) -> usize {
-        if let Some(format) = self.format()
-            && let Some(crate::ComponentType::Compressed) = format.component_type()
-        {
-            todo!()
-        } else {
-            self.mip_level_logical_height(mip_level) as usize
+        let height = self.mip_level_logical_height(mip_level) as usize;
+        match self.compressed_format() {
+            Some(format) => height.next_multiple_of(format.block_height() as usize),
+            None => height,
+        }
+    }
+
+    /// The number of rows of the mip level in memory : a row holds a line of blocks for the compressed formats.
+    pub fn mip_level_row_count(&self, mip_level: u32) -> usize {
+        match self.compressed_format() {
+            Some(format) => {
+                self.mip_level_storage_height(mip_level) / format.block_height() as usize
+            }
+            None => self.mip_level_storage_height(mip_level),
         }
     }
 
@@ -447,7 +459,9 @@ impl TextureDesc<'_> {
         self.format()
             .map_or(self.mip_level_storage_width(mip_level), |format| {
                 if let Some(crate::ComponentType::Compressed) = format.component_type() {
-                    todo!()
+                    // The component size of a compressed format is the size of a block
+                    self.mip_level_storage_width(mip_level) / format.block_width() as usize
+                        * format.component_size() as usize
                 } else {
                     self.mip_level_storage_width(mip_level)
                         * format.component_size() as usize
@@ -457,13 +471,16 @@ impl TextureDesc<'_> {
     }
 
     pub fn mip_level_depth_slice_size(&self, mip_level: u32) -> usize {
-        if let Some(format) = self.format()
-            && let Some(crate::ComponentType::Compressed) = format.component_type()
-        {
-            todo!()
-        } else {
-            self.mip_level_row_size(mip_level) * self.mip_level_storage_height(mip_level)
-        }
+        self.mip_level_row_size(mip_level) * self.mip_level_row_count(mip_level)
+    }
+
+    fn compressed_format(&self) -> Option<TextureFormat> {
+        self.format().filter(|format| {
+            matches!(
+                format.component_type(),
+                Some(crate::ComponentType::Compressed)
+            )
+        })
     }
 
     pub fn mip_level_mip_size(&self, mip_level: u32) -> usize {
@@ -550,6 +567,15 @@ define_ported!(
     DeviceObject
 );
 
+impl Interface for Texture {
+    const IID: diligent_sys::INTERFACE_ID = diligent_sys::INTERFACE_ID {
+        Data1: 0xa64b0e60,
+        Data2: 0x1b5e,
+        Data3: 0x4cfd,
+        Data4: [0xb8, 0x80, 0x66, 0x3a, 0x1a, 0xdc, 0xbe, 0x98],
+    };
+}
+
 impl Texture {
     pub fn desc(&self) -> &TextureDesc<'_> {
         let desc_ptr = unsafe_member_call!(self, DeviceObject, GetDesc);
@@ -559,8 +585,9 @@ impl Texture {
     pub fn create_view(
         &self,
         texture_view_desc: &TextureViewDesc,
-    ) -> Result<Boxed<TextureView>, BoxedFromNulError> {
+    ) -> Result<Boxed<TextureView>, Error> {
         let mut texture_view_ptr = std::ptr::null_mut();
+        let capture = MessageCapture::begin();
         unsafe_member_call!(
             self,
             Texture,
@@ -569,7 +596,11 @@ impl Texture {
             &mut texture_view_ptr
         );
 
-        Boxed::new(texture_view_ptr)
+        capture.boxed(
+            texture_view_ptr,
+            Operation::CreateTextureView,
+            texture_view_desc.name(),
+        )
     }
 
     pub fn get_default_view(&self, texture_view_type: TextureViewType) -> Option<&TextureView> {
@@ -587,6 +618,12 @@ impl Texture {
     }
 
     pub fn set_state(&mut self, state: ResourceState) {
+        self.record_state(state);
+    }
+
+    /// Sets the state known by the engine through a shared reference, for the state tracker
+    /// which holds the textures it transitions through shared references.
+    pub(crate) fn record_state(&self, state: ResourceState) {
         unsafe_member_call!(self, Texture, SetState, state.bits());
     }
 
@@ -636,11 +673,12 @@ impl<'context, 'texture, T: Sized, State: MapType> TextureMapToken<'context, 'te
         array_slice: u32,
         map_flags: crate::MapFlags,
         map_region: Option<crate::Box>,
-    ) -> Result<TextureMapToken<'context, 'texture, T, State>, BoxedFromNulError> {
+    ) -> Result<TextureMapToken<'context, 'texture, T, State>, Error> {
         use crate::Ported;
 
         let mut mapped_resource = std::mem::MaybeUninit::uninit();
 
+        let capture = MessageCapture::begin();
         unsafe_member_call!(
             device_context,
             DeviceContext,
@@ -657,7 +695,7 @@ impl<'context, 'texture, T: Sized, State: MapType> TextureMapToken<'context, 'te
         let mapped_resource = unsafe { mapped_resource.assume_init() };
 
         if mapped_resource.pData.is_null() {
-            return Err(BoxedFromNulError);
+            return Err(capture.map_failed(Operation::MapTextureSubresource, texture.desc().name()));
         }
 
         Ok(TextureMapToken::<T, State> {
@@ -1,4 +1,4 @@
-use std::{ffi::CStr, marker::PhantomData, ops::Deref};
+use std::{ffi::CStr, marker::PhantomData, num::NonZero, ops::Deref};
 
 use bitflags::bitflags;
 use static_assertions::const_assert_eq;
@@ -10,11 +10,13 @@ use crate::{
         ResourceStateVerify,
     },
     graphics_types::TextureFormat,
+    object::Interface,
     sampler::Sampler,
     texture::{Texture, TextureDimension},
 };
 
 #[derive(Clone, Copy)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum TextureViewType {
     ShaderResource,
     RenderTarget,
@@ -61,6 +63,7 @@ impl TryFrom<diligent_sys::TEXTURE_VIEW_TYPE> for TextureViewType {
 
 bitflags! {
     #[derive(Clone,Copy)]
+    #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
     pub struct UavAccessFlags: diligent_sys::UAV_ACCESS_FLAG {
         const Unspecified = diligent_sys::UAV_ACCESS_UNSPECIFIED as diligent_sys::UAV_ACCESS_FLAG;
         const Read        = diligent_sys::UAV_ACCESS_FLAG_READ as diligent_sys::UAV_ACCESS_FLAG;
@@ -73,6 +76,7 @@ const_assert_eq!(diligent_sys::UAV_ACCESS_FLAG_LAST, 3);
 
 bitflags! {
     #[derive(Clone,Copy)]
+    #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
     pub struct TextureViewFlags: diligent_sys::TEXTURE_VIEW_FLAGS {
         const None                  = diligent_sys::TEXTURE_VIEW_FLAG_NONE as diligent_sys::TEXTURE_VIEW_FLAGS;
         const AllowMipMapGeneration = diligent_sys::TEXTURE_VIEW_FLAG_ALLOW_MIP_MAP_GENERATION as diligent_sys::TEXTURE_VIEW_FLAGS;
@@ -82,6 +86,7 @@ bitflags! {
 const_assert_eq!(diligent_sys::TEXTURE_VIEW_FLAG_LAST, 1);
 
 #[derive(Clone, Copy)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum TextureComponentSwizzle {
     Identity,
     Zero,
@@ -107,6 +112,27 @@ impl From<TextureComponentSwizzle> for diligent_sys::TEXTURE_COMPONENT_SWIZZLE {
     }
 }
 
+impl TryFrom<diligent_sys::TEXTURE_COMPONENT_SWIZZLE> for TextureComponentSwizzle {
+    type Error = std::io::Error;
+    fn try_from(value: diligent_sys::TEXTURE_COMPONENT_SWIZZLE) -> Result<Self, Self::Error> {
+        match value as _ {
+            diligent_sys::TEXTURE_COMPONENT_SWIZZLE_IDENTITY => {
+                Ok(TextureComponentSwizzle::Identity)
+            }
+            diligent_sys::TEXTURE_COMPONENT_SWIZZLE_ZERO => Ok(TextureComponentSwizzle::Zero),
+            diligent_sys::TEXTURE_COMPONENT_SWIZZLE_ONE => Ok(TextureComponentSwizzle::One),
+            diligent_sys::TEXTURE_COMPONENT_SWIZZLE_R => Ok(TextureComponentSwizzle::R),
+            diligent_sys::TEXTURE_COMPONENT_SWIZZLE_G => Ok(TextureComponentSwizzle::G),
+            diligent_sys::TEXTURE_COMPONENT_SWIZZLE_B => Ok(TextureComponentSwizzle::B),
+            diligent_sys::TEXTURE_COMPONENT_SWIZZLE_A => Ok(TextureComponentSwizzle::A),
+            _ => Err(Self::Error::new(
+                std::io::ErrorKind::InvalidData,
+                "Unknown TEXTURE_COMPONENT_SWIZZLE value",
+            )),
+        }
+    }
+}
+
 #[repr(transparent)]
 #[derive(Clone)]
 pub struct TextureComponentMapping(diligent_sys::TextureComponentMapping);
@@ -130,6 +156,19 @@ impl TextureComponentMapping {
 }
 
 impl TextureComponentMapping {
+    pub fn r(&self) -> TextureComponentSwizzle {
+        self.0.R.try_into().unwrap()
+    }
+    pub fn g(&self) -> TextureComponentSwizzle {
+        self.0.G.try_into().unwrap()
+    }
+    pub fn b(&self) -> TextureComponentSwizzle {
+        self.0.B.try_into().unwrap()
+    }
+    pub fn a(&self) -> TextureComponentSwizzle {
+        self.0.A.try_into().unwrap()
+    }
+
     #[rustfmt::skip]
     pub fn is_identity(&self) -> bool {
             ( self.0.R == diligent_sys::TEXTURE_COMPONENT_SWIZZLE_IDENTITY as diligent_sys::TEXTURE_COMPONENT_SWIZZLE
@@ -204,7 +243,18 @@ impl TextureViewDesc<'_> {
     pub fn view_type(&self) -> TextureViewType {
         self.0.ViewType.try_into().unwrap()
     }
-
+    pub fn dimension(&self) -> Option<TextureDimension> {
+        // The number of slices of the view is not part of the dimension passed to the builder
+        let num_slices =
+            NonZero::new(self.num_array_or_depth_slices()).unwrap_or(NonZero::<usize>::MIN);
+        TextureDimension::from_sys(self.0.TextureDim, num_slices, num_slices)
+    }
+    pub fn num_array_or_depth_slices(&self) -> usize {
+        unsafe { self.0.__bindgen_anon_2.NumArraySlices as usize }
+    }
+    pub fn first_array_or_depth_slice(&self) -> usize {
+        unsafe { self.0.__bindgen_anon_1.FirstArraySlice as usize }
+    }
     pub fn format(&self) -> Option<TextureFormat> {
         TextureFormat::try_from_sys(self.0.Format).unwrap()
     }
@@ -232,6 +282,15 @@ define_ported!(
     DeviceObject
 );
 
+impl Interface for TextureView {
+    const IID: diligent_sys::INTERFACE_ID = diligent_sys::INTERFACE_ID {
+        Data1: 0x5b2ea04e,
+        Data2: 0x8128,
+        Data3: 0x45e4,
+        Data4: [0xaa, 0x4d, 0x6d, 0xc7, 0xe7, 0x0d, 0xc4, 0x24],
+    };
+}
+
 impl TextureView {
     pub fn desc(&self) -> &TextureViewDesc<'_> {
         let desc_ptr = unsafe_member_call!(self, DeviceObject, GetDesc);
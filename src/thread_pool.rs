@@ -0,0 +1,386 @@
+use std::sync::{Arc, Condvar, Mutex, MutexGuard};
+
+use crate::{
+    Boxed, Ported,
+    object::{Object, RustObject},
+};
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum AsyncTaskStatus {
+    Unknown,
+    NotStarted,
+    Running,
+    Cancelled,
+    Complete,
+}
+
+impl From<diligent_sys::ASYNC_TASK_STATUS> for AsyncTaskStatus {
+    fn from(value: diligent_sys::ASYNC_TASK_STATUS) -> Self {
+        match value as _ {
+            diligent_sys::ASYNC_TASK_STATUS_NOT_STARTED => AsyncTaskStatus::NotStarted,
+            diligent_sys::ASYNC_TASK_STATUS_RUNNING => AsyncTaskStatus::Running,
+            diligent_sys::ASYNC_TASK_STATUS_CANCELLED => AsyncTaskStatus::Cancelled,
+            diligent_sys::ASYNC_TASK_STATUS_COMPLETE => AsyncTaskStatus::Complete,
+            _ => AsyncTaskStatus::Unknown,
+        }
+    }
+}
+
+impl From<AsyncTaskStatus> for diligent_sys::ASYNC_TASK_STATUS {
+    fn from(value: AsyncTaskStatus) -> Self {
+        (match value {
+            AsyncTaskStatus::Unknown => diligent_sys::ASYNC_TASK_STATUS_UNKNOWN,
+            AsyncTaskStatus::NotStarted => diligent_sys::ASYNC_TASK_STATUS_NOT_STARTED,
+            AsyncTaskStatus::Running => diligent_sys::ASYNC_TASK_STATUS_RUNNING,
+            AsyncTaskStatus::Cancelled => diligent_sys::ASYNC_TASK_STATUS_CANCELLED,
+            AsyncTaskStatus::Complete => diligent_sys::ASYNC_TASK_STATUS_COMPLETE,
+        }) as _
+    }
+}
+
+define_ported!(AsyncTask, diligent_sys::IAsyncTask, Object);
+
+impl AsyncTask {
+    pub fn run(&self, thread_id: u32) -> AsyncTaskStatus {
+        unsafe_member_call!(self, AsyncTask, Run, thread_id).into()
+    }
+
+    pub fn cancel(&self) {
+        unsafe_member_call!(self, AsyncTask, Cancel)
+    }
+
+    pub fn set_status(&self, status: AsyncTaskStatus) {
+        unsafe_member_call!(self, AsyncTask, SetStatus, status.into())
+    }
+
+    pub fn get_status(&self) -> AsyncTaskStatus {
+        unsafe_member_call!(self, AsyncTask, GetStatus).into()
+    }
+
+    pub fn set_priority(&self, priority: f32) {
+        unsafe_member_call!(self, AsyncTask, SetPriority, priority)
+    }
+
+    pub fn get_priority(&self) -> f32 {
+        unsafe_member_call!(self, AsyncTask, GetPriority)
+    }
+
+    pub fn is_finished(&self) -> bool {
+        unsafe_member_call!(self, AsyncTask, IsFinished)
+    }
+
+    pub fn wait_for_completion(&self) {
+        unsafe_member_call!(self, AsyncTask, WaitForCompletion)
+    }
+}
+
+define_ported!(
+    ThreadPool,
+    diligent_sys::IThreadPool,
+    diligent_sys::IThreadPoolMethods : 9,
+    Object
+);
+
+/// Runs the tasks of a `ThreadPool` implemented in Rust.
+///
+/// The thread pool keeps track of the task queue and of the dependencies between the tasks.
+/// It only hands the tasks that are ready to run to the executor.
+pub trait ThreadPoolExecutor: Send + Sync + 'static {
+    /// Runs `job` on one of the workers. The job receives the index of the worker that runs it.
+    fn execute(&self, job: Box<dyn FnOnce(u32) + Send>);
+}
+
+struct TaskEntry {
+    task: Boxed<AsyncTask>,
+    prerequisites: Vec<Boxed<AsyncTask>>,
+}
+
+// Safety : the engine tasks are reference counted objects that are meant to be run on any thread
+unsafe impl Send for TaskEntry {}
+
+impl TaskEntry {
+    fn is_ready(&self) -> bool {
+        self.prerequisites
+            .iter()
+            .all(|prerequisite| prerequisite.is_finished())
+    }
+}
+
+#[derive(Default)]
+struct PoolState {
+    queue: Vec<TaskEntry>,
+    running: u32,
+    stopped: bool,
+}
+
+impl PoolState {
+    fn take_ready_tasks(&mut self) -> Vec<TaskEntry> {
+        if self.stopped {
+            return Vec::new();
+        }
+
+        let (mut ready, pending): (Vec<_>, Vec<_>) = std::mem::take(&mut self.queue)
+            .into_iter()
+            .partition(TaskEntry::is_ready);
+
+        self.queue = pending;
+        self.running += ready.len() as u32;
+
+        ready.sort_by(|a, b| b.task.get_priority().total_cmp(&a.task.get_priority()));
+        ready
+    }
+
+    fn take_ready_task(&mut self) -> Option<TaskEntry> {
+        if self.stopped {
+            return None;
+        }
+
+        let (index, _) = self
+            .queue
+            .iter()
+            .enumerate()
+            .filter(|(_, entry)| entry.is_ready())
+            .max_by(|(_, a), (_, b)| a.task.get_priority().total_cmp(&b.task.get_priority()))?;
+
+        self.running += 1;
+        Some(self.queue.remove(index))
+    }
+
+    fn position(&self, task: *mut diligent_sys::IAsyncTask) -> Option<usize> {
+        self.queue
+            .iter()
+            .position(|entry| entry.task.sys_ptr() == task)
+    }
+}
+
+struct SharedPool<E: ThreadPoolExecutor> {
+    executor: E,
+    state: Mutex<PoolState>,
+    state_changed: Condvar,
+}
+
+impl<E: ThreadPoolExecutor> SharedPool<E> {
+    fn state(&self) -> MutexGuard<'_, PoolState> {
+        self.state
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner())
+    }
+
+    fn dispatch(self: &Arc<Self>) {
+        let ready = self.state().take_ready_tasks();
+
+        for entry in ready {
+            let pool = Arc::clone(self);
+            self.executor
+                .execute(Box::new(move |thread_id| pool.run_task(entry, thread_id)));
+        }
+    }
+
+    fn run_task(self: &Arc<Self>, entry: TaskEntry, thread_id: u32) {
+        if entry.task.get_status() != AsyncTaskStatus::Cancelled {
+            entry.task.set_status(AsyncTaskStatus::Running);
+            let status = entry.task.run(thread_id);
+
+            // The task asks to be run again later
+            if status == AsyncTaskStatus::NotStarted {
+                entry.task.set_status(status);
+                let mut state = self.state();
+                state.running -= 1;
+                state.queue.push(entry);
+                drop(state);
+
+                self.state_changed.notify_all();
+                self.dispatch();
+                return;
+            }
+
+            entry.task.set_status(status);
+        }
+
+        self.state().running -= 1;
+        drop(entry);
+
+        self.state_changed.notify_all();
+        self.dispatch();
+    }
+}
+
+struct RustThreadPool<E: ThreadPoolExecutor> {
+    pool: Arc<SharedPool<E>>,
+}
+
+type ThreadPoolObject<E> = RustObject<diligent_sys::IThreadPoolVtbl, RustThreadPool<E>>;
+
+impl<E: ThreadPoolExecutor> RustThreadPool<E> {
+    unsafe fn from_sys<'a>(this: *mut diligent_sys::IThreadPool) -> &'a Arc<SharedPool<E>> {
+        unsafe { &ThreadPoolObject::<E>::from_sys(this).value.pool }
+    }
+
+    unsafe extern "C" fn enqueue_task(
+        this: *mut diligent_sys::IThreadPool,
+        task: *mut diligent_sys::IAsyncTask,
+        prerequisites: *mut *mut diligent_sys::IAsyncTask,
+        num_prerequisites: u32,
+    ) {
+        let pool = unsafe { Self::from_sys(this) };
+
+        let add_ref = |task: *mut diligent_sys::IAsyncTask| {
+            Boxed::from_ref(unsafe { &*(task as *const Object) })
+        };
+
+        let prerequisites = if prerequisites.is_null() {
+            Vec::new()
+        } else {
+            unsafe { std::slice::from_raw_parts(prerequisites, num_prerequisites as usize) }
+                .iter()
+                .filter(|prerequisite| !prerequisite.is_null())
+                .map(|&prerequisite| add_ref(prerequisite))
+                .collect()
+        };
+
+        pool.state().queue.push(TaskEntry {
+            task: add_ref(task),
+            prerequisites,
+        });
+
+        pool.state_changed.notify_all();
+        pool.dispatch();
+    }
+
+    unsafe extern "C" fn reprioritize_task(
+        this: *mut diligent_sys::IThreadPool,
+        task: *mut diligent_sys::IAsyncTask,
+    ) -> bool {
+        // The priorities are read when the tasks are dispatched
+        let pool = unsafe { Self::from_sys(this) };
+        pool.state().position(task).is_some()
+    }
+
+    unsafe extern "C" fn reprioritize_all_tasks(_this: *mut diligent_sys::IThreadPool) {}
+
+    unsafe extern "C" fn remove_task(
+        this: *mut diligent_sys::IThreadPool,
+        task: *mut diligent_sys::IAsyncTask,
+    ) -> bool {
+        let pool = unsafe { Self::from_sys(this) };
+
+        let mut state = pool.state();
+        let removed = state.position(task).map(|index| state.queue.remove(index));
+        drop(state);
+
+        removed.is_some()
+    }
+
+    unsafe extern "C" fn wait_for_all_tasks(this: *mut diligent_sys::IThreadPool) {
+        let pool = unsafe { Self::from_sys(this) };
+
+        // Once the pool is stopped, the queued tasks are never run and only the running ones are waited for
+        let mut state = pool.state();
+        while (!state.stopped && !state.queue.is_empty()) || state.running > 0 {
+            state = pool
+                .state_changed
+                .wait(state)
+                .unwrap_or_else(|poisoned| poisoned.into_inner());
+        }
+    }
+
+    unsafe extern "C" fn get_queue_size(this: *mut diligent_sys::IThreadPool) -> u32 {
+        let pool = unsafe { Self::from_sys(this) };
+        pool.state().queue.len() as u32
+    }
+
+    unsafe extern "C" fn get_running_task_count(this: *mut diligent_sys::IThreadPool) -> u32 {
+        let pool = unsafe { Self::from_sys(this) };
+        pool.state().running
+    }
+
+    unsafe extern "C" fn stop_threads(this: *mut diligent_sys::IThreadPool) {
+        let pool = unsafe { Self::from_sys(this) };
+
+        let mut state = pool.state();
+        state.stopped = true;
+        let cancelled = std::mem::take(&mut state.queue);
+        drop(state);
+
+        for entry in cancelled {
+            entry.task.set_status(AsyncTaskStatus::Cancelled);
+        }
+
+        pool.state_changed.notify_all();
+    }
+
+    unsafe extern "C" fn process_task(
+        this: *mut diligent_sys::IThreadPool,
+        thread_id: u32,
+        wait_for_task: bool,
+    ) -> bool {
+        let pool = unsafe { Self::from_sys(this) };
+
+        let entry = {
+            let mut state = pool.state();
+            loop {
+                if let Some(entry) = state.take_ready_task() {
+                    break Some(entry);
+                }
+                if !wait_for_task || state.stopped || state.queue.is_empty() {
+                    break None;
+                }
+                state = pool
+                    .state_changed
+                    .wait(state)
+                    .unwrap_or_else(|poisoned| poisoned.into_inner());
+            }
+        };
+
+        entry.is_some_and(|entry| {
+            pool.run_task(entry, thread_id);
+            true
+        })
+    }
+}
+
+impl ThreadPool {
+    /// Creates a thread pool that runs the engine tasks on `executor`.
+    pub fn from_executor<E: ThreadPoolExecutor>(executor: E) -> Boxed<Self> {
+        let thread_pool_ptr = ThreadPoolObject::<E>::create(
+            diligent_sys::IThreadPoolVtbl {
+                Object: ThreadPoolObject::<E>::OBJECT_METHODS,
+                ThreadPool: diligent_sys::IThreadPoolMethods {
+                    EnqueueTask: Some(RustThreadPool::<E>::enqueue_task),
+                    ReprioritizeTask: Some(RustThreadPool::<E>::reprioritize_task),
+                    ReprioritizeAllTasks: Some(RustThreadPool::<E>::reprioritize_all_tasks),
+                    RemoveTask: Some(RustThreadPool::<E>::remove_task),
+                    WaitForAllTasks: Some(RustThreadPool::<E>::wait_for_all_tasks),
+                    GetQueueSize: Some(RustThreadPool::<E>::get_queue_size),
+                    GetRunningTaskCount: Some(RustThreadPool::<E>::get_running_task_count),
+                    StopThreads: Some(RustThreadPool::<E>::stop_threads),
+                    ProcessTask: Some(RustThreadPool::<E>::process_task),
+                },
+            },
+            RustThreadPool {
+                pool: Arc::new(SharedPool {
+                    executor,
+                    state: Mutex::new(PoolState::default()),
+                    state_changed: Condvar::new(),
+                }),
+            },
+        );
+
+        // The object was just allocated, the pointer can not be null
+        Boxed::new(thread_pool_ptr).unwrap()
+    }
+}
+
+/// Runs the engine tasks on a rayon thread pool.
+#[cfg(feature = "rayon")]
+pub struct RayonExecutor(pub Arc<rayon::ThreadPool>);
+
+#[cfg(feature = "rayon")]
+impl ThreadPoolExecutor for RayonExecutor {
+    fn execute(&self, job: Box<dyn FnOnce(u32) + Send>) {
+        self.0.spawn(move || {
+            let thread_id = rayon::current_thread_index().unwrap_or_default();
+            job(thread_id as u32)
+        });
+    }
+}
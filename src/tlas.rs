@@ -15,6 +15,7 @@ use crate::{
         ResourceStateVerify, ResourceTransition,
     },
     graphics_types::ResourceState,
+    object::Interface,
 };
 
 pub const TLAS_INSTANCE_DATA_SIZE: u32 = diligent_sys::DILIGENT_TLAS_INSTANCE_DATA_SIZE;
@@ -213,6 +214,15 @@ define_ported!(
     DeviceObject
 );
 
+impl Interface for TopLevelAS {
+    const IID: diligent_sys::INTERFACE_ID = diligent_sys::INTERFACE_ID {
+        Data1: 0x16561861,
+        Data2: 0x294b,
+        Data3: 0x4804,
+        Data4: [0x96, 0xfa, 0x17, 0x17, 0x33, 0x3f, 0x76, 0x9a],
+    };
+}
+
 impl TopLevelAS {
     pub fn desc(&self) -> &TopLevelASDesc<'_> {
         let desc_ptr = unsafe_member_call!(self, DeviceObject, GetDesc);
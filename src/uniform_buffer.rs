@@ -0,0 +1,246 @@
+use std::{
+    ffi::CStr,
+    fmt::Display,
+    marker::PhantomData,
+    ops::{Deref, DerefMut},
+};
+
+use bytemuck::Pod;
+
+use crate::{
+    Boxed, Error,
+    buffer::{Buffer, BufferDesc, BufferMapWriteToken},
+    device_context::DeviceContext,
+    graphics_types::{BindFlags, CpuAccessFlags, MapFlags, Usage},
+    render_device::RenderDevice,
+    shader::{Shader, ShaderCodeBufferDesc, ShaderResourceType},
+};
+
+/// The layout of a Rust struct that does not match the constant buffer declared in a shader.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum UniformLayoutMismatch {
+    /// The sizes differ, once rounded up to the 16 bytes of a constant buffer register.
+    Size { rust_size: usize, shader_size: u32 },
+    /// The shader declares no constant buffer with this name.
+    MissingBuffer { name: String },
+    /// The shader was created without loading the constant buffer reflection.
+    NoReflection { name: String },
+    /// The constant buffer has no variable with the name of a field.
+    MissingField { field: String },
+    /// No field is given for a variable of the constant buffer.
+    UncheckedVariable { variable: String },
+    FieldOffset {
+        field: String,
+        rust_offset: usize,
+        shader_offset: u32,
+    },
+}
+
+impl std::error::Error for UniformLayoutMismatch {}
+
+impl Display for UniformLayoutMismatch {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            UniformLayoutMismatch::Size {
+                rust_size,
+                shader_size,
+            } => write!(
+                f,
+                "the Rust struct is {rust_size} bytes but the constant buffer is {shader_size} bytes"
+            ),
+            UniformLayoutMismatch::MissingBuffer { name } => {
+                write!(f, "the shader has no constant buffer named '{name}'")
+            }
+            UniformLayoutMismatch::NoReflection { name } => write!(
+                f,
+                "the reflection of the constant buffer '{name}' was not loaded with the shader"
+            ),
+            UniformLayoutMismatch::UncheckedVariable { variable } => {
+                write!(f, "no field is given for the variable '{variable}'")
+            }
+            UniformLayoutMismatch::MissingField { field } => {
+                write!(f, "the constant buffer has no variable named '{field}'")
+            }
+            UniformLayoutMismatch::FieldOffset {
+                field,
+                rust_offset,
+                shader_offset,
+            } => write!(
+                f,
+                "'{field}' is at offset {rust_offset} in the Rust struct but at offset {shader_offset} in the constant buffer"
+            ),
+        }
+    }
+}
+
+/// A uniform buffer that holds a single `T`.
+pub struct UniformBuffer<T: Pod> {
+    buffer: Boxed<Buffer>,
+    phantom: PhantomData<T>,
+}
+
+#[bon::bon]
+impl<T: Pod> UniformBuffer<T> {
+    #[builder]
+    pub fn new(
+        render_device: &RenderDevice,
+
+        name: Option<&CStr>,
+
+        #[builder(default = Usage::Dynamic)] usage: Usage,
+
+        initial_value: Option<&T>,
+    ) -> Result<Self, Error> {
+        let buffer_desc = BufferDesc::builder()
+            .maybe_name(name)
+            .size(size_of::<T>() as u64)
+            .bind_flags(BindFlags::UniformBuffer)
+            .usage(usage)
+            .cpu_access_flags(match usage {
+                Usage::Dynamic => CpuAccessFlags::Write,
+                _ => CpuAccessFlags::None,
+            })
+            .build();
+
+        let buffer = match initial_value {
+            Some(value) => render_device.create_buffer_with_data(&buffer_desc, value, None)?,
+            None => render_device.create_buffer(&buffer_desc)?,
+        };
+
+        Ok(UniformBuffer {
+            buffer,
+            phantom: PhantomData,
+        })
+    }
+}
+
+impl<T: Pod> UniformBuffer<T> {
+    pub fn buffer(&self) -> &Buffer {
+        &self.buffer
+    }
+
+    pub fn buffer_mut(&mut self) -> &mut Buffer {
+        &mut self.buffer
+    }
+
+    /// Replaces the content of the buffer : dynamic buffers are mapped with `MapFlags::Discard`,
+    /// the others are updated with `DeviceContext::update_buffer`.
+    pub fn write(&mut self, device_context: &DeviceContext, value: &T) -> Result<(), Error> {
+        if let Usage::Dynamic = self.buffer.desc().usage() {
+            *self.map(device_context)? = *value;
+        } else {
+            device_context.update_buffer(
+                self.buffer.transition_state(),
+                0,
+                size_of::<T>() as u64,
+                value,
+            );
+        }
+        Ok(())
+    }
+
+    /// Maps a dynamic buffer with `MapFlags::Discard` : the previous content is lost.
+    pub fn map<'context, 'buffer>(
+        &'buffer self,
+        device_context: &'context DeviceContext,
+    ) -> Result<UniformBufferMapToken<'context, 'buffer, T>, Error> {
+        Ok(UniformBufferMapToken(
+            device_context.map_buffer_write(&self.buffer, MapFlags::Discard)?,
+        ))
+    }
+
+    /// Checks that the size of `T`, and the offsets of `fields`, match the constant buffer
+    /// described by `buffer_desc`.
+    ///
+    /// `fields` are the names of the variables of the constant buffer, with the offset of the
+    /// field of `T` they are stored in, as given by `std::mem::offset_of!`. Every variable of the
+    /// constant buffer must be in `fields`.
+    pub fn check_layout(
+        buffer_desc: &ShaderCodeBufferDesc,
+        fields: &[(&str, usize)],
+    ) -> Result<(), UniformLayoutMismatch> {
+        if size_of::<T>().next_multiple_of(16) != (buffer_desc.size() as usize).next_multiple_of(16)
+        {
+            return Err(UniformLayoutMismatch::Size {
+                rust_size: size_of::<T>(),
+                shader_size: buffer_desc.size(),
+            });
+        }
+
+        if let Some(variable) = buffer_desc.variables().iter().find(|variable| {
+            !fields
+                .iter()
+                .any(|&(field, _)| variable.name() == Some(field))
+        }) {
+            return Err(UniformLayoutMismatch::UncheckedVariable {
+                variable: variable.name().unwrap_or_default().to_owned(),
+            });
+        }
+
+        fields.iter().try_for_each(|&(field, rust_offset)| {
+            let variable = buffer_desc
+                .variables()
+                .iter()
+                .find(|variable| variable.name() == Some(field))
+                .ok_or_else(|| UniformLayoutMismatch::MissingField {
+                    field: field.to_owned(),
+                })?;
+
+            if variable.offset() as usize == rust_offset {
+                Ok(())
+            } else {
+                Err(UniformLayoutMismatch::FieldOffset {
+                    field: field.to_owned(),
+                    rust_offset,
+                    shader_offset: variable.offset(),
+                })
+            }
+        })
+    }
+
+    /// Checks the layout of `T` against the constant buffer `name` of `shader`, like `check_layout`.
+    ///
+    /// Fails if the shader does not declare the constant buffer, or if it was created
+    /// without loading the constant buffer reflection.
+    pub fn check_shader_layout(
+        shader: &Shader,
+        name: &CStr,
+        fields: &[(&str, usize)],
+    ) -> Result<(), UniformLayoutMismatch> {
+        let index = shader
+            .resources()
+            .position(|resource| {
+                matches!(resource.resource_type(), ShaderResourceType::ConstantBuffer)
+                    && resource.name() == name
+            })
+            .ok_or_else(|| UniformLayoutMismatch::MissingBuffer {
+                name: name.to_string_lossy().into_owned(),
+            })?;
+
+        let buffer_desc = shader
+            .get_constant_buffer_desc(index as u32)
+            .ok_or_else(|| UniformLayoutMismatch::NoReflection {
+                name: name.to_string_lossy().into_owned(),
+            })?;
+
+        Self::check_layout(buffer_desc, fields)
+    }
+}
+
+/// The mapped content of a `UniformBuffer`, unmapped when dropped.
+pub struct UniformBufferMapToken<'context, 'buffer, T: Pod>(
+    BufferMapWriteToken<'context, 'buffer, T>,
+);
+
+impl<T: Pod> Deref for UniformBufferMapToken<'_, '_, T> {
+    type Target = T;
+    fn deref(&self) -> &Self::Target {
+        &self.0[0]
+    }
+}
+
+impl<T: Pod> DerefMut for UniformBufferMapToken<'_, '_, T> {
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        &mut self.0[0]
+    }
+}
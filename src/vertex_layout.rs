@@ -0,0 +1,92 @@
+use crate::{graphics_types::ValueType, input_layout::LayoutElement};
+
+/// The format of a field of a vertex struct, as read by the input assembler.
+pub trait VertexAttribute {
+    const VALUE_TYPE: ValueType;
+    const NUM_COMPONENTS: u32;
+    const IS_NORMALIZED: bool;
+    /// The number of consecutive attributes the field occupies, such as the rows of a matrix.
+    const ATTRIBUTE_COUNT: u32 = 1;
+}
+
+/// A vertex struct whose fields are the attributes of an input layout.
+///
+/// It is implemented by `#[derive(VertexLayout)]` with the `derive` feature, for `#[repr(C)]` structs
+/// whose fields implement `VertexAttribute`. The derive accepts these attributes :
+/// * `#[vertex(per_instance)]` on the struct reads it once per instance instead of once per vertex,
+///   and `#[vertex(per_instance, step_rate = N)]` once every N instances.
+/// * `#[vertex(normalized)]` or `#[vertex(normalized = false)]` on a field overrides the normalization of its type.
+/// * `#[vertex(skip)]` on a field leaves it out of the layout, like padding.
+pub trait VertexLayout {
+    /// The number of input indices used by the elements of the layout.
+    const ATTRIBUTE_COUNT: u32;
+
+    /// The elements of the fields, read from the buffer bound to `slot`,
+    /// with consecutive input indices starting at `first_input_index`.
+    fn layout_elements(slot: u32, first_input_index: u32) -> Vec<LayoutElement>;
+}
+
+macro_rules! impl_vertex_attribute {
+    ($type:ty, $value_type:expr, $num_components:expr, $normalized:expr) => {
+        impl_vertex_attribute!($type, $value_type, $num_components, $normalized, 1);
+    };
+    ($type:ty, $value_type:expr, $num_components:expr, $normalized:expr, $attribute_count:expr) => {
+        impl VertexAttribute for $type {
+            const VALUE_TYPE: ValueType = $value_type;
+            const NUM_COMPONENTS: u32 = $num_components;
+            const IS_NORMALIZED: bool = $normalized;
+            const ATTRIBUTE_COUNT: u32 = $attribute_count;
+        }
+    };
+}
+
+macro_rules! impl_vertex_attribute_arrays {
+    ($scalar:ty, $value_type:expr, $normalized:expr) => {
+        impl_vertex_attribute!($scalar, $value_type, 1, $normalized);
+        impl_vertex_attribute!([$scalar; 1], $value_type, 1, $normalized);
+        impl_vertex_attribute!([$scalar; 2], $value_type, 2, $normalized);
+        impl_vertex_attribute!([$scalar; 4], $value_type, 4, $normalized);
+    };
+}
+
+// The 8 and 16 bit integers are normalized, like colors. The 32 bit integers can not be.
+impl_vertex_attribute_arrays!(i8, ValueType::Int8, true);
+impl_vertex_attribute_arrays!(u8, ValueType::Uint8, true);
+impl_vertex_attribute_arrays!(i16, ValueType::Int16, true);
+impl_vertex_attribute_arrays!(u16, ValueType::Uint16, true);
+impl_vertex_attribute_arrays!(i32, ValueType::Int32, false);
+impl_vertex_attribute_arrays!(u32, ValueType::Uint32, false);
+impl_vertex_attribute_arrays!(f32, ValueType::Float32, false);
+
+// There are no 3 component formats of 8 or 16 bit components
+impl_vertex_attribute!([i32; 3], ValueType::Int32, 3, false);
+impl_vertex_attribute!([u32; 3], ValueType::Uint32, 3, false);
+impl_vertex_attribute!([f32; 3], ValueType::Float32, 3, false);
+
+impl_vertex_attribute!([[f32; 2]; 2], ValueType::Float32, 2, false, 2);
+impl_vertex_attribute!([[f32; 3]; 3], ValueType::Float32, 3, false, 3);
+impl_vertex_attribute!([[f32; 4]; 4], ValueType::Float32, 4, false, 4);
+
+#[cfg(feature = "glam")]
+mod glam_attributes {
+    use super::VertexAttribute;
+    use crate::graphics_types::ValueType;
+
+    impl_vertex_attribute!(glam::Vec2, ValueType::Float32, 2, false);
+    impl_vertex_attribute!(glam::Vec3, ValueType::Float32, 3, false);
+    impl_vertex_attribute!(glam::Vec4, ValueType::Float32, 4, false);
+    impl_vertex_attribute!(glam::Quat, ValueType::Float32, 4, false);
+
+    impl_vertex_attribute!(glam::IVec2, ValueType::Int32, 2, false);
+    impl_vertex_attribute!(glam::IVec3, ValueType::Int32, 3, false);
+    impl_vertex_attribute!(glam::IVec4, ValueType::Int32, 4, false);
+
+    impl_vertex_attribute!(glam::UVec2, ValueType::Uint32, 2, false);
+    impl_vertex_attribute!(glam::UVec3, ValueType::Uint32, 3, false);
+    impl_vertex_attribute!(glam::UVec4, ValueType::Uint32, 4, false);
+
+    // The matrices are read column by column
+    impl_vertex_attribute!(glam::Mat2, ValueType::Float32, 2, false, 2);
+    impl_vertex_attribute!(glam::Mat3, ValueType::Float32, 3, false, 3);
+    impl_vertex_attribute!(glam::Mat4, ValueType::Float32, 4, false, 4);
+}
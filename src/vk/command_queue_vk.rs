@@ -1,4 +1,4 @@
-use crate::command_queue::CommandQueue;
+use crate::{command_queue::CommandQueue, object::Interface};
 
 define_ported!(
     CommandQueueVk,
@@ -7,6 +7,15 @@ define_ported!(
     CommandQueue
 );
 
+impl Interface for CommandQueueVk {
+    const IID: diligent_sys::INTERFACE_ID = diligent_sys::INTERFACE_ID {
+        Data1: 0x9fbf582f,
+        Data2: 0x3069,
+        Data3: 0x41b9,
+        Data4: [0xac, 0x05, 0x34, 0x4d, 0x5a, 0xf5, 0xce, 0x8c],
+    };
+}
+
 impl CommandQueueVk {
     /// # Safety
     /// cmd_buffer is a pointer. The user of this function must make sure that it is valid.
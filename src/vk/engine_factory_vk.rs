@@ -4,11 +4,13 @@ use std::ops::DerefMut;
 use std::path::PathBuf;
 
 use crate::Boxed;
-use crate::BoxedFromNulError;
 use crate::EngineFactory;
+use crate::Error;
 use crate::Ported;
+use crate::error::{MessageCapture, Operation};
 use crate::graphics_types::Version;
 use crate::swap_chain::SwapChainCreateInfo;
+use crate::{abi::debug_verify_abi, debug_output::install_message_hook};
 use crate::{
     device_context::DeferredDeviceContext, device_context::ImmediateDeviceContext,
     engine_factory::EngineCreateInfo, graphics_types::DeviceFeatureState,
@@ -29,8 +31,8 @@ impl DeviceFeaturesVk {
     }
 }
 
-pub struct EngineVkCreateInfo<'immediate_context_info, 'xr_attribs> {
-    engine_create_info: EngineCreateInfo<'immediate_context_info, 'xr_attribs>,
+pub struct EngineVkCreateInfo<'immediate_context_info, 'xr_attribs, 'thread_pool> {
+    engine_create_info: EngineCreateInfo<'immediate_context_info, 'xr_attribs, 'thread_pool>,
 
     pub features_vk: DeviceFeaturesVk,
 
@@ -56,26 +58,28 @@ pub struct EngineVkCreateInfo<'immediate_context_info, 'xr_attribs> {
     pub dx_compiler_path: Option<PathBuf>,
 }
 
-impl<'immediate_context_info, 'xr_attribs> Deref
-    for EngineVkCreateInfo<'immediate_context_info, 'xr_attribs>
+impl<'immediate_context_info, 'xr_attribs, 'thread_pool> Deref
+    for EngineVkCreateInfo<'immediate_context_info, 'xr_attribs, 'thread_pool>
 {
-    type Target = EngineCreateInfo<'immediate_context_info, 'xr_attribs>;
+    type Target = EngineCreateInfo<'immediate_context_info, 'xr_attribs, 'thread_pool>;
 
     fn deref(&self) -> &Self::Target {
         &self.engine_create_info
     }
 }
 
-impl DerefMut for EngineVkCreateInfo<'_, '_> {
+impl DerefMut for EngineVkCreateInfo<'_, '_, '_> {
     fn deref_mut(&mut self) -> &mut Self::Target {
         &mut self.engine_create_info
     }
 }
 
-impl<'immediate_context_info, 'xr_attribs>
-    EngineVkCreateInfo<'immediate_context_info, 'xr_attribs>
+impl<'immediate_context_info, 'xr_attribs, 'thread_pool>
+    EngineVkCreateInfo<'immediate_context_info, 'xr_attribs, 'thread_pool>
 {
-    pub fn new(engine_create_info: EngineCreateInfo<'immediate_context_info, 'xr_attribs>) -> Self {
+    pub fn new(
+        engine_create_info: EngineCreateInfo<'immediate_context_info, 'xr_attribs, 'thread_pool>,
+    ) -> Self {
         EngineVkCreateInfo {
             engine_create_info,
 
@@ -138,7 +142,7 @@ impl<'immediate_context_info, 'xr_attribs>
     }
 }
 
-impl Default for EngineVkCreateInfo<'_, '_> {
+impl Default for EngineVkCreateInfo<'_, '_, '_> {
     fn default() -> Self {
         EngineVkCreateInfo::new(EngineCreateInfo::builder().build())
     }
@@ -154,7 +158,10 @@ define_ported!(
 pub fn get_engine_factory_vk() -> Boxed<EngineFactoryVk> {
     let engine_factory_vk = unsafe { diligent_sys::Diligent_GetEngineFactoryVk() };
 
-    Boxed::new(engine_factory_vk as _).unwrap()
+    let engine_factory_vk = Boxed::<EngineFactoryVk>::new(engine_factory_vk as _).unwrap();
+    install_message_hook(&engine_factory_vk);
+    debug_verify_abi(&engine_factory_vk);
+    engine_factory_vk
 }
 
 impl EngineFactoryVk {
@@ -167,7 +174,7 @@ impl EngineFactoryVk {
             Vec<Boxed<ImmediateDeviceContext>>,
             Vec<Boxed<DeferredDeviceContext>>,
         ),
-        BoxedFromNulError,
+        Error,
     > {
         let num_immediate_contexts = create_info
             .engine_create_info
@@ -183,6 +190,7 @@ impl EngineFactoryVk {
             num_immediate_contexts + num_deferred_contexts,
         ));
 
+        let capture = MessageCapture::begin();
         {
             let instance_layer_names = create_info
                 .instance_layer_names
@@ -274,24 +282,26 @@ impl EngineFactoryVk {
             )
         }
 
-        Boxed::new(render_device_ptr).and_then(|render_device| {
-            device_context_ptrs
-                .iter()
-                .take(num_immediate_contexts)
-                .map(|&dc_ptr| Boxed::new(dc_ptr))
-                .collect::<Result<Vec<_>, _>>()
-                .and_then(|immediate_devices| {
-                    device_context_ptrs
-                        .iter()
-                        .rev()
-                        .take(num_deferred_contexts)
-                        .map(|&dc_ptr| Boxed::new(dc_ptr))
-                        .collect::<Result<Vec<_>, _>>()
-                        .map(|deferred_devices| {
-                            (render_device, immediate_devices, deferred_devices)
-                        })
-                })
-        })
+        Boxed::new(render_device_ptr)
+            .and_then(|render_device| {
+                device_context_ptrs
+                    .iter()
+                    .take(num_immediate_contexts)
+                    .map(|&dc_ptr| Boxed::new(dc_ptr))
+                    .collect::<Option<Vec<_>>>()
+                    .and_then(|immediate_devices| {
+                        device_context_ptrs
+                            .iter()
+                            .rev()
+                            .take(num_deferred_contexts)
+                            .map(|&dc_ptr| Boxed::new(dc_ptr))
+                            .collect::<Option<Vec<_>>>()
+                            .map(|deferred_devices| {
+                                (render_device, immediate_devices, deferred_devices)
+                            })
+                    })
+            })
+            .ok_or_else(|| capture.null_object(Operation::CreateDeviceAndContexts, None))
     }
 
     pub fn create_swap_chain(
@@ -300,9 +310,10 @@ impl EngineFactoryVk {
         immediate_context: &ImmediateDeviceContext,
         swapchain_ci: &SwapChainCreateInfo,
         window: &NativeWindow,
-    ) -> Result<Boxed<SwapChain>, BoxedFromNulError> {
+    ) -> Result<Boxed<SwapChain>, Error> {
         let mut swap_chain_ptr = std::ptr::null_mut();
 
+        let capture = MessageCapture::begin();
         unsafe_member_call!(
             self,
             EngineFactoryVk,
@@ -314,7 +325,7 @@ impl EngineFactoryVk {
             &mut swap_chain_ptr
         );
 
-        Boxed::new(swap_chain_ptr)
+        capture.boxed(swap_chain_ptr, Operation::CreateSwapChain, None)
     }
 
     pub fn enable_device_simulation(&self) {
@@ -1,6 +1,7 @@
 use crate::{
-    BottomLevelAS, BottomLevelASDesc, Boxed, BoxedFromNulError, TopLevelAS, TopLevelASDesc,
+    BottomLevelAS, BottomLevelASDesc, Boxed, Error, TopLevelAS, TopLevelASDesc,
     buffer::{Buffer, BufferDesc},
+    error::{MessageCapture, Operation},
     fence::{Fence, FenceDesc},
     graphics_types::ResourceState,
     render_device::RenderDevice,
@@ -38,9 +39,10 @@ impl RenderDeviceVk {
         vk_image: diligent_sys::VkImage,
         texture_desc: &TextureDesc,
         initial_state: ResourceState,
-    ) -> Result<Boxed<Texture>, BoxedFromNulError> {
+    ) -> Result<Boxed<Texture>, Error> {
         let mut texture_ptr = std::ptr::null_mut();
 
+        let capture = MessageCapture::begin();
         unsafe_member_call!(
             self,
             RenderDeviceVk,
@@ -51,7 +53,7 @@ impl RenderDeviceVk {
             &mut texture_ptr
         );
 
-        Boxed::new(texture_ptr)
+        capture.boxed(texture_ptr, Operation::CreateTexture, texture_desc.name())
     }
 
     /// # Safety
@@ -61,9 +63,10 @@ impl RenderDeviceVk {
         vk_buffer: diligent_sys::VkBuffer,
         buffer_desc: &BufferDesc,
         initial_state: ResourceState,
-    ) -> Result<Boxed<Buffer>, BoxedFromNulError> {
+    ) -> Result<Boxed<Buffer>, Error> {
         let mut buffer_ptr = std::ptr::null_mut();
 
+        let capture = MessageCapture::begin();
         unsafe_member_call!(
             self,
             RenderDeviceVk,
@@ -74,7 +77,7 @@ impl RenderDeviceVk {
             &mut buffer_ptr
         );
 
-        Boxed::new(buffer_ptr)
+        capture.boxed(buffer_ptr, Operation::CreateBuffer, buffer_desc.name())
     }
 
     /// # Safety
@@ -84,9 +87,10 @@ impl RenderDeviceVk {
         vk_blas: diligent_sys::VkAccelerationStructureKHR,
         blas_desc: &BottomLevelASDesc,
         initial_state: ResourceState,
-    ) -> Result<Boxed<BottomLevelAS>, BoxedFromNulError> {
+    ) -> Result<Boxed<BottomLevelAS>, Error> {
         let mut bottom_level_as_ptr = std::ptr::null_mut();
 
+        let capture = MessageCapture::begin();
         unsafe_member_call!(
             self,
             RenderDeviceVk,
@@ -97,7 +101,7 @@ impl RenderDeviceVk {
             &mut bottom_level_as_ptr
         );
 
-        Boxed::new(bottom_level_as_ptr)
+        capture.boxed(bottom_level_as_ptr, Operation::CreateBLAS, blas_desc.name())
     }
 
     /// # Safety
@@ -107,9 +111,10 @@ impl RenderDeviceVk {
         vk_tlas: diligent_sys::VkAccelerationStructureKHR,
         tlas_desc: &TopLevelASDesc,
         initial_state: ResourceState,
-    ) -> Result<Boxed<TopLevelAS>, BoxedFromNulError> {
+    ) -> Result<Boxed<TopLevelAS>, Error> {
         let mut top_level_as_ptr = std::ptr::null_mut();
 
+        let capture = MessageCapture::begin();
         unsafe_member_call!(
             self,
             RenderDeviceVk,
@@ -120,7 +125,7 @@ impl RenderDeviceVk {
             &mut top_level_as_ptr
         );
 
-        Boxed::new(top_level_as_ptr)
+        capture.boxed(top_level_as_ptr, Operation::CreateTLAS, tlas_desc.name())
     }
 
     /// # Safety
@@ -129,9 +134,10 @@ impl RenderDeviceVk {
         &self,
         vk_timeline_semaphore: diligent_sys::VkSemaphore,
         fence_desc: &FenceDesc,
-    ) -> Result<Boxed<Fence>, BoxedFromNulError> {
+    ) -> Result<Boxed<Fence>, Error> {
         let mut fence_ptr = std::ptr::null_mut();
 
+        let capture = MessageCapture::begin();
         unsafe_member_call!(
             self,
             RenderDeviceVk,
@@ -141,7 +147,7 @@ impl RenderDeviceVk {
             &mut fence_ptr
         );
 
-        Boxed::new(fence_ptr)
+        capture.boxed(fence_ptr, Operation::CreateFence, fence_desc.name())
     }
 
     // TODO
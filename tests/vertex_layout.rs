@@ -0,0 +1,104 @@
+#![cfg(feature = "derive")]
+
+use std::mem::{offset_of, size_of};
+
+use diligent::{InputElementFrequency, LayoutElement, ValueType, VertexLayout};
+
+#[repr(C)]
+#[derive(VertexLayout)]
+struct Vertex {
+    position: [f32; 3],
+    #[vertex(skip)]
+    _padding: f32,
+    color: [u8; 4],
+    #[vertex(normalized = false)]
+    bone_indices: [u16; 2],
+    uv: [f32; 2],
+}
+
+#[repr(C)]
+#[derive(VertexLayout)]
+#[vertex(per_instance, step_rate = 2)]
+struct Instance {
+    transform: [[f32; 4]; 4],
+    tint: [u8; 4],
+}
+
+fn offsets(elements: &[LayoutElement]) -> Vec<u32> {
+    elements
+        .iter()
+        .map(LayoutElement::relative_offset)
+        .collect()
+}
+
+#[test]
+fn vertex_offsets_and_strides() {
+    let elements = Vertex::layout_elements(0, 0);
+
+    assert_eq!(Vertex::ATTRIBUTE_COUNT, 4);
+    assert_eq!(
+        offsets(&elements),
+        [
+            offset_of!(Vertex, position) as u32,
+            offset_of!(Vertex, color) as u32,
+            offset_of!(Vertex, bone_indices) as u32,
+            offset_of!(Vertex, uv) as u32,
+        ]
+    );
+    assert!(
+        elements
+            .iter()
+            .all(|element| element.stride() == size_of::<Vertex>() as u32)
+    );
+
+    assert_eq!(
+        elements
+            .iter()
+            .map(LayoutElement::input_index)
+            .collect::<Vec<_>>(),
+        [0, 1, 2, 3]
+    );
+    assert!(matches!(elements[1].value_type(), ValueType::Uint8));
+    assert!(elements[1].is_normalized());
+    assert!(matches!(elements[2].value_type(), ValueType::Uint16));
+    assert!(!elements[2].is_normalized());
+    assert!(
+        elements
+            .iter()
+            .all(|element| matches!(element.frequency(), InputElementFrequency::PerVertex))
+    );
+}
+
+#[test]
+fn instance_offsets_and_strides() {
+    let elements = Instance::layout_elements(1, 4);
+
+    // The matrix takes one attribute per row
+    let transform = offset_of!(Instance, transform) as u32;
+    let row_size = size_of::<[f32; 4]>() as u32;
+
+    assert_eq!(Instance::ATTRIBUTE_COUNT, 5);
+    assert_eq!(
+        offsets(&elements),
+        [
+            transform,
+            transform + row_size,
+            transform + 2 * row_size,
+            transform + 3 * row_size,
+            offset_of!(Instance, tint) as u32,
+        ]
+    );
+    assert!(elements.iter().all(|element| {
+        element.stride() == size_of::<Instance>() as u32
+            && element.slot() == 1
+            && matches!(element.frequency(), InputElementFrequency::PerInstance)
+            && element.instance_data_step_rate() == 2
+    }));
+    assert_eq!(
+        elements
+            .iter()
+            .map(LayoutElement::input_index)
+            .collect::<Vec<_>>(),
+        [4, 5, 6, 7, 8]
+    );
+}